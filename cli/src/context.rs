@@ -0,0 +1,75 @@
+use crate::output::OutputFormat;
+use anchor_lang::prelude::Pubkey;
+use anyhow::{anyhow, Context as _, Result};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+use solana_sdk::transaction::Transaction;
+
+pub struct Context {
+    pub rpc: RpcClient,
+    pub payer: Keypair,
+    pub program_id: Pubkey,
+    pub output: OutputFormat,
+    pub dry_run: bool,
+}
+
+impl Context {
+    pub fn new(url: &str, keypair: &str, program_id: Pubkey, output: OutputFormat, dry_run: bool) -> Result<Self> {
+        let path = match keypair.strip_prefix("~/") {
+            Some(rest) => format!("{}/{}", std::env::var("HOME")?, rest),
+            None => keypair.to_string(),
+        };
+        let payer = read_keypair_file(&path).map_err(|error| anyhow!("failed to read keypair {}: {}", path, error))?;
+
+        Ok(Context {
+            rpc: RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed()),
+            payer,
+            program_id,
+            output,
+            dry_run,
+        })
+    }
+
+    // * signs with the payer and `signers`, simulating instead when running dry
+    pub fn send(&self, instructions: &[Instruction], signers: &[&Keypair]) -> Result<()> {
+        let mut all_signers: Vec<&Keypair> = vec![&self.payer];
+        all_signers.extend_from_slice(signers);
+
+        let blockhash = self.rpc.get_latest_blockhash()?;
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+
+        if self.dry_run {
+            let result = self.rpc.simulate_transaction(&transaction)?.value;
+            for log in result.logs.unwrap_or_default() {
+                println!("  {}", log);
+            }
+            return match result.err {
+                Some(error) => Err(anyhow!("simulation failed: {}", error)),
+                None => {
+                    println!("simulation succeeded, {} units consumed", result.units_consumed.unwrap_or_default());
+                    Ok(())
+                }
+            };
+        }
+
+        let signature = self
+            .rpc
+            .send_and_confirm_transaction_with_spinner(&transaction)
+            .context("transaction failed")?;
+        println!("{}", signature);
+        Ok(())
+    }
+
+    // * cluster time, used for lockups and cooldowns
+    pub fn now(&self) -> Result<i64> {
+        let slot = self.rpc.get_slot()?;
+        Ok(self.rpc.get_block_time(slot)?)
+    }
+}
@@ -0,0 +1,144 @@
+//! `merstab` command line interface for vault operators.
+
+mod context;
+mod output;
+mod vault;
+
+use anchor_lang::prelude::Pubkey;
+use clap::{Parser, Subcommand};
+use context::Context;
+use merstab_client::MangoAccounts;
+use output::OutputFormat;
+
+#[derive(Parser)]
+#[clap(name = "merstab", version, about = "Operate merstab vaults")]
+struct Opts {
+    #[clap(long, short = 'u', env = "MERSTAB_RPC_URL", default_value = "http://localhost:8899", global = true)]
+    url: String,
+
+    #[clap(long, short = 'k', env = "MERSTAB_KEYPAIR", default_value = "~/.config/solana/id.json", global = true)]
+    keypair: String,
+
+    #[clap(long, env = "MERSTAB_PROGRAM_ID", default_value_t = m_protocol::ID, global = true)]
+    program_id: Pubkey,
+
+    #[clap(long, value_enum, default_value = "table", global = true)]
+    output: OutputFormat,
+
+    /// Simulate transactions instead of sending them
+    #[clap(long, global = true)]
+    dry_run: bool,
+
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    #[clap(subcommand)]
+    Vault(VaultCommand),
+}
+
+#[derive(Subcommand)]
+pub enum VaultCommand {
+    /// Create a vault, its request queues, token account and share mint
+    Create {
+        #[clap(long)]
+        name: String,
+        /// Deposit limit in native units of the mint
+        #[clap(long)]
+        limit: u64,
+        #[clap(long)]
+        mint: Pubkey,
+    },
+    /// Print vault state, queues and optionally every user account
+    Show {
+        vault: Pubkey,
+        #[clap(long)]
+        users: bool,
+    },
+    /// Create the vault's mango account
+    CreateMango {
+        vault: Pubkey,
+        #[clap(long, default_value_t = 0)]
+        account_num: u64,
+        #[clap(flatten)]
+        mango: MangoArgs,
+    },
+    /// Update the vault balance, then the balance of every user
+    UpdateBalance {
+        vault: Pubkey,
+        #[clap(flatten)]
+        mango: MangoArgs,
+    },
+    /// Settle the stake and unstake requests that are ready
+    ProcessQueue {
+        vault: Pubkey,
+        /// Users settled per transaction
+        #[clap(long, default_value_t = 4)]
+        batch_size: usize,
+        #[clap(flatten)]
+        mango: MangoArgs,
+    },
+    /// Delegate trading on the vault's mango account
+    Delegate {
+        vault: Pubkey,
+        #[clap(long)]
+        delegate: Pubkey,
+        #[clap(flatten)]
+        mango: MangoArgs,
+    },
+    /// Revoke the delegate of the vault's mango account, signed by the guardian, admin or trader
+    RevokeDelegate {
+        vault: Pubkey,
+        #[clap(flatten)]
+        mango: MangoArgs,
+    },
+    /// Close the vault's mango account
+    CloseMango {
+        vault: Pubkey,
+        #[clap(flatten)]
+        mango: MangoArgs,
+    },
+}
+
+#[derive(clap::Args)]
+pub struct MangoArgs {
+    #[clap(long, env = "MANGO_PROGRAM_ID")]
+    mango_program: Pubkey,
+    #[clap(long, env = "MANGO_GROUP")]
+    mango_group: Pubkey,
+    #[clap(long, env = "MANGO_CACHE")]
+    mango_cache: Pubkey,
+    #[clap(long, env = "MANGO_ROOT_BANK")]
+    mango_root_bank: Pubkey,
+    #[clap(long, env = "MANGO_NODE_BANK")]
+    mango_node_bank: Pubkey,
+    #[clap(long, env = "MANGO_VAULT")]
+    mango_vault: Pubkey,
+    #[clap(long, env = "MANGO_SIGNER")]
+    mango_signer: Pubkey,
+}
+
+impl From<&MangoArgs> for MangoAccounts {
+    fn from(args: &MangoArgs) -> Self {
+        MangoAccounts {
+            program_id: args.mango_program,
+            group: args.mango_group,
+            cache: args.mango_cache,
+            root_bank: args.mango_root_bank,
+            node_bank: args.mango_node_bank,
+            vault: args.mango_vault,
+            signer: args.mango_signer,
+        }
+    }
+}
+
+fn main() -> anyhow::Result<()> {
+    let opts = Opts::parse();
+    let ctx = Context::new(&opts.url, &opts.keypair, opts.program_id, opts.output, opts.dry_run)?;
+
+    match opts.command {
+        Command::Vault(command) => vault::run(&ctx, command),
+    }
+}
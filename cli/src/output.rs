@@ -0,0 +1,241 @@
+use anchor_lang::prelude::Pubkey;
+use m_protocol::constants::PNL_SCALE;
+use m_protocol::state::{HaltReason, RequestEntry, RequestStatus, UserVaultAccount, Vault, VaultStatus};
+use serde::Serialize;
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+pub enum OutputFormat {
+    Table,
+    Json,
+}
+
+#[derive(Serialize)]
+pub struct VaultView {
+    pub address: String,
+    pub name: String,
+    pub admin: String,
+    pub trader: String,
+    pub keeper: String,
+    pub status: &'static str,
+    pub halt_reason: &'static str,
+    pub paused: u8,
+    pub limit: u64,
+    pub deposit: u64,
+    pub total_equity: u64,
+    pub previous_total_equity: u64,
+    pub day_pnl: f64,
+    pub epoch: u64,
+    pub high_water_mark: u64,
+    pub peak_share_price: u64,
+    pub accrued_fees: u64,
+    pub accrued_insurance: u64,
+    pub insurance_fund_balance: u64,
+    pub stake_queue: Vec<RequestView>,
+    pub unstake_queue: Vec<RequestView>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub users: Vec<UserView>,
+}
+
+#[derive(Serialize)]
+pub struct RequestView {
+    pub user_vault_account: String,
+    pub amount: u64,
+    pub max: bool,
+    pub epoch: u64,
+    pub timestamp: i64,
+}
+
+#[derive(Serialize)]
+pub struct UserView {
+    pub address: String,
+    pub authority: String,
+    pub deposit: u64,
+    pub equity: u64,
+    pub total_stake: u64,
+    pub total_unstake: u64,
+    pub pnl: f64,
+    pub stake_status: &'static str,
+    pub unstake_status: &'static str,
+}
+
+impl VaultView {
+    pub fn new(address: &Pubkey, vault: &Vault, stake_queue: &[RequestEntry], unstake_queue: &[RequestEntry]) -> Self {
+        VaultView {
+            address: address.to_string(),
+            name: vault.name.clone(),
+            admin: vault.admin.to_string(),
+            trader: vault.trader.to_string(),
+            keeper: vault.keeper.to_string(),
+            status: match vault.status {
+                VaultStatus::Active => "active",
+                VaultStatus::Halted => "halted",
+            },
+            halt_reason: match vault.halt_reason {
+                HaltReason::None => "none",
+                HaltReason::DailyDrawdown => "daily drawdown",
+                HaltReason::PeakDrawdown => "peak drawdown",
+                HaltReason::ZeroSharePrice => "zero share price",
+            },
+            paused: vault.paused,
+            limit: vault.limit,
+            deposit: vault.deposit,
+            total_equity: vault.total_equity,
+            previous_total_equity: vault.previous_total_equity,
+            day_pnl: pnl_ratio(vault.day_pnl),
+            epoch: vault.epoch.current,
+            high_water_mark: vault.high_water_mark,
+            peak_share_price: vault.peak_share_price,
+            accrued_fees: vault.accrued_fees,
+            accrued_insurance: vault.accrued_insurance,
+            insurance_fund_balance: vault.insurance_fund_balance,
+            stake_queue: stake_queue.iter().map(RequestView::from).collect(),
+            unstake_queue: unstake_queue.iter().map(RequestView::from).collect(),
+            users: Vec::new(),
+        }
+    }
+}
+
+impl From<&RequestEntry> for RequestView {
+    fn from(entry: &RequestEntry) -> Self {
+        RequestView {
+            user_vault_account: entry.user_vault_account.to_string(),
+            amount: entry.amount,
+            max: entry.max == 1,
+            epoch: entry.epoch,
+            timestamp: entry.timestamp,
+        }
+    }
+}
+
+impl UserView {
+    pub fn new(address: &Pubkey, user: &UserVaultAccount) -> Self {
+        UserView {
+            address: address.to_string(),
+            authority: user.authority.to_string(),
+            deposit: user.deposit,
+            equity: user.equity,
+            total_stake: user.user_total_stake,
+            total_unstake: user.user_total_unstake,
+            pnl: pnl_ratio(user.user_pnl),
+            stake_status: status_name(user.user_stake.status),
+            unstake_status: status_name(user.user_unstake.status),
+        }
+    }
+}
+
+pub fn print_vault(format: OutputFormat, view: &VaultView) -> anyhow::Result<()> {
+    if let OutputFormat::Json = format {
+        println!("{}", serde_json::to_string_pretty(view)?);
+        return Ok(());
+    }
+
+    let rows = vec![
+        ("address", view.address.clone()),
+        ("name", view.name.clone()),
+        ("admin", view.admin.clone()),
+        ("trader", view.trader.clone()),
+        ("keeper", view.keeper.clone()),
+        ("status", view.status.to_string()),
+        ("halt reason", view.halt_reason.to_string()),
+        ("paused", format!("{:#08b}", view.paused)),
+        ("limit", view.limit.to_string()),
+        ("deposit", view.deposit.to_string()),
+        ("total equity", view.total_equity.to_string()),
+        ("previous total equity", view.previous_total_equity.to_string()),
+        ("day pnl", format!("{:.4}%", view.day_pnl * 100.0)),
+        ("epoch", view.epoch.to_string()),
+        ("high water mark", view.high_water_mark.to_string()),
+        ("peak share price", view.peak_share_price.to_string()),
+        ("accrued fees", view.accrued_fees.to_string()),
+        ("accrued insurance", view.accrued_insurance.to_string()),
+        ("insurance fund balance", view.insurance_fund_balance.to_string()),
+    ];
+    print_table(
+        &["field", "value"],
+        rows.into_iter().map(|(field, value)| vec![field.to_string(), value]).collect(),
+    );
+
+    for (title, queue) in [("stake queue", &view.stake_queue), ("unstake queue", &view.unstake_queue)] {
+        println!("\n{} ({})", title, queue.len());
+        print_table(
+            &["user vault account", "amount", "max", "epoch", "timestamp"],
+            queue
+                .iter()
+                .map(|entry| {
+                    vec![
+                        entry.user_vault_account.clone(),
+                        entry.amount.to_string(),
+                        entry.max.to_string(),
+                        entry.epoch.to_string(),
+                        entry.timestamp.to_string(),
+                    ]
+                })
+                .collect(),
+        );
+    }
+
+    if !view.users.is_empty() {
+        println!("\nusers ({})", view.users.len());
+        print_table(
+            &["address", "authority", "deposit", "equity", "stake", "unstake", "pnl", "stake status", "unstake status"],
+            view.users
+                .iter()
+                .map(|user| {
+                    vec![
+                        user.address.clone(),
+                        user.authority.clone(),
+                        user.deposit.to_string(),
+                        user.equity.to_string(),
+                        user.total_stake.to_string(),
+                        user.total_unstake.to_string(),
+                        format!("{:.4}%", user.pnl * 100.0),
+                        user.stake_status.to_string(),
+                        user.unstake_status.to_string(),
+                    ]
+                })
+                .collect(),
+        );
+    }
+    Ok(())
+}
+
+fn print_table(headers: &[&str], rows: Vec<Vec<String>>) {
+    let mut widths: Vec<usize> = headers.iter().map(|header| header.len()).collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let line = |cells: Vec<&str>| {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        println!("{}", padded.join("  ").trim_end());
+    };
+
+    line(headers.to_vec());
+    let separators: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+    line(separators.iter().map(String::as_str).collect());
+    for row in &rows {
+        line(row.iter().map(String::as_str).collect());
+    }
+}
+
+fn pnl_ratio(pnl: i128) -> f64 {
+    pnl as f64 / PNL_SCALE as f64
+}
+
+fn status_name(status: RequestStatus) -> &'static str {
+    match status {
+        RequestStatus::Inactive => "inactive",
+        RequestStatus::Pending => "pending",
+        RequestStatus::Successful => "successful",
+        RequestStatus::Unsuccessful => "unsuccessful",
+        RequestStatus::Cancelled => "cancelled",
+        RequestStatus::InsufficientBalance => "insufficient balance",
+        RequestStatus::LockupActive => "lockup active",
+    }
+}
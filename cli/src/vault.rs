@@ -0,0 +1,148 @@
+use crate::context::Context;
+use crate::output::{print_vault, UserView, VaultView};
+use crate::{MangoArgs, VaultCommand};
+use anchor_lang::prelude::Pubkey;
+use anyhow::Result;
+use m_protocol::state::{RequestEntry, Vault};
+use merstab_client::instruction;
+use merstab_client::queue::{settleable_stakes, settleable_unstakes};
+use merstab_client::{
+    fetch_mango_account, fetch_stake_req, fetch_unstake_req, fetch_user_vault_account, fetch_user_vault_accounts, fetch_vault, pda,
+    queued_entries, ClientError, VaultKeys,
+};
+use solana_sdk::signature::{Keypair, Signer};
+
+// * update_user_balance instructions packed per transaction
+const USER_UPDATES_PER_TX: usize = 8;
+
+pub fn run(ctx: &Context, command: VaultCommand) -> Result<()> {
+    match command {
+        VaultCommand::Create { name, limit, mint } => create(ctx, name, limit, &mint),
+        VaultCommand::Show { vault, users } => show(ctx, &vault, users),
+        VaultCommand::CreateMango { vault, account_num, mango } => {
+            let (_, keys) = load(ctx, &vault, &mango)?;
+            ctx.send(&[instruction::create_mango_account(&keys, account_num)], &[])
+        }
+        VaultCommand::UpdateBalance { vault, mango } => update_balance(ctx, &vault, &mango),
+        VaultCommand::ProcessQueue { vault, batch_size, mango } => process_queue(ctx, &vault, batch_size.max(1), &mango),
+        VaultCommand::Delegate { vault, delegate, mango } => {
+            let (_, keys) = load(ctx, &vault, &mango)?;
+            ctx.send(&[instruction::delegate_mango_account(&keys, &delegate)], &[])
+        }
+        VaultCommand::RevokeDelegate { vault, mango } => {
+            let (_, keys) = load(ctx, &vault, &mango)?;
+            ctx.send(&[instruction::revoke_mango_delegate(&keys, &ctx.payer.pubkey())], &[])
+        }
+        VaultCommand::CloseMango { vault, mango } => {
+            let (_, keys) = load(ctx, &vault, &mango)?;
+            ctx.send(&[instruction::close_mango_account(&keys)], &[])
+        }
+    }
+}
+
+fn create(ctx: &Context, name: String, limit: u64, mint: &Pubkey) -> Result<()> {
+    let stake_req = Keypair::new();
+    let unstake_req = Keypair::new();
+    let lamports = ctx
+        .rpc
+        .get_minimum_balance_for_rent_exemption(instruction::REQUEST_ACCOUNT_SPACE)?;
+    let payer = ctx.payer.pubkey();
+    let (vault, _) = pda::vault(&ctx.program_id, &name);
+
+    ctx.send(
+        &[
+            instruction::create_request_account(&ctx.program_id, &payer, &stake_req.pubkey(), lamports),
+            instruction::create_request_account(&ctx.program_id, &payer, &unstake_req.pubkey(), lamports),
+            instruction::create_vault(
+                &ctx.program_id,
+                &payer,
+                mint,
+                &stake_req.pubkey(),
+                &unstake_req.pubkey(),
+                name,
+                limit,
+            ),
+        ],
+        &[&stake_req, &unstake_req],
+    )?;
+    println!("vault {}", vault);
+    Ok(())
+}
+
+fn show(ctx: &Context, vault_key: &Pubkey, with_users: bool) -> Result<()> {
+    let vault = fetch_vault(&ctx.rpc, &ctx.program_id, vault_key)?;
+    let stake_req = fetch_stake_req(&ctx.rpc, &ctx.program_id, &vault.stake_request_account)?;
+    let unstake_req = fetch_unstake_req(&ctx.rpc, &ctx.program_id, &vault.unstake_request_account)?;
+
+    let mut view = VaultView::new(
+        vault_key,
+        &vault,
+        &queued_entries(&stake_req.queue),
+        &queued_entries(&unstake_req.queue),
+    );
+    if with_users {
+        view.users = fetch_user_vault_accounts(&ctx.rpc, &ctx.program_id, vault_key)?
+            .iter()
+            .map(|(address, user)| UserView::new(address, user))
+            .collect();
+    }
+    print_vault(ctx.output, &view)
+}
+
+fn update_balance(ctx: &Context, vault_key: &Pubkey, mango: &MangoArgs) -> Result<()> {
+    let (_, keys) = load(ctx, vault_key, mango)?;
+    ctx.send(&[instruction::update_vault_balance(&keys)], &[])?;
+
+    let users = fetch_user_vault_accounts(&ctx.rpc, &ctx.program_id, vault_key)?;
+    for chunk in users.chunks(USER_UPDATES_PER_TX) {
+        let instructions: Vec<_> = chunk
+            .iter()
+            .map(|(_, user)| instruction::update_user_balance(&keys, &user.authority))
+            .collect();
+        ctx.send(&instructions, &[])?;
+    }
+    Ok(())
+}
+
+fn process_queue(ctx: &Context, vault_key: &Pubkey, batch_size: usize, mango: &MangoArgs) -> Result<()> {
+    let (vault, keys) = load(ctx, vault_key, mango)?;
+
+    let stake_req = fetch_stake_req(&ctx.rpc, &ctx.program_id, &vault.stake_request_account)?;
+    let stakes = authorities(ctx, &settleable_stakes(&vault, &stake_req.queue))?;
+    println!("{} stake requests ready", stakes.len());
+    // * batches find their own entries in the queue, so a dry run simulates each one against the current state
+    for chunk in stakes.chunks(batch_size) {
+        ctx.send(&[instruction::process_stake_batch(&keys, chunk)], &[])?;
+    }
+
+    let unstake_req = fetch_unstake_req(&ctx.rpc, &ctx.program_id, &vault.unstake_request_account)?;
+    let unstakes = authorities(ctx, &settleable_unstakes(&vault, &unstake_req.queue, ctx.now()?))?;
+    println!("{} unstake requests ready", unstakes.len());
+    for chunk in unstakes.chunks(batch_size) {
+        ctx.send(&[instruction::process_unstake_batch(&keys, chunk)], &[])?;
+    }
+    Ok(())
+}
+
+fn load(ctx: &Context, vault_key: &Pubkey, mango: &MangoArgs) -> Result<(Vault, VaultKeys)> {
+    let vault = fetch_vault(&ctx.rpc, &ctx.program_id, vault_key)?;
+    let keys = VaultKeys::new(ctx.program_id, *vault_key, &vault, mango.into());
+    // * the mango account only exists once create-mango ran
+    let keys = match fetch_mango_account(&ctx.rpc, &keys.mango.program_id, &keys.mango_account) {
+        Ok(mango_account) => keys.with_margin_basket(&mango_account),
+        Err(ClientError::AccountNotFound(_)) => keys,
+        Err(error) => return Err(error.into()),
+    };
+    Ok((vault, keys))
+}
+
+// * batches take the user authorities, the queue only records the user vault accounts
+fn authorities(ctx: &Context, entries: &[RequestEntry]) -> Result<Vec<Pubkey>> {
+    entries
+        .iter()
+        .map(|entry| {
+            let user = fetch_user_vault_account(&ctx.rpc, &ctx.program_id, &entry.user_vault_account)?;
+            Ok(user.authority)
+        })
+        .collect()
+}
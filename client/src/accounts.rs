@@ -0,0 +1,126 @@
+//! Fetching and decoding of program accounts.
+
+use crate::error::{ClientError, Result};
+use anchor_lang::{AccountDeserialize, Discriminator};
+use anchor_lang::prelude::Pubkey;
+use m_protocol::state::{RequestEntry, RequestQueue, StakeReq, UnstakeReq, UserVaultAccount, Vault};
+use mango::state::MangoAccount;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType};
+use solana_sdk::account::Account;
+use std::mem::size_of;
+
+pub fn decode_vault(key: &Pubkey, data: &[u8]) -> Result<Vault> {
+    decode_account(key, data)
+}
+
+pub fn decode_user_vault_account(key: &Pubkey, data: &[u8]) -> Result<UserVaultAccount> {
+    decode_account(key, data)
+}
+
+pub fn decode_stake_req(key: &Pubkey, data: &[u8]) -> Result<StakeReq> {
+    decode_zero_copy(key, data)
+}
+
+pub fn decode_unstake_req(key: &Pubkey, data: &[u8]) -> Result<UnstakeReq> {
+    decode_zero_copy(key, data)
+}
+
+// * mango accounts have no anchor discriminator, the owner check is left to the fetch
+pub fn decode_mango_account(key: &Pubkey, data: &[u8]) -> Result<MangoAccount> {
+    bytemuck::try_pod_read_unaligned(data).map_err(|error| ClientError::Decode(*key, error.to_string()))
+}
+
+pub fn fetch_vault(rpc: &RpcClient, program_id: &Pubkey, key: &Pubkey) -> Result<Vault> {
+    decode_vault(key, &fetch_owned(rpc, program_id, key)?.data)
+}
+
+pub fn fetch_user_vault_account(rpc: &RpcClient, program_id: &Pubkey, key: &Pubkey) -> Result<UserVaultAccount> {
+    decode_user_vault_account(key, &fetch_owned(rpc, program_id, key)?.data)
+}
+
+pub fn fetch_stake_req(rpc: &RpcClient, program_id: &Pubkey, key: &Pubkey) -> Result<StakeReq> {
+    decode_stake_req(key, &fetch_owned(rpc, program_id, key)?.data)
+}
+
+pub fn fetch_unstake_req(rpc: &RpcClient, program_id: &Pubkey, key: &Pubkey) -> Result<UnstakeReq> {
+    decode_unstake_req(key, &fetch_owned(rpc, program_id, key)?.data)
+}
+
+pub fn fetch_mango_account(rpc: &RpcClient, mango_program_id: &Pubkey, key: &Pubkey) -> Result<MangoAccount> {
+    decode_mango_account(key, &fetch_owned(rpc, mango_program_id, key)?.data)
+}
+
+// * every user vault account of `vault`
+pub fn fetch_user_vault_accounts(
+    rpc: &RpcClient,
+    program_id: &Pubkey,
+    vault: &Pubkey,
+) -> Result<Vec<(Pubkey, UserVaultAccount)>> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(user_vault_account_filters(vault)),
+        account_config: RpcAccountInfoConfig {
+            commitment: Some(rpc.commitment()),
+            ..RpcAccountInfoConfig::default()
+        },
+        with_context: None,
+    };
+    rpc.get_program_accounts_with_config(program_id, config)?
+        .into_iter()
+        .map(|(key, account)| Ok((key, decode_user_vault_account(&key, &account.data)?)))
+        .collect()
+}
+
+// * matches user vault accounts on the discriminator and the vault field
+pub fn user_vault_account_filters(vault: &Pubkey) -> Vec<RpcFilterType> {
+    vec![
+        RpcFilterType::Memcmp(Memcmp {
+            offset: 0,
+            bytes: MemcmpEncodedBytes::Bytes(UserVaultAccount::discriminator().to_vec()),
+            encoding: None,
+        }),
+        RpcFilterType::Memcmp(Memcmp {
+            offset: 8,
+            bytes: MemcmpEncodedBytes::Bytes(vault.to_bytes().to_vec()),
+            encoding: None,
+        }),
+    ]
+}
+
+// * queued entries from the head of the queue
+pub fn queued_entries(queue: &RequestQueue) -> Vec<RequestEntry> {
+    queue.iter().collect()
+}
+
+fn fetch_owned(rpc: &RpcClient, program_id: &Pubkey, key: &Pubkey) -> Result<Account> {
+    let account = rpc
+        .get_account_with_commitment(key, rpc.commitment())?
+        .value
+        .ok_or(ClientError::AccountNotFound(*key))?;
+    if account.owner != *program_id {
+        return Err(ClientError::WrongOwner(*key));
+    }
+    Ok(account)
+}
+
+fn decode_account<T: AccountDeserialize + Discriminator>(key: &Pubkey, data: &[u8]) -> Result<T> {
+    check_discriminator::<T>(key, data)?;
+    T::try_deserialize(&mut &data[..]).map_err(|error| ClientError::Decode(*key, error.to_string()))
+}
+
+// * zero copy accounts are read straight from the bytes following the discriminator
+fn decode_zero_copy<T: bytemuck::Pod + Discriminator>(key: &Pubkey, data: &[u8]) -> Result<T> {
+    check_discriminator::<T>(key, data)?;
+    let bytes = data
+        .get(8..8 + size_of::<T>())
+        .ok_or_else(|| ClientError::Decode(*key, "account data too small".to_string()))?;
+    bytemuck::try_pod_read_unaligned(bytes).map_err(|error| ClientError::Decode(*key, format!("{:?}", error)))
+}
+
+fn check_discriminator<T: Discriminator>(key: &Pubkey, data: &[u8]) -> Result<()> {
+    if data.len() < 8 || data[..8] != T::discriminator() {
+        return Err(ClientError::WrongDiscriminator(*key));
+    }
+    Ok(())
+}
@@ -0,0 +1,18 @@
+use anchor_lang::prelude::Pubkey;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ClientError {
+    #[error("rpc request failed: {0}")]
+    Rpc(#[from] solana_client::client_error::ClientError),
+    #[error("account {0} not found")]
+    AccountNotFound(Pubkey),
+    #[error("account {0} is not owned by the program")]
+    WrongOwner(Pubkey),
+    #[error("account {0} has an unexpected discriminator")]
+    WrongDiscriminator(Pubkey),
+    #[error("account {0} could not be decoded: {1}")]
+    Decode(Pubkey, String),
+}
+
+pub type Result<T> = std::result::Result<T, ClientError>;
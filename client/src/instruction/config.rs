@@ -0,0 +1,106 @@
+use super::build;
+use crate::pda;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::system_program;
+use m_protocol::{accounts, instruction};
+
+pub fn initialize_config(program_id: &Pubkey, admin: &Pubkey) -> Instruction {
+    build(
+        *program_id,
+        accounts::InitializeConfig {
+            config: pda::config(program_id).0,
+            admin: *admin,
+            program: *program_id,
+            program_data: pda::program_data(program_id).0,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeConfig {},
+        vec![],
+    )
+}
+
+pub fn propose_admin(program_id: &Pubkey, admin: &Pubkey, new_admin: Pubkey) -> Instruction {
+    build(
+        *program_id,
+        accounts::ProposeAdmin {
+            config: pda::config(program_id).0,
+            admin: *admin,
+        },
+        instruction::ProposeAdmin { new_admin },
+        vec![],
+    )
+}
+
+pub fn accept_admin(program_id: &Pubkey, pending_admin: &Pubkey) -> Instruction {
+    build(
+        *program_id,
+        accounts::AcceptAdmin {
+            config: pda::config(program_id).0,
+            pending_admin: *pending_admin,
+        },
+        instruction::AcceptAdmin {},
+        vec![],
+    )
+}
+
+pub fn add_vault_creator(program_id: &Pubkey, admin: &Pubkey, creator: Pubkey) -> Instruction {
+    build(
+        *program_id,
+        accounts::AddVaultCreator {
+            config: pda::config(program_id).0,
+            admin: *admin,
+        },
+        instruction::AddVaultCreator { creator },
+        vec![],
+    )
+}
+
+pub fn remove_vault_creator(program_id: &Pubkey, admin: &Pubkey, creator: Pubkey) -> Instruction {
+    build(
+        *program_id,
+        accounts::RemoveVaultCreator {
+            config: pda::config(program_id).0,
+            admin: *admin,
+        },
+        instruction::RemoveVaultCreator { creator },
+        vec![],
+    )
+}
+
+pub fn set_guardian(program_id: &Pubkey, admin: &Pubkey, guardian: Pubkey) -> Instruction {
+    build(
+        *program_id,
+        accounts::SetGuardian {
+            config: pda::config(program_id).0,
+            admin: *admin,
+        },
+        instruction::SetGuardian { guardian },
+        vec![],
+    )
+}
+
+// * `authority` is the config guardian or admin
+pub fn pause_protocol(program_id: &Pubkey, authority: &Pubkey, flags: u8) -> Instruction {
+    build(
+        *program_id,
+        accounts::PauseProtocol {
+            config: pda::config(program_id).0,
+            authority: *authority,
+        },
+        instruction::PauseProtocol { flags },
+        vec![],
+    )
+}
+
+pub fn unpause_protocol(program_id: &Pubkey, admin: &Pubkey, flags: u8) -> Instruction {
+    build(
+        *program_id,
+        accounts::UnpauseProtocol {
+            config: pda::config(program_id).0,
+            admin: *admin,
+        },
+        instruction::UnpauseProtocol { flags },
+        vec![],
+    )
+}
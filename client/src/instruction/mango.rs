@@ -0,0 +1,344 @@
+use super::{build, open_orders_metas};
+use crate::keys::{PerpMarketAccounts, SpotMarketAccounts, VaultKeys};
+use crate::pda;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::system_program;
+use anchor_spl::token;
+use m_protocol::state::OrderSide;
+use m_protocol::{accounts, instruction};
+
+pub fn create_mango_account(keys: &VaultKeys, account_num: u64) -> Instruction {
+    let (mango_account, mango_account_bump) = pda::mango_account(
+        &keys.mango.program_id,
+        &keys.mango.group,
+        &keys.vault_authority,
+        account_num,
+    );
+    build(
+        keys.program_id,
+        accounts::CreateAccountOnMango {
+            vault: keys.vault,
+            config: keys.config,
+            vault_authority: keys.vault_authority,
+            unverified_mango_account_pda: mango_account,
+            admin: keys.admin,
+            mango_program_id: keys.mango.program_id,
+            mango_group_ai: keys.mango.group,
+            system_program: system_program::ID,
+        },
+        instruction::CreateMangoAccount {
+            account_num,
+            mango_account_bump,
+        },
+        vec![],
+    )
+}
+
+pub fn close_mango_account(keys: &VaultKeys) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::CloseAccountOnMango {
+            vault: keys.vault,
+            config: keys.config,
+            mango_group: keys.mango.group,
+            mango_account: keys.mango_account,
+            vault_authority: keys.vault_authority,
+            admin: keys.admin,
+            mango_program_id: keys.mango.program_id,
+        },
+        instruction::CloseMangoAccount {},
+        vec![],
+    )
+}
+
+pub fn deposit_to_mango(keys: &VaultKeys, amount: u64) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::DepositToMango {
+            vault: keys.vault,
+            config: keys.config,
+            mango_group: keys.mango.group,
+            mango_account: keys.mango_account,
+            mango_cache: keys.mango.cache,
+            mango_root_bank: keys.mango.root_bank,
+            mango_node_bank: keys.mango.node_bank,
+            mango_vault: keys.mango.vault,
+            vault_token_account: keys.token_account,
+            vault_authority: keys.vault_authority,
+            trader: keys.trader,
+            mango_program_id: keys.mango.program_id,
+            token_program: token::ID,
+        },
+        instruction::DepositToMango { amount },
+        vec![],
+    )
+}
+
+pub fn withdraw_from_mango(keys: &VaultKeys, amount: u64) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::WithdrawFromMango {
+            vault: keys.vault,
+            config: keys.config,
+            mango_group: keys.mango.group,
+            mango_account: keys.mango_account,
+            mango_cache: keys.mango.cache,
+            mango_root_bank: keys.mango.root_bank,
+            mango_node_bank: keys.mango.node_bank,
+            mango_vault: keys.mango.vault,
+            vault_token_account: keys.token_account,
+            vault_authority: keys.vault_authority,
+            signer: keys.mango.signer,
+            trader: keys.trader,
+            token_program: token::ID,
+            mango_program_id: keys.mango.program_id,
+        },
+        instruction::WithdrawFromMango { amount },
+        open_orders_metas(&keys.margin_basket),
+    )
+}
+
+pub fn delegate_mango_account(keys: &VaultKeys, delegate: &Pubkey) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::DelegateMangoAccount {
+            vault: keys.vault,
+            risk_params: keys.risk_params,
+            config: keys.config,
+            mango_group: keys.mango.group,
+            mango_account: keys.mango_account,
+            vault_authority: keys.vault_authority,
+            delegate_pubkey: *delegate,
+            trader: keys.trader,
+            mango_program_id: keys.mango.program_id,
+        },
+        instruction::DelegateMangoAccount {},
+        vec![],
+    )
+}
+
+// * `authority` is the config guardian, the vault admin or the trader
+pub fn revoke_mango_delegate(keys: &VaultKeys, authority: &Pubkey) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::RevokeMangoDelegate {
+            vault: keys.vault,
+            config: keys.config,
+            mango_group: keys.mango.group,
+            mango_account: keys.mango_account,
+            vault_authority: keys.vault_authority,
+            authority: *authority,
+            mango_program_id: keys.mango.program_id,
+            system_program: system_program::ID,
+        },
+        instruction::RevokeMangoDelegate {},
+        vec![],
+    )
+}
+
+pub fn place_perp_order(keys: &VaultKeys, market: &PerpMarketAccounts, order: instruction::PlacePerpOrder) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::PlacePerpOrderOnMango {
+            vault: keys.vault,
+            config: keys.config,
+            risk_params: keys.risk_params,
+            mango_group: keys.mango.group,
+            mango_account: keys.mango_account,
+            vault_authority: keys.vault_authority,
+            mango_cache: keys.mango.cache,
+            perp_market: market.perp_market,
+            bids: market.bids,
+            asks: market.asks,
+            event_queue: market.event_queue,
+            trader: keys.trader,
+            mango_program_id: keys.mango.program_id,
+        },
+        order,
+        open_orders_metas(&keys.margin_basket),
+    )
+}
+
+pub fn cancel_perp_order(keys: &VaultKeys, market: &PerpMarketAccounts, order_id: i128, invalid_id_ok: bool) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::CancelPerpOrderOnMango {
+            vault: keys.vault,
+            config: keys.config,
+            mango_group: keys.mango.group,
+            mango_account: keys.mango_account,
+            vault_authority: keys.vault_authority,
+            perp_market: market.perp_market,
+            bids: market.bids,
+            asks: market.asks,
+            trader: keys.trader,
+            mango_program_id: keys.mango.program_id,
+        },
+        instruction::CancelPerpOrder { order_id, invalid_id_ok },
+        vec![],
+    )
+}
+
+pub fn cancel_all_perp_orders(keys: &VaultKeys, market: &PerpMarketAccounts, limit: u8) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::CancelAllPerpOrdersOnMango {
+            vault: keys.vault,
+            config: keys.config,
+            mango_group: keys.mango.group,
+            mango_account: keys.mango_account,
+            vault_authority: keys.vault_authority,
+            perp_market: market.perp_market,
+            bids: market.bids,
+            asks: market.asks,
+            trader: keys.trader,
+            mango_program_id: keys.mango.program_id,
+        },
+        instruction::CancelAllPerpOrders { limit },
+        vec![],
+    )
+}
+
+pub fn settle_pnl(keys: &VaultKeys, counterparty_mango_account: &Pubkey, market_index: u64) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::SettlePnlOnMango {
+            vault: keys.vault,
+            config: keys.config,
+            mango_group: keys.mango.group,
+            mango_account: keys.mango_account,
+            counterparty_mango_account: *counterparty_mango_account,
+            vault_authority: keys.vault_authority,
+            mango_cache: keys.mango.cache,
+            mango_root_bank: keys.mango.root_bank,
+            mango_node_bank: keys.mango.node_bank,
+            trader: keys.trader,
+            mango_program_id: keys.mango.program_id,
+        },
+        instruction::SettlePnl { market_index },
+        vec![],
+    )
+}
+
+pub fn create_spot_open_orders(keys: &VaultKeys, market: &SpotMarketAccounts) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::CreateSpotOpenOrdersOnMango {
+            vault: keys.vault,
+            config: keys.config,
+            mango_group: keys.mango.group,
+            mango_account: keys.mango_account,
+            vault_authority: keys.vault_authority,
+            dex_program: market.dex_program,
+            open_orders: open_orders(keys, market),
+            spot_market: market.spot_market,
+            mango_signer: keys.mango.signer,
+            trader: keys.trader,
+            system_program: system_program::ID,
+            mango_program_id: keys.mango.program_id,
+        },
+        instruction::CreateSpotOpenOrders {
+            market_index: market.market_index,
+        },
+        vec![],
+    )
+}
+
+// * `order.market_index` should match `market.market_index`
+pub fn place_spot_order(keys: &VaultKeys, market: &SpotMarketAccounts, order: instruction::PlaceSpotOrder) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::PlaceSpotOrderOnMango {
+            vault: keys.vault,
+            config: keys.config,
+            risk_params: keys.risk_params,
+            mango_group: keys.mango.group,
+            mango_account: keys.mango_account,
+            vault_authority: keys.vault_authority,
+            mango_cache: keys.mango.cache,
+            dex_program: market.dex_program,
+            spot_market: market.spot_market,
+            bids: market.bids,
+            asks: market.asks,
+            dex_request_queue: market.request_queue,
+            dex_event_queue: market.event_queue,
+            dex_base: market.dex_base,
+            dex_quote: market.dex_quote,
+            base_root_bank: market.base_root_bank,
+            base_node_bank: market.base_node_bank,
+            base_vault: market.base_vault,
+            quote_root_bank: keys.mango.root_bank,
+            quote_node_bank: keys.mango.node_bank,
+            quote_vault: keys.mango.vault,
+            mango_signer: keys.mango.signer,
+            dex_signer: market.dex_signer,
+            msrm_or_srm_vault: market.msrm_or_srm_vault,
+            trader: keys.trader,
+            token_program: token::ID,
+            mango_program_id: keys.mango.program_id,
+        },
+        order,
+        open_orders_metas(&keys.spot_open_orders),
+    )
+}
+
+pub fn cancel_spot_order(keys: &VaultKeys, market: &SpotMarketAccounts, side: OrderSide, order_id: u128) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::CancelSpotOrderOnMango {
+            vault: keys.vault,
+            config: keys.config,
+            mango_group: keys.mango.group,
+            mango_account: keys.mango_account,
+            vault_authority: keys.vault_authority,
+            dex_program: market.dex_program,
+            spot_market: market.spot_market,
+            bids: market.bids,
+            asks: market.asks,
+            open_orders: open_orders(keys, market),
+            mango_signer: keys.mango.signer,
+            dex_event_queue: market.event_queue,
+            trader: keys.trader,
+            mango_program_id: keys.mango.program_id,
+        },
+        instruction::CancelSpotOrder { side, order_id },
+        vec![],
+    )
+}
+
+pub fn settle_funds(keys: &VaultKeys, market: &SpotMarketAccounts) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::SettleFundsOnMango {
+            vault: keys.vault,
+            config: keys.config,
+            mango_group: keys.mango.group,
+            mango_cache: keys.mango.cache,
+            mango_account: keys.mango_account,
+            vault_authority: keys.vault_authority,
+            dex_program: market.dex_program,
+            spot_market: market.spot_market,
+            open_orders: open_orders(keys, market),
+            mango_signer: keys.mango.signer,
+            dex_base: market.dex_base,
+            dex_quote: market.dex_quote,
+            base_root_bank: market.base_root_bank,
+            base_node_bank: market.base_node_bank,
+            quote_root_bank: keys.mango.root_bank,
+            quote_node_bank: keys.mango.node_bank,
+            base_vault: market.base_vault,
+            quote_vault: keys.mango.vault,
+            dex_signer: market.dex_signer,
+            trader: keys.trader,
+            token_program: token::ID,
+            mango_program_id: keys.mango.program_id,
+        },
+        instruction::SettleFunds {},
+        vec![],
+    )
+}
+
+fn open_orders(keys: &VaultKeys, market: &SpotMarketAccounts) -> Pubkey {
+    pda::mango_open_orders(&keys.mango.program_id, &keys.mango_account, market.market_index).0
+}
@@ -0,0 +1,36 @@
+//! Instruction builders with the full account list of every program instruction.
+
+pub mod config;
+pub mod mango;
+pub mod vault;
+
+pub use config::*;
+pub use mango::*;
+pub use vault::*;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::{InstructionData, ToAccountMetas};
+
+fn build(
+    program_id: Pubkey,
+    accounts: impl ToAccountMetas,
+    data: impl InstructionData,
+    remaining_accounts: Vec<AccountMeta>,
+) -> Instruction {
+    let mut metas = accounts.to_account_metas(None);
+    metas.extend(remaining_accounts);
+    Instruction {
+        program_id,
+        accounts: metas,
+        data: data.data(),
+    }
+}
+
+// * open orders passed to mango for the health check of the margin basket
+fn open_orders_metas(open_orders: &[Pubkey]) -> Vec<AccountMeta> {
+    open_orders
+        .iter()
+        .map(|key| AccountMeta::new(*key, false))
+        .collect()
+}
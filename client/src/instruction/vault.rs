@@ -0,0 +1,779 @@
+use super::{build, open_orders_metas};
+use crate::keys::VaultKeys;
+use crate::pda;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::{system_instruction, sysvar};
+use anchor_lang::system_program;
+use anchor_spl::token;
+use m_protocol::state::{AccessMode, PenaltyRecipient, StakeReq, VaultRole};
+use m_protocol::{accounts, instruction};
+use std::mem::size_of;
+
+// * stake and unstake request accounts share the layout and are created before `create_vault`
+pub const REQUEST_ACCOUNT_SPACE: usize = 8 + size_of::<StakeReq>();
+
+pub fn create_request_account(program_id: &Pubkey, payer: &Pubkey, account: &Pubkey, lamports: u64) -> Instruction {
+    system_instruction::create_account(payer, account, lamports, REQUEST_ACCOUNT_SPACE as u64, program_id)
+}
+
+pub fn create_vault(
+    program_id: &Pubkey,
+    creator: &Pubkey,
+    token_mint: &Pubkey,
+    stake_req: &Pubkey,
+    unstake_req: &Pubkey,
+    name: String,
+    limit: u64,
+) -> Instruction {
+    let (vault, vault_bump) = pda::vault(program_id, &name);
+    let (vault_pda_authority, vault_pda_bump) = pda::vault_authority(program_id, &vault);
+    build(
+        *program_id,
+        accounts::CreateVault {
+            vault,
+            stake_req: *stake_req,
+            unstake_req: *unstake_req,
+            config: pda::config(program_id).0,
+            creator: *creator,
+            token_account: pda::vault_token_account(program_id, &vault).0,
+            share_mint: pda::share_mint(program_id, &vault).0,
+            vault_pda_authority,
+            token_mint: *token_mint,
+            rent: sysvar::rent::ID,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::CreateVault {
+            name,
+            limit,
+            vault_bump,
+            vault_pda_bump,
+        },
+        vec![],
+    )
+}
+
+// * `gateway_token` may be omitted when the vault has no gatekeeper network
+pub fn create_user_vault_account(
+    keys: &VaultKeys,
+    user: &Pubkey,
+    gateway_token: Option<Pubkey>,
+    limit: u64,
+) -> Instruction {
+    let (user_vault_account, user_account_bump) = pda::user_vault_account(&keys.program_id, &keys.vault, user);
+    build(
+        keys.program_id,
+        accounts::CreateUserVaultAccount {
+            user_vault_account,
+            token_account: keys.user_token_account(user),
+            share_token_account: keys.user_share_token_account(user),
+            vault: keys.vault,
+            config: keys.config,
+            vault_pda_authority: keys.vault_authority,
+            user_account_authority: *user,
+            gateway_token: gateway_token.unwrap_or(system_program::ID),
+            access_entry: keys.access_entry(user),
+            token_mint: keys.token_mint,
+            share_mint: keys.share_mint,
+            token_program: token::ID,
+            rent: sysvar::rent::ID,
+            system_program: system_program::ID,
+        },
+        instruction::CreateUserVaultAccount {
+            limit,
+            user_account_bump,
+        },
+        vec![],
+    )
+}
+
+pub fn deposit_to_user_vault_account(
+    keys: &VaultKeys,
+    user: &Pubkey,
+    user_ata: &Pubkey,
+    gateway_token: Option<Pubkey>,
+    amount: u64,
+) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::DepositToUserVaultAccount {
+            vault: keys.vault,
+            config: keys.config,
+            user_vault_account: keys.user_vault_account(user),
+            user_vault_usdc_token_account: keys.user_token_account(user),
+            authority: *user,
+            gateway_token: gateway_token.unwrap_or(system_program::ID),
+            vault_pda_authority: keys.vault_authority,
+            user_ata: *user_ata,
+            token_program: token::ID,
+        },
+        instruction::DepositToUserVaultAccount { amount },
+        vec![],
+    )
+}
+
+pub fn withdraw_from_user_vault_account(keys: &VaultKeys, user: &Pubkey, user_ata: &Pubkey, amount: u64) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::WithdrawFromUserVaultAccount {
+            vault: keys.vault,
+            config: keys.config,
+            user_vault_account: keys.user_vault_account(user),
+            user_vault_usdc_token_account: keys.user_token_account(user),
+            vault_pda_authority: keys.vault_authority,
+            authority: *user,
+            user_ata: *user_ata,
+            token_program: token::ID,
+        },
+        instruction::WithdrawFromUserVaultAccount { amount },
+        vec![],
+    )
+}
+
+pub fn request_to_stake(
+    keys: &VaultKeys,
+    user: &Pubkey,
+    gateway_token: Option<Pubkey>,
+    amount: u64,
+    max: bool,
+) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::RequestToStake {
+            user_vault_account: keys.user_vault_account(user),
+            vault: keys.vault,
+            config: keys.config,
+            authority: *user,
+            gateway_token: gateway_token.unwrap_or(system_program::ID),
+            access_entry: keys.access_entry(user),
+            vault_stake_req_account: keys.stake_request_account,
+        },
+        instruction::RequestToStake { amount, max },
+        vec![],
+    )
+}
+
+pub fn request_to_unstake(keys: &VaultKeys, user: &Pubkey, amount: u64, max: bool) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::RequestToUnstake {
+            user_vault_account: keys.user_vault_account(user),
+            vault: keys.vault,
+            config: keys.config,
+            authority: *user,
+            vault_unstake_req_account: keys.unstake_request_account,
+        },
+        instruction::RequestToUnstake { amount, max },
+        vec![],
+    )
+}
+
+pub fn update_stake_request(keys: &VaultKeys, user: &Pubkey, amount: u64, max: bool, cancel: bool) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::UpdateStakeRequest {
+            vault: keys.vault,
+            config: keys.config,
+            user_vault_account: keys.user_vault_account(user),
+            stake_request_account: keys.stake_request_account,
+            access_entry: keys.access_entry(user),
+            authority: *user,
+        },
+        instruction::UpdateStakeRequest { amount, max, cancel },
+        vec![],
+    )
+}
+
+pub fn update_unstake_request(keys: &VaultKeys, user: &Pubkey, amount: u64, max: bool, cancel: bool) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::UpdateUnstakeRequest {
+            vault: keys.vault,
+            config: keys.config,
+            user_vault_account: keys.user_vault_account(user),
+            unstake_request_account: keys.unstake_request_account,
+            authority: *user,
+        },
+        instruction::UpdateUnstakeRequest { amount, max, cancel },
+        vec![],
+    )
+}
+
+// * `user` is the authority of the user vault account at the head of the stake queue
+pub fn process_stake(keys: &VaultKeys, user: &Pubkey) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::ProcessStake {
+            vault: keys.vault,
+            config: keys.config,
+            user_vault_account: keys.user_vault_account(user),
+            access_entry: keys.access_entry(user),
+            stake_request_account: keys.stake_request_account,
+            vault_authority: keys.vault_authority,
+            keeper: keys.keeper,
+            user_token_account: keys.user_token_account(user),
+            vault_token_account: keys.token_account,
+            share_mint: keys.share_mint,
+            user_share_token_account: keys.user_share_token_account(user),
+            mango_group: keys.mango.group,
+            mango_account: keys.mango_account,
+            mango_cache: keys.mango.cache,
+            mango_root_bank: keys.mango.root_bank,
+            mango_node_bank: keys.mango.node_bank,
+            mango_vault: keys.mango.vault,
+            mango_program_id: keys.mango.program_id,
+            token_program: token::ID,
+        },
+        instruction::ProcessStake {},
+        vec![],
+    )
+}
+
+// * `user` is the authority of the user vault account at the head of the unstake queue
+pub fn process_unstake(keys: &VaultKeys, user: &Pubkey) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::ProcessUnstake {
+            vault: keys.vault,
+            config: keys.config,
+            user_vault_account: keys.user_vault_account(user),
+            unstake_request_account: keys.unstake_request_account,
+            vault_authority: keys.vault_authority,
+            mango_group: keys.mango.group,
+            mango_account: keys.mango_account,
+            mango_cache: keys.mango.cache,
+            mango_root_bank: keys.mango.root_bank,
+            mango_node_bank: keys.mango.node_bank,
+            mango_vault: keys.mango.vault,
+            keeper: keys.keeper,
+            user_token_account: keys.user_token_account(user),
+            vault_token_account: keys.token_account,
+            share_mint: keys.share_mint,
+            user_share_token_account: keys.user_share_token_account(user),
+            signer: keys.mango.signer,
+            token_program: token::ID,
+            mango_program_id: keys.mango.program_id,
+        },
+        instruction::ProcessUnstake {},
+        open_orders_metas(&keys.margin_basket),
+    )
+}
+
+// * `users` are the authorities of the queued requests, in any order
+pub fn process_stake_batch(keys: &VaultKeys, users: &[Pubkey]) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::ProcessStakeBatch {
+            vault: keys.vault,
+            config: keys.config,
+            stake_request_account: keys.stake_request_account,
+            vault_authority: keys.vault_authority,
+            keeper: keys.keeper,
+            share_mint: keys.share_mint,
+            mango_group: keys.mango.group,
+            mango_account: keys.mango_account,
+            mango_cache: keys.mango.cache,
+            mango_root_bank: keys.mango.root_bank,
+            mango_node_bank: keys.mango.node_bank,
+            mango_vault: keys.mango.vault,
+            mango_program_id: keys.mango.program_id,
+            token_program: token::ID,
+        },
+        instruction::ProcessStakeBatch {},
+        stake_batch_user_metas(keys, users),
+    )
+}
+
+pub fn process_unstake_batch(keys: &VaultKeys, users: &[Pubkey]) -> Instruction {
+    let mut remaining_accounts = open_orders_metas(&keys.margin_basket);
+    remaining_accounts.extend(batch_user_metas(keys, users));
+    build(
+        keys.program_id,
+        accounts::ProcessUnstakeBatch {
+            vault: keys.vault,
+            config: keys.config,
+            unstake_request_account: keys.unstake_request_account,
+            vault_authority: keys.vault_authority,
+            mango_group: keys.mango.group,
+            mango_account: keys.mango_account,
+            mango_cache: keys.mango.cache,
+            mango_root_bank: keys.mango.root_bank,
+            mango_node_bank: keys.mango.node_bank,
+            mango_vault: keys.mango.vault,
+            keeper: keys.keeper,
+            share_mint: keys.share_mint,
+            signer: keys.mango.signer,
+            token_program: token::ID,
+            mango_program_id: keys.mango.program_id,
+        },
+        instruction::ProcessUnstakeBatch {},
+        remaining_accounts,
+    )
+}
+
+pub fn update_user_balance(keys: &VaultKeys, user: &Pubkey) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::UpdateUserBalance {
+            vault: keys.vault,
+            config: keys.config,
+            user_vault_account: keys.user_vault_account(user),
+            share_mint: keys.share_mint,
+            user_share_token_account: keys.user_share_token_account(user),
+            keeper: keys.keeper,
+        },
+        instruction::UpdateUserBalance {},
+        vec![],
+    )
+}
+
+pub fn update_vault_balance(keys: &VaultKeys) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::UpdateVaultBalance {
+            vault: keys.vault,
+            config: keys.config,
+            stake_request_account: keys.stake_request_account,
+            unstake_request_account: keys.unstake_request_account,
+            vault_token_account: keys.token_account,
+            share_mint: keys.share_mint,
+            vault_authority: keys.vault_authority,
+            mango_account: keys.mango_account,
+            mango_cache: keys.mango.cache,
+            mango_program_id: keys.mango.program_id,
+            mango_group: keys.mango.group,
+            mango_root_bank: keys.mango.root_bank,
+            keeper: keys.keeper,
+            system_program: system_program::ID,
+        },
+        instruction::UpdateVaultBalance {},
+        vec![],
+    )
+}
+
+// * `user_vault_accounts` are the queued entries to clear, the program only removes stale ones
+pub fn clear_stake_request(keys: &VaultKeys, user_vault_accounts: &[Pubkey]) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::ClearStakeRequest {
+            vault: keys.vault,
+            config: keys.config,
+            stake_request_account: keys.stake_request_account,
+            keeper: keys.keeper,
+        },
+        instruction::ClearStakeRequest {},
+        user_vault_accounts
+            .iter()
+            .map(|key| AccountMeta::new_readonly(*key, false))
+            .collect(),
+    )
+}
+
+// * `user_vault_accounts` are the queued entries to clear, the program only removes stale ones
+pub fn clear_unstake_request(keys: &VaultKeys, user_vault_accounts: &[Pubkey]) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::ClearUnstakeRequest {
+            vault: keys.vault,
+            config: keys.config,
+            unstake_request_account: keys.unstake_request_account,
+            keeper: keys.keeper,
+        },
+        instruction::ClearUnstakeRequest {},
+        user_vault_accounts
+            .iter()
+            .map(|key| AccountMeta::new_readonly(*key, false))
+            .collect(),
+    )
+}
+
+pub fn update_vault_limit(keys: &VaultKeys, new_limit: u64) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::UpdateVaultLimit {
+            vault: keys.vault,
+            admin: keys.admin,
+        },
+        instruction::UpdateVaultLimit { new_limit },
+        vec![],
+    )
+}
+
+pub fn set_vault_role(keys: &VaultKeys, role: VaultRole, new_key: Pubkey) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::SetVaultRole {
+            vault: keys.vault,
+            admin: keys.admin,
+        },
+        instruction::SetVaultRole { role, new_key },
+        vec![],
+    )
+}
+
+// * goes in the same transaction after `update_vault_balance`, and after `collect_fees` when the recipient changes
+pub fn update_vault_fees(
+    keys: &VaultKeys,
+    management_fee_bps: u16,
+    performance_fee_bps: u16,
+    fee_recipient: Pubkey,
+) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::UpdateVaultFees {
+            vault: keys.vault,
+            admin: keys.admin,
+        },
+        instruction::UpdateVaultFees {
+            management_fee_bps,
+            performance_fee_bps,
+            fee_recipient,
+        },
+        vec![],
+    )
+}
+
+// * `fee_recipient_token_account` is a token account of the vault mint owned by the fee recipient
+pub fn collect_fees(keys: &VaultKeys, fee_recipient_token_account: &Pubkey) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::CollectFees {
+            vault: keys.vault,
+            config: keys.config,
+            vault_authority: keys.vault_authority,
+            mango_group: keys.mango.group,
+            mango_account: keys.mango_account,
+            mango_cache: keys.mango.cache,
+            mango_root_bank: keys.mango.root_bank,
+            mango_node_bank: keys.mango.node_bank,
+            mango_vault: keys.mango.vault,
+            keeper: keys.keeper,
+            fee_recipient_token_account: *fee_recipient_token_account,
+            signer: keys.mango.signer,
+            token_program: token::ID,
+            mango_program_id: keys.mango.program_id,
+        },
+        instruction::CollectFees {},
+        open_orders_metas(&keys.margin_basket),
+    )
+}
+
+pub fn initialize_risk_params(
+    keys: &VaultKeys,
+    allowed_markets: u16,
+    max_leverage_bps: u64,
+    min_health_ratio_bps: u64,
+) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::InitializeRiskParams {
+            vault: keys.vault,
+            risk_params: keys.risk_params,
+            mango_group: keys.mango.group,
+            mango_account: keys.mango_account,
+            vault_authority: keys.vault_authority,
+            admin: keys.admin,
+            mango_program_id: keys.mango.program_id,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeRiskParams {
+            allowed_markets,
+            max_leverage_bps,
+            min_health_ratio_bps,
+        },
+        vec![],
+    )
+}
+
+pub fn set_market_risk(keys: &VaultKeys, market_index: u64, allowed: bool, max_notional: u64) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::SetMarketRisk {
+            vault: keys.vault,
+            risk_params: keys.risk_params,
+            admin: keys.admin,
+        },
+        instruction::SetMarketRisk {
+            market_index,
+            allowed,
+            max_notional,
+        },
+        vec![],
+    )
+}
+
+pub fn update_risk_limits(keys: &VaultKeys, max_leverage_bps: u64, min_health_ratio_bps: u64) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::UpdateRiskLimits {
+            vault: keys.vault,
+            risk_params: keys.risk_params,
+            admin: keys.admin,
+        },
+        instruction::UpdateRiskLimits {
+            max_leverage_bps,
+            min_health_ratio_bps,
+        },
+        vec![],
+    )
+}
+
+pub fn update_drawdown_limits(keys: &VaultKeys, max_daily_drawdown_bps: u16, max_drawdown_bps: u16) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::UpdateDrawdownLimits {
+            vault: keys.vault,
+            admin: keys.admin,
+        },
+        instruction::UpdateDrawdownLimits {
+            max_daily_drawdown_bps,
+            max_drawdown_bps,
+        },
+        vec![],
+    )
+}
+
+pub fn clear_halt(keys: &VaultKeys) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::ClearHalt {
+            vault: keys.vault,
+            admin: keys.admin,
+        },
+        instruction::ClearHalt {},
+        vec![],
+    )
+}
+
+// * `authority` is the config guardian or the vault admin
+pub fn pause_vault(keys: &VaultKeys, authority: &Pubkey, flags: u8) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::PauseVault {
+            vault: keys.vault,
+            config: keys.config,
+            authority: *authority,
+        },
+        instruction::PauseVault { flags },
+        vec![],
+    )
+}
+
+pub fn unpause_vault(keys: &VaultKeys, flags: u8) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::UnpauseVault {
+            vault: keys.vault,
+            admin: keys.admin,
+        },
+        instruction::UnpauseVault { flags },
+        vec![],
+    )
+}
+
+pub fn set_gatekeeper_network(keys: &VaultKeys, gatekeeper_network: Option<Pubkey>) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::SetGatekeeperNetwork {
+            vault: keys.vault,
+            admin: keys.admin,
+        },
+        instruction::SetGatekeeperNetwork { gatekeeper_network },
+        vec![],
+    )
+}
+
+pub fn set_access_mode(keys: &VaultKeys, access_mode: AccessMode) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::SetAccessMode {
+            vault: keys.vault,
+            admin: keys.admin,
+        },
+        instruction::SetAccessMode { access_mode },
+        vec![],
+    )
+}
+
+pub fn add_access_entry(keys: &VaultKeys, wallet: Pubkey, allowed: bool, denied: bool) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::AddAccessEntry {
+            vault: keys.vault,
+            access_entry: keys.access_entry(&wallet),
+            admin: keys.admin,
+            system_program: system_program::ID,
+        },
+        instruction::AddAccessEntry { wallet, allowed, denied },
+        vec![],
+    )
+}
+
+pub fn update_access_entry(keys: &VaultKeys, wallet: &Pubkey, allowed: bool, denied: bool) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::UpdateAccessEntry {
+            vault: keys.vault,
+            access_entry: keys.access_entry(wallet),
+            admin: keys.admin,
+        },
+        instruction::UpdateAccessEntry { allowed, denied },
+        vec![],
+    )
+}
+
+pub fn remove_access_entry(keys: &VaultKeys, wallet: &Pubkey) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::RemoveAccessEntry {
+            vault: keys.vault,
+            access_entry: keys.access_entry(wallet),
+            admin: keys.admin,
+        },
+        instruction::RemoveAccessEntry {},
+        vec![],
+    )
+}
+
+pub fn update_lockup(keys: &VaultKeys, lockup_seconds: i64, unstake_cooldown_seconds: i64) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::UpdateLockup {
+            vault: keys.vault,
+            admin: keys.admin,
+        },
+        instruction::UpdateLockup {
+            lockup_seconds,
+            unstake_cooldown_seconds,
+        },
+        vec![],
+    )
+}
+
+pub fn update_withdrawal_penalty(
+    keys: &VaultKeys,
+    early_withdrawal_penalty_bps: u16,
+    penalty_period_seconds: i64,
+    penalty_recipient: PenaltyRecipient,
+) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::UpdateWithdrawalPenalty {
+            vault: keys.vault,
+            admin: keys.admin,
+        },
+        instruction::UpdateWithdrawalPenalty {
+            early_withdrawal_penalty_bps,
+            penalty_period_seconds,
+            penalty_recipient,
+        },
+        vec![],
+    )
+}
+
+pub fn create_insurance_fund(keys: &VaultKeys) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::CreateInsuranceFund {
+            vault: keys.vault,
+            insurance_fund: keys.insurance_fund,
+            token_mint: keys.token_mint,
+            vault_authority: keys.vault_authority,
+            admin: keys.admin,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            rent: sysvar::rent::ID,
+        },
+        instruction::CreateInsuranceFund {},
+        vec![],
+    )
+}
+
+pub fn update_insurance_params(keys: &VaultKeys, insurance_fee_bps: u16, insurance_trigger_bps: u16) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::UpdateInsuranceParams {
+            vault: keys.vault,
+            admin: keys.admin,
+        },
+        instruction::UpdateInsuranceParams {
+            insurance_fee_bps,
+            insurance_trigger_bps,
+        },
+        vec![],
+    )
+}
+
+pub fn collect_insurance(keys: &VaultKeys) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::CollectInsurance {
+            vault: keys.vault,
+            config: keys.config,
+            vault_authority: keys.vault_authority,
+            mango_group: keys.mango.group,
+            mango_account: keys.mango_account,
+            mango_cache: keys.mango.cache,
+            mango_root_bank: keys.mango.root_bank,
+            mango_node_bank: keys.mango.node_bank,
+            mango_vault: keys.mango.vault,
+            keeper: keys.keeper,
+            insurance_fund: keys.insurance_fund,
+            signer: keys.mango.signer,
+            token_program: token::ID,
+            mango_program_id: keys.mango.program_id,
+        },
+        instruction::CollectInsurance {},
+        open_orders_metas(&keys.margin_basket),
+    )
+}
+
+pub fn inject_insurance(keys: &VaultKeys, amount: u64) -> Instruction {
+    build(
+        keys.program_id,
+        accounts::InjectInsurance {
+            vault: keys.vault,
+            config: keys.config,
+            vault_authority: keys.vault_authority,
+            mango_group: keys.mango.group,
+            mango_account: keys.mango_account,
+            mango_cache: keys.mango.cache,
+            mango_root_bank: keys.mango.root_bank,
+            mango_node_bank: keys.mango.node_bank,
+            mango_vault: keys.mango.vault,
+            insurance_fund: keys.insurance_fund,
+            admin: keys.admin,
+            mango_program_id: keys.mango.program_id,
+            token_program: token::ID,
+        },
+        instruction::InjectInsurance { amount },
+        vec![],
+    )
+}
+
+// * [user_vault_account, user_token_account, user_share_token_account] per user
+fn batch_user_metas(keys: &VaultKeys, users: &[Pubkey]) -> Vec<AccountMeta> {
+    users
+        .iter()
+        .flat_map(|user| {
+            [
+                AccountMeta::new(keys.user_vault_account(user), false),
+                AccountMeta::new(keys.user_token_account(user), false),
+                AccountMeta::new(keys.user_share_token_account(user), false),
+            ]
+        })
+        .collect()
+}
+
+// * the batch accounts of each user followed by its access entry
+fn stake_batch_user_metas(keys: &VaultKeys, users: &[Pubkey]) -> Vec<AccountMeta> {
+    users
+        .iter()
+        .flat_map(|user| {
+            let mut metas = batch_user_metas(keys, std::slice::from_ref(user));
+            metas.push(AccountMeta::new_readonly(keys.access_entry(user), false));
+            metas
+        })
+        .collect()
+}
@@ -0,0 +1,131 @@
+//! Account keys the instruction builders need, resolved once per vault.
+
+use crate::pda;
+use anchor_lang::prelude::Pubkey;
+use m_protocol::state::Vault;
+use mango::state::{MangoAccount, MAX_PAIRS};
+
+// * mango group accounts, banks and vault of the quote token
+#[derive(Clone, Copy, Debug)]
+pub struct MangoAccounts {
+    pub program_id: Pubkey,
+    pub group: Pubkey,
+    pub cache: Pubkey,
+    pub root_bank: Pubkey,
+    pub node_bank: Pubkey,
+    pub vault: Pubkey,
+    pub signer: Pubkey,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct PerpMarketAccounts {
+    pub perp_market: Pubkey,
+    pub bids: Pubkey,
+    pub asks: Pubkey,
+    pub event_queue: Pubkey,
+}
+
+// * serum market and the mango banks of its base token
+#[derive(Clone, Copy, Debug)]
+pub struct SpotMarketAccounts {
+    pub market_index: u64,
+    pub dex_program: Pubkey,
+    pub spot_market: Pubkey,
+    pub bids: Pubkey,
+    pub asks: Pubkey,
+    pub request_queue: Pubkey,
+    pub event_queue: Pubkey,
+    pub dex_base: Pubkey,
+    pub dex_quote: Pubkey,
+    pub dex_signer: Pubkey,
+    pub base_root_bank: Pubkey,
+    pub base_node_bank: Pubkey,
+    pub base_vault: Pubkey,
+    pub msrm_or_srm_vault: Pubkey,
+}
+
+#[derive(Clone, Debug)]
+pub struct VaultKeys {
+    pub program_id: Pubkey,
+    pub vault: Pubkey,
+    pub config: Pubkey,
+    pub vault_authority: Pubkey,
+    pub token_account: Pubkey,
+    pub token_mint: Pubkey,
+    pub share_mint: Pubkey,
+    pub stake_request_account: Pubkey,
+    pub unstake_request_account: Pubkey,
+    pub risk_params: Pubkey,
+    pub insurance_fund: Pubkey,
+    pub admin: Pubkey,
+    pub trader: Pubkey,
+    pub keeper: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub mango: MangoAccounts,
+    pub mango_account: Pubkey,
+    pub spot_open_orders: Vec<Pubkey>, // * tracked open orders in market index order
+    pub margin_basket: Vec<Pubkey>,    // * open orders mango counts for health, see `with_margin_basket`
+}
+
+impl VaultKeys {
+    pub fn new(program_id: Pubkey, vault_key: Pubkey, vault: &Vault, mango: MangoAccounts) -> Self {
+        let (vault_authority, _) = pda::vault_authority(&program_id, &vault_key);
+        let (mango_account, _) = pda::mango_account(
+            &mango.program_id,
+            &mango.group,
+            &vault_authority,
+            vault.mango_account_num,
+        );
+        VaultKeys {
+            program_id,
+            vault: vault_key,
+            config: pda::config(&program_id).0,
+            vault_authority,
+            token_account: vault.token_account,
+            token_mint: vault.mint,
+            share_mint: vault.share_mint,
+            stake_request_account: vault.stake_request_account,
+            unstake_request_account: vault.unstake_request_account,
+            risk_params: pda::risk_params(&program_id, &vault_key).0,
+            insurance_fund: pda::insurance_fund(&program_id, &vault_key).0,
+            admin: vault.admin,
+            trader: vault.trader,
+            keeper: vault.keeper,
+            fee_recipient: vault.fee_recipient,
+            mango,
+            mango_account,
+            spot_open_orders: vault
+                .spot_open_orders
+                .iter()
+                .filter(|key| **key != Pubkey::default())
+                .copied()
+                .collect(),
+            margin_basket: Vec::new(),
+        }
+    }
+
+    // * mango reads the basket packed in market index order, settled markets drop out of it
+    pub fn with_margin_basket(mut self, mango_account: &MangoAccount) -> Self {
+        self.margin_basket = (0..MAX_PAIRS)
+            .filter(|index| mango_account.in_margin_basket[*index])
+            .map(|index| mango_account.spot_open_orders[index])
+            .collect();
+        self
+    }
+
+    pub fn user_vault_account(&self, user: &Pubkey) -> Pubkey {
+        pda::user_vault_account(&self.program_id, &self.vault, user).0
+    }
+
+    pub fn user_token_account(&self, user: &Pubkey) -> Pubkey {
+        pda::user_token_account(&self.program_id, &self.vault, user).0
+    }
+
+    pub fn user_share_token_account(&self, user: &Pubkey) -> Pubkey {
+        pda::user_share_token_account(&self.program_id, &self.vault, user).0
+    }
+
+    pub fn access_entry(&self, wallet: &Pubkey) -> Pubkey {
+        pda::access_entry(&self.program_id, &self.vault, wallet).0
+    }
+}
@@ -0,0 +1,15 @@
+//! Rust client for the merstab protocol: pda derivation, instruction builders
+//! and account decoding.
+
+pub mod accounts;
+pub mod error;
+pub mod instruction;
+pub mod keys;
+pub mod pda;
+pub mod queue;
+
+pub use accounts::*;
+pub use error::{ClientError, Result};
+pub use keys::*;
+
+pub use m_protocol::ID as PROGRAM_ID;
@@ -0,0 +1,74 @@
+//! Program derived addresses of the protocol, mirroring the seeds checked on chain.
+
+use anchor_lang::prelude::Pubkey;
+use m_protocol::constants::*;
+use solana_sdk::bpf_loader_upgradeable;
+
+pub fn config(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONFIG_SEED], program_id)
+}
+
+pub fn program_data(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id())
+}
+
+pub fn vault(program_id: &Pubkey, name: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[name.as_bytes(), VAULT_SEED], program_id)
+}
+
+pub fn vault_authority(program_id: &Pubkey, vault: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[vault.as_ref(), VAULT_PDA_AUTHORITY_SEED], program_id)
+}
+
+pub fn vault_token_account(program_id: &Pubkey, vault: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[vault.as_ref(), USDC_SEED], program_id)
+}
+
+pub fn share_mint(program_id: &Pubkey, vault: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[vault.as_ref(), SHARES_SEED], program_id)
+}
+
+pub fn user_vault_account(program_id: &Pubkey, vault: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[vault.as_ref(), user.as_ref()], program_id)
+}
+
+pub fn user_token_account(program_id: &Pubkey, vault: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[vault.as_ref(), user.as_ref(), USDC_SEED], program_id)
+}
+
+pub fn user_share_token_account(program_id: &Pubkey, vault: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[vault.as_ref(), user.as_ref(), SHARES_SEED], program_id)
+}
+
+pub fn risk_params(program_id: &Pubkey, vault: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[vault.as_ref(), RISK_PARAMS_SEED], program_id)
+}
+
+pub fn access_entry(program_id: &Pubkey, vault: &Pubkey, wallet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[vault.as_ref(), wallet.as_ref(), ACCESS_ENTRY_SEED], program_id)
+}
+
+pub fn insurance_fund(program_id: &Pubkey, vault: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[vault.as_ref(), INSURANCE_SEED], program_id)
+}
+
+// * derived under the mango program, as created by `create_mango_account`
+pub fn mango_account(
+    mango_program_id: &Pubkey,
+    mango_group: &Pubkey,
+    vault_authority: &Pubkey,
+    account_num: u64,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[mango_group.as_ref(), vault_authority.as_ref(), &account_num.to_le_bytes()],
+        mango_program_id,
+    )
+}
+
+// * derived under the mango program, as created by `create_spot_open_orders`
+pub fn mango_open_orders(mango_program_id: &Pubkey, mango_account: &Pubkey, market_index: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[mango_account.as_ref(), &market_index.to_le_bytes(), OPEN_ORDERS_SEED],
+        mango_program_id,
+    )
+}
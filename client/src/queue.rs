@@ -0,0 +1,22 @@
+//! Selection of queued requests a keeper can settle.
+
+use crate::accounts::queued_entries;
+use m_protocol::state::{RequestEntry, RequestQueue, Vault};
+
+// * stakes settle from the head once their epoch has closed
+pub fn settleable_stakes(vault: &Vault, queue: &RequestQueue) -> Vec<RequestEntry> {
+    queued_entries(queue)
+        .into_iter()
+        .take_while(|entry| vault.epoch.is_settled(entry.epoch))
+        .collect()
+}
+
+// * unstakes also wait out the cooldown recorded on the request, those still cooling are passed over
+pub fn settleable_unstakes(vault: &Vault, queue: &RequestQueue, now: i64) -> Vec<RequestEntry> {
+    queued_entries(queue)
+        .into_iter()
+        .filter(|entry| {
+            vault.epoch.is_settled(entry.epoch) && Vault::cooldown_remaining(entry.cooldown_ends_at, now) == 0
+        })
+        .collect()
+}
@@ -0,0 +1,68 @@
+//! Cluster access used by the keeper, so the cycle can run against an rpc node or a test bank.
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::Result;
+use async_trait::async_trait;
+use m_protocol::state::UserVaultAccount;
+use merstab_client::{decode_user_vault_account, user_vault_account_filters};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_sdk::account::Account;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::transaction::Transaction;
+
+#[async_trait]
+pub trait Chain: Send + Sync {
+    async fn get_account(&self, key: &Pubkey) -> Result<Option<Account>>;
+
+    async fn user_vault_accounts(&self, program_id: &Pubkey, vault: &Pubkey) -> Result<Vec<(Pubkey, UserVaultAccount)>>;
+
+    async fn unix_timestamp(&self) -> Result<i64>;
+
+    async fn send(&self, instructions: &[Instruction], payer: &Keypair) -> Result<Signature>;
+}
+
+pub struct RpcChain {
+    pub rpc: RpcClient,
+}
+
+#[async_trait]
+impl Chain for RpcChain {
+    async fn get_account(&self, key: &Pubkey) -> Result<Option<Account>> {
+        Ok(self
+            .rpc
+            .get_account_with_commitment(key, self.rpc.commitment())
+            .await?
+            .value)
+    }
+
+    async fn user_vault_accounts(&self, program_id: &Pubkey, vault: &Pubkey) -> Result<Vec<(Pubkey, UserVaultAccount)>> {
+        let config = RpcProgramAccountsConfig {
+            filters: Some(user_vault_account_filters(vault)),
+            account_config: RpcAccountInfoConfig {
+                commitment: Some(self.rpc.commitment()),
+                ..RpcAccountInfoConfig::default()
+            },
+            with_context: None,
+        };
+        self.rpc
+            .get_program_accounts_with_config(program_id, config)
+            .await?
+            .into_iter()
+            .map(|(key, account)| Ok((key, decode_user_vault_account(&key, &account.data)?)))
+            .collect()
+    }
+
+    async fn unix_timestamp(&self) -> Result<i64> {
+        let slot = self.rpc.get_slot().await?;
+        Ok(self.rpc.get_block_time(slot).await?)
+    }
+
+    async fn send(&self, instructions: &[Instruction], payer: &Keypair) -> Result<Signature> {
+        let blockhash = self.rpc.get_latest_blockhash().await?;
+        let transaction =
+            Transaction::new_signed_with_payer(instructions, Some(&payer.pubkey()), &[payer], blockhash);
+        Ok(self.rpc.send_and_confirm_transaction(&transaction).await?)
+    }
+}
@@ -0,0 +1,261 @@
+//! Settlement cycle of a vault: balances first, then the queues in order.
+
+use crate::chain::Chain;
+use crate::metrics::Metrics;
+use anchor_lang::prelude::Pubkey;
+use anyhow::{anyhow, Result};
+use log::{error, info, warn};
+use m_protocol::state::{RequestEntry, UserVaultAccount, Vault};
+use merstab_client::instruction;
+use merstab_client::queue::{settleable_stakes, settleable_unstakes};
+use merstab_client::{
+    decode_mango_account, decode_stake_req, decode_unstake_req, decode_user_vault_account, decode_vault, MangoAccounts, VaultKeys,
+};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::{Keypair, Signature};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+// * update_user_balance instructions packed per transaction
+const USER_UPDATES_PER_TX: usize = 8;
+
+#[derive(Clone, Debug)]
+pub struct KeeperConfig {
+    pub interval: Duration,
+    pub max_retries: u32,
+    pub retry_delay: Duration,
+    pub batch_size: usize, // * users settled per batch transaction
+    pub metrics_file: Option<PathBuf>,
+}
+
+impl Default for KeeperConfig {
+    fn default() -> Self {
+        KeeperConfig {
+            interval: Duration::from_secs(60),
+            max_retries: 3,
+            retry_delay: Duration::from_secs(2),
+            batch_size: 5,
+            metrics_file: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SettleReport {
+    pub processed: usize,
+    pub skipped: usize,
+}
+
+pub struct Keeper<C: Chain> {
+    pub chain: C,
+    pub payer: Keypair,
+    pub program_id: Pubkey,
+    pub mango: MangoAccounts,
+    pub config: KeeperConfig,
+    pub metrics: Metrics,
+}
+
+impl<C: Chain> Keeper<C> {
+    pub fn new(chain: C, payer: Keypair, program_id: Pubkey, mango: MangoAccounts, config: KeeperConfig) -> Self {
+        Keeper {
+            chain,
+            payer,
+            program_id,
+            mango,
+            config,
+            metrics: Metrics::default(),
+        }
+    }
+
+    // * runs a cycle for every vault at the configured cadence, a failed cycle is retried at the next tick
+    pub async fn run(&mut self, vaults: &[Pubkey]) -> Result<()> {
+        let mut interval = tokio::time::interval(self.config.interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            for vault in vaults {
+                let started = Instant::now();
+                match self.run_cycle(vault).await {
+                    Ok(()) => info!("vault {} settled", vault),
+                    Err(error) => {
+                        self.metrics.cycle_failures += 1;
+                        error!("vault {} cycle failed: {:#}", vault, error);
+                    }
+                }
+                self.metrics.cycles += 1;
+                self.metrics.last_cycle_millis = started.elapsed().as_millis() as u64;
+            }
+
+            info!("{:?}", self.metrics);
+            if let Some(path) = &self.config.metrics_file {
+                if let Err(error) = std::fs::write(path, self.metrics.render_prometheus()) {
+                    warn!("failed to write metrics to {}: {}", path.display(), error);
+                }
+            }
+        }
+    }
+
+    pub async fn run_cycle(&mut self, vault: &Pubkey) -> Result<()> {
+        let keys = self.vault_keys(vault).await?;
+
+        // * requests left over by a failed cycle would block the balance update
+        self.settle_stakes(&keys).await?;
+        self.settle_unstakes(&keys).await?;
+
+        self.update_balances(&keys).await?;
+        let stakes = self.settle_stakes(&keys).await?;
+        let unstakes = self.settle_unstakes(&keys).await?;
+
+        info!(
+            "vault {}: {} stakes and {} unstakes processed, {} skipped",
+            vault,
+            stakes.processed,
+            unstakes.processed,
+            stakes.skipped + unstakes.skipped
+        );
+        Ok(())
+    }
+
+    pub async fn vault_keys(&self, vault: &Pubkey) -> Result<VaultKeys> {
+        let data = self.vault(vault).await?;
+        let keys = VaultKeys::new(self.program_id, *vault, &data, self.mango);
+        // * withdrawals pass mango the open orders of the margin basket
+        match self.chain.get_account(&keys.mango_account).await? {
+            Some(account) if account.owner == self.mango.program_id => {
+                let mango_account = decode_mango_account(&keys.mango_account, &account.data)?;
+                Ok(keys.with_margin_basket(&mango_account))
+            }
+            Some(_) => Err(anyhow!("account {} is not owned by mango", keys.mango_account)),
+            None => Ok(keys),
+        }
+    }
+
+    // * closes the epoch at the new nav, then refreshes the equity of every user
+    pub async fn update_balances(&mut self, keys: &VaultKeys) -> Result<()> {
+        self.send(&[instruction::update_vault_balance(keys)]).await?;
+
+        let users = self.chain.user_vault_accounts(&self.program_id, &keys.vault).await?;
+        for chunk in users.chunks(USER_UPDATES_PER_TX) {
+            let instructions: Vec<_> = chunk
+                .iter()
+                .map(|(_, user)| instruction::update_user_balance(keys, &user.authority))
+                .collect();
+            self.send(&instructions).await?;
+            self.metrics.users_updated += chunk.len() as u64;
+        }
+        Ok(())
+    }
+
+    pub async fn settle_stakes(&mut self, keys: &VaultKeys) -> Result<SettleReport> {
+        let vault = self.vault(&keys.vault).await?;
+        let stake_req = self.required(&keys.stake_request_account, decode_stake_req).await?;
+        let entries = settleable_stakes(&vault, &stake_req.queue);
+
+        let (users, report) = self
+            .classify(&entries, |user| user.user_stake.stake_request_active && !user.user_stake.cancel)
+            .await?;
+        for chunk in users.chunks(self.config.batch_size.max(1)) {
+            self.send(&[instruction::process_stake_batch(keys, chunk)]).await?;
+        }
+
+        self.metrics.stakes_processed += report.processed as u64;
+        self.metrics.requests_skipped += report.skipped as u64;
+        Ok(report)
+    }
+
+    pub async fn settle_unstakes(&mut self, keys: &VaultKeys) -> Result<SettleReport> {
+        let vault = self.vault(&keys.vault).await?;
+        let unstake_req = self.required(&keys.unstake_request_account, decode_unstake_req).await?;
+        let now = self.chain.unix_timestamp().await?;
+        let entries = settleable_unstakes(&vault, &unstake_req.queue, now);
+
+        let (users, report) = self
+            .classify(&entries, |user| user.user_unstake.unstake_request_active && !user.user_unstake.cancel)
+            .await?;
+        for chunk in users.chunks(self.config.batch_size.max(1)) {
+            self.send(&[instruction::process_unstake_batch(keys, chunk)]).await?;
+        }
+
+        self.metrics.unstakes_processed += report.processed as u64;
+        self.metrics.requests_skipped += report.skipped as u64;
+        Ok(report)
+    }
+
+    // * sends with a fresh blockhash on every attempt, backing off linearly between attempts
+    pub async fn send(&mut self, instructions: &[Instruction]) -> Result<Signature> {
+        let mut attempt = 0;
+        loop {
+            match self.chain.send(instructions, &self.payer).await {
+                Ok(signature) => {
+                    self.metrics.transactions_sent += 1;
+                    return Ok(signature);
+                }
+                Err(error) if attempt < self.config.max_retries => {
+                    attempt += 1;
+                    self.metrics.retries += 1;
+                    warn!("transaction failed, retry {}/{}: {:#}", attempt, self.config.max_retries, error);
+                    tokio::time::sleep(self.config.retry_delay * attempt).await;
+                }
+                Err(error) => {
+                    self.metrics.transactions_failed += 1;
+                    return Err(error);
+                }
+            }
+        }
+    }
+
+    // * batches settle the selected entries in queue order, so cancelled requests ride along and are only
+    // * dequeued on chain, the batch stops before an entry whose user account no longer exists
+    async fn classify(
+        &self,
+        entries: &[RequestEntry],
+        is_active: impl Fn(&UserVaultAccount) -> bool,
+    ) -> Result<(Vec<Pubkey>, SettleReport)> {
+        let mut users = Vec::new();
+        let mut report = SettleReport::default();
+        for entry in entries {
+            let user_vault_account = entry.user_vault_account;
+            match self.user(&user_vault_account).await? {
+                Some(user) => {
+                    if is_active(&user) {
+                        report.processed += 1;
+                    } else {
+                        report.skipped += 1;
+                    }
+                    users.push(user.authority);
+                }
+                None => {
+                    warn!("queued user vault account {} no longer exists", user_vault_account);
+                    break;
+                }
+            }
+        }
+        Ok((users, report))
+    }
+
+    async fn vault(&self, key: &Pubkey) -> Result<Vault> {
+        self.required(key, decode_vault).await
+    }
+
+    async fn user(&self, key: &Pubkey) -> Result<Option<UserVaultAccount>> {
+        self.fetch(key, decode_user_vault_account).await
+    }
+
+    async fn required<T>(&self, key: &Pubkey, decode: fn(&Pubkey, &[u8]) -> merstab_client::Result<T>) -> Result<T> {
+        self.fetch(key, decode)
+            .await?
+            .ok_or_else(|| anyhow!("account {} not found", key))
+    }
+
+    async fn fetch<T>(
+        &self,
+        key: &Pubkey,
+        decode: fn(&Pubkey, &[u8]) -> merstab_client::Result<T>,
+    ) -> Result<Option<T>> {
+        match self.chain.get_account(key).await? {
+            Some(account) if account.owner == self.program_id => Ok(Some(decode(key, &account.data)?)),
+            Some(_) => Err(anyhow!("account {} is not owned by the program", key)),
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,9 @@
+//! Keeper settling merstab vaults: nav updates, user balances and the request queues.
+
+pub mod chain;
+pub mod keeper;
+pub mod metrics;
+
+pub use chain::*;
+pub use keeper::*;
+pub use metrics::*;
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::Pubkey;
+use anyhow::anyhow;
+use clap::Parser;
+use merstab_client::MangoAccounts;
+use merstab_keeper::{Keeper, KeeperConfig, RpcChain};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::read_keypair_file;
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[derive(Parser)]
+#[clap(name = "merstab-keeper", version, about = "Settle merstab vault queues and balances on a schedule")]
+struct Opts {
+    #[clap(long, short = 'u', env = "MERSTAB_RPC_URL", default_value = "http://localhost:8899")]
+    url: String,
+
+    /// Keypair of the vault keeper, also pays for the transactions
+    #[clap(long, short = 'k', env = "MERSTAB_KEYPAIR")]
+    keypair: PathBuf,
+
+    #[clap(long, env = "MERSTAB_PROGRAM_ID", default_value_t = m_protocol::ID)]
+    program_id: Pubkey,
+
+    /// Vaults to settle, in the order they are processed every cycle
+    #[clap(long = "vault", required = true)]
+    vaults: Vec<Pubkey>,
+
+    /// Seconds between cycles
+    #[clap(long, default_value_t = 60)]
+    interval: u64,
+
+    #[clap(long, default_value_t = 3)]
+    max_retries: u32,
+
+    /// Milliseconds before the first retry, later retries wait longer
+    #[clap(long, default_value_t = 2_000)]
+    retry_delay_ms: u64,
+
+    /// Users settled per batch transaction
+    #[clap(long, default_value_t = 5)]
+    batch_size: usize,
+
+    /// Prometheus textfile the metrics are written to after every cycle
+    #[clap(long)]
+    metrics_file: Option<PathBuf>,
+
+    #[clap(long, env = "MANGO_PROGRAM_ID")]
+    mango_program: Pubkey,
+    #[clap(long, env = "MANGO_GROUP")]
+    mango_group: Pubkey,
+    #[clap(long, env = "MANGO_CACHE")]
+    mango_cache: Pubkey,
+    #[clap(long, env = "MANGO_ROOT_BANK")]
+    mango_root_bank: Pubkey,
+    #[clap(long, env = "MANGO_NODE_BANK")]
+    mango_node_bank: Pubkey,
+    #[clap(long, env = "MANGO_VAULT")]
+    mango_vault: Pubkey,
+    #[clap(long, env = "MANGO_SIGNER")]
+    mango_signer: Pubkey,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+    let opts = Opts::parse();
+
+    let payer = read_keypair_file(&opts.keypair)
+        .map_err(|error| anyhow!("failed to read keypair {}: {}", opts.keypair.display(), error))?;
+    let chain = RpcChain {
+        rpc: RpcClient::new_with_commitment(opts.url, CommitmentConfig::confirmed()),
+    };
+    let mango = MangoAccounts {
+        program_id: opts.mango_program,
+        group: opts.mango_group,
+        cache: opts.mango_cache,
+        root_bank: opts.mango_root_bank,
+        node_bank: opts.mango_node_bank,
+        vault: opts.mango_vault,
+        signer: opts.mango_signer,
+    };
+    let config = KeeperConfig {
+        interval: Duration::from_secs(opts.interval),
+        max_retries: opts.max_retries,
+        retry_delay: Duration::from_millis(opts.retry_delay_ms),
+        batch_size: opts.batch_size,
+        metrics_file: opts.metrics_file,
+    };
+
+    let mut keeper = Keeper::new(chain, payer, opts.program_id, mango, config);
+    keeper.run(&opts.vaults).await
+}
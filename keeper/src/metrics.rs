@@ -0,0 +1,43 @@
+//! Counters kept across cycles, logged after every cycle and optionally written
+//! in the prometheus text format for a node exporter textfile collector.
+
+use std::fmt::Write;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Metrics {
+    pub cycles: u64,
+    pub cycle_failures: u64,
+    pub transactions_sent: u64,
+    pub transactions_failed: u64,
+    pub retries: u64,
+    pub users_updated: u64,
+    pub stakes_processed: u64,
+    pub unstakes_processed: u64,
+    pub requests_skipped: u64,
+    pub last_cycle_millis: u64,
+}
+
+impl Metrics {
+    pub fn render_prometheus(&self) -> String {
+        let counters = [
+            ("cycles_total", self.cycles),
+            ("cycle_failures_total", self.cycle_failures),
+            ("transactions_sent_total", self.transactions_sent),
+            ("transactions_failed_total", self.transactions_failed),
+            ("retries_total", self.retries),
+            ("users_updated_total", self.users_updated),
+            ("stakes_processed_total", self.stakes_processed),
+            ("unstakes_processed_total", self.unstakes_processed),
+            ("requests_skipped_total", self.requests_skipped),
+        ];
+
+        let mut output = String::new();
+        for (name, value) in counters {
+            let _ = writeln!(output, "# TYPE merstab_keeper_{} counter", name);
+            let _ = writeln!(output, "merstab_keeper_{} {}", name, value);
+        }
+        let _ = writeln!(output, "# TYPE merstab_keeper_last_cycle_milliseconds gauge");
+        let _ = writeln!(output, "merstab_keeper_last_cycle_milliseconds {}", self.last_cycle_millis);
+        output
+    }
+}
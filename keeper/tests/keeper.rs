@@ -0,0 +1,167 @@
+#[path = "../../programs/m-protocol/tests/common/mod.rs"]
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::AccountDeserialize;
+use async_trait::async_trait;
+use common::{Harness, User};
+use m_protocol::state::UserVaultAccount;
+use merstab_client::{instruction, VaultKeys};
+use merstab_keeper::{Chain, Keeper, KeeperConfig};
+use solana_sdk::account::Account;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::{Keypair, Signature};
+use solana_sdk::sysvar::clock::Clock;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+// * the harness bank behind the chain, user accounts are tracked by the test since banks have no program account scan
+struct BanksChain {
+    harness: Mutex<Harness>,
+    users: Vec<Pubkey>,
+    failures: AtomicU32, // * sends that fail before reaching the bank
+}
+
+#[async_trait]
+impl Chain for BanksChain {
+    async fn get_account(&self, key: &Pubkey) -> anyhow::Result<Option<Account>> {
+        Ok(self.harness.lock().await.context.banks_client.get_account(*key).await?)
+    }
+
+    async fn user_vault_accounts(&self, _program_id: &Pubkey, _vault: &Pubkey) -> anyhow::Result<Vec<(Pubkey, UserVaultAccount)>> {
+        let mut users = Vec::new();
+        for key in &self.users {
+            if let Some(account) = self.get_account(key).await? {
+                users.push((*key, UserVaultAccount::try_deserialize(&mut &account.data[..])?));
+            }
+        }
+        Ok(users)
+    }
+
+    async fn unix_timestamp(&self) -> anyhow::Result<i64> {
+        Ok(self.harness.lock().await.context.banks_client.get_sysvar::<Clock>().await?.unix_timestamp)
+    }
+
+    async fn send(&self, instructions: &[Instruction], payer: &Keypair) -> anyhow::Result<Signature> {
+        if self
+            .failures
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |failures| failures.checked_sub(1))
+            .is_ok()
+        {
+            anyhow::bail!("injected failure");
+        }
+        Ok(self.harness.lock().await.process_signed(instructions, &[payer]).await?)
+    }
+}
+
+struct Setup {
+    keeper: Keeper<BanksChain>,
+    keys: VaultKeys,
+    users: Vec<User>,
+}
+
+// * a vault on the mock mango group with a user per amount, each staking it in epoch 0
+async fn setup(stakes: &[u64]) -> Setup {
+    let mut harness = Harness::new().await;
+    let mut users = Vec::new();
+    for amount in stakes {
+        let user = harness.add_user(*amount).await;
+        harness.deposit_and_stake(&user, *amount).await.unwrap();
+        users.push(user);
+    }
+
+    // * the harness admin is also the vault keeper
+    let keys = harness.keys.clone();
+    let payer = Keypair::from_bytes(&harness.admin.to_bytes()).unwrap();
+    let chain = BanksChain {
+        harness: Mutex::new(harness),
+        users: users.iter().map(|user| keys.user_vault_account(&user.pubkey())).collect(),
+        failures: AtomicU32::new(0),
+    };
+    let config = KeeperConfig {
+        retry_delay: Duration::from_millis(1),
+        ..KeeperConfig::default()
+    };
+    Setup {
+        keeper: Keeper::new(chain, payer, m_protocol::ID, keys.mango, config),
+        keys,
+        users,
+    }
+}
+
+#[tokio::test]
+async fn leaves_requests_of_the_open_epoch_queued() {
+    let Setup { mut keeper, keys, .. } = setup(&[100_000_000]).await;
+
+    keeper.settle_stakes(&keys).await.unwrap();
+    keeper.settle_unstakes(&keys).await.unwrap();
+
+    assert_eq!(keeper.chain.harness.lock().await.stake_queue_len().await, 1);
+    assert_eq!(keeper.metrics.transactions_sent, 0);
+}
+
+#[tokio::test]
+async fn a_cycle_closes_the_epoch_and_settles_its_stakes() {
+    let Setup { mut keeper, keys, users } = setup(&[100_000_000, 300_000_000]).await;
+
+    keeper.run_cycle(&keys.vault).await.unwrap();
+
+    let mut harness = keeper.chain.harness.lock().await;
+    assert_eq!(harness.vault().await.epoch.current, 1);
+    assert_eq!(harness.stake_queue_len().await, 0);
+    assert_eq!(harness.mango_deposit().await, 400_000_000);
+    assert_eq!(harness.share_balance(&users[0]).await, 100_000_000);
+    assert_eq!(harness.share_balance(&users[1]).await, 300_000_000);
+    assert_eq!(keeper.metrics.stakes_processed, 2);
+    assert_eq!(keeper.metrics.users_updated, 2);
+}
+
+#[tokio::test]
+async fn balance_updates_recognize_pnl_before_unstakes_pay_out() {
+    let Setup { mut keeper, keys, users } = setup(&[100_000_000, 300_000_000]).await;
+    keeper.run_cycle(&keys.vault).await.unwrap();
+    {
+        let mut harness = keeper.chain.harness.lock().await;
+        harness.realize_pnl(40_000_000).await;
+        let ix = instruction::request_to_unstake(&harness.keys, &users[0].pubkey(), 0, true);
+        harness.process(&[ix], &[&users[0].wallet]).await.unwrap();
+    }
+
+    keeper.run_cycle(&keys.vault).await.unwrap();
+
+    let mut harness = keeper.chain.harness.lock().await;
+    let user_token_account = keys.user_token_account(&users[0].pubkey());
+    assert_eq!(harness.unstake_queue_len().await, 0);
+    assert_eq!(harness.token_balance(&user_token_account).await, 110_000_000);
+    assert_eq!(harness.share_balance(&users[0]).await, 0);
+    assert_eq!(harness.user_vault_account(&users[1]).await.equity, 330_000_000);
+    assert_eq!(harness.vault().await.total_equity, 330_000_000);
+    assert_eq!(keeper.metrics.unstakes_processed, 1);
+}
+
+#[tokio::test]
+async fn retries_failed_transactions() {
+    let Setup { mut keeper, keys, .. } = setup(&[100_000_000]).await;
+    keeper.chain.failures.store(2, Ordering::SeqCst);
+
+    keeper.run_cycle(&keys.vault).await.unwrap();
+
+    assert_eq!(keeper.chain.harness.lock().await.stake_queue_len().await, 0);
+    assert_eq!(keeper.metrics.retries, 2);
+    assert_eq!(keeper.metrics.transactions_failed, 0);
+}
+
+#[tokio::test]
+async fn gives_up_after_max_retries() {
+    let Setup { mut keeper, keys, .. } = setup(&[100_000_000]).await;
+    keeper.chain.failures.store(keeper.config.max_retries + 1, Ordering::SeqCst);
+
+    assert!(keeper.run_cycle(&keys.vault).await.is_err());
+
+    let mut harness = keeper.chain.harness.lock().await;
+    assert_eq!(harness.vault().await.epoch.current, 0);
+    assert_eq!(harness.stake_queue_len().await, 1);
+    assert_eq!(keeper.metrics.retries, keeper.config.max_retries as u64);
+    assert_eq!(keeper.metrics.transactions_failed, 1);
+}
@@ -1,6 +1,33 @@
 pub const VAULT_SEED: &[u8] = b"vault";
 pub const USDC_SEED: &[u8] = b"usdc";
 pub const VAULT_PDA_AUTHORITY_SEED: &[u8] = b"pdaauthority";
+pub const SHARES_SEED: &[u8] = b"shares";
+pub const CONFIG_SEED: &[u8] = b"config";
+pub const RISK_PARAMS_SEED: &[u8] = b"risk";
+pub const ACCESS_ENTRY_SEED: &[u8] = b"access";
+pub const INSURANCE_SEED: &[u8] = b"insurance";
+pub const OPEN_ORDERS_SEED: &[u8] = b"OpenOrders"; // * mango open orders pda seed
 
-pub const MANAGER_PUBKEY: &str = "EQcDizVxte35CPSaShS91x5iNspZhoqeTchz8b9BEJZg";
-pub const MAX_REQUESTS: usize = 1000;
\ No newline at end of file
+pub const MAX_VAULT_CREATORS: usize = 10;
+pub const MAX_REQUESTS: usize = 1000;
+pub const BATCH_ACCOUNTS_PER_USER: usize = 3;
+pub const STAKE_BATCH_ACCOUNTS_PER_USER: usize = 4; // * the user's access entry follows
+
+pub const BPS_DENOMINATOR: u64 = 10_000;
+pub const SECONDS_PER_YEAR: u64 = 31_536_000;
+pub const SHARE_PRICE_SCALE: u64 = 1_000_000_000;
+pub const PNL_SCALE: i128 = 1_000_000_000;
+pub const MAX_MANAGEMENT_FEE_BPS: u16 = 500;
+pub const MAX_PERFORMANCE_FEE_BPS: u16 = 5_000;
+pub const MAX_EARLY_WITHDRAWAL_PENALTY_BPS: u16 = 1_000;
+pub const MAX_LOCKUP_SECONDS: i64 = 90 * 86_400;
+pub const MAX_UNSTAKE_COOLDOWN_SECONDS: i64 = 30 * 86_400;
+
+// * pause bits, set on the protocol config or a single vault
+pub const PAUSE_DEPOSITS: u8 = 1 << 0;
+pub const PAUSE_WITHDRAWALS: u8 = 1 << 1;
+pub const PAUSE_STAKE_REQUESTS: u8 = 1 << 2;
+pub const PAUSE_UNSTAKE_REQUESTS: u8 = 1 << 3;
+pub const PAUSE_KEEPER: u8 = 1 << 4;
+pub const PAUSE_MANGO: u8 = 1 << 5;
+pub const PAUSE_ALL: u8 = (1 << 6) - 1;
\ No newline at end of file
@@ -43,4 +43,172 @@ pub enum ErrorCode {
 
     #[msg("the stake mint does not match")]
     StakeMintMismatch, //308, 0x134
+
+    #[msg("Signer Is Not An Approved Vault Creator")]
+    NotVaultCreator,
+
+    #[msg("Vault Creator Already Approved")]
+    CreatorAlreadyApproved,
+
+    #[msg("Vault Creator Not Found")]
+    CreatorNotFound,
+
+    #[msg("Max Vault Creators Limit Hit")]
+    MaxCreatorsLimit,
+
+    #[msg("Signer Is Not The Pending Admin")]
+    NotPendingAdmin,
+
+    #[msg("Trader Signer Only")]
+    NotTrader,
+
+    #[msg("Keeper Signer Only")]
+    NotKeeper,
+
+    #[msg("Fee Rate Exceeds Maximum")]
+    InvalidFeeRate,
+
+    #[msg("No Fees To Collect")]
+    NoFeesToCollect,
+
+    #[msg("Remaining Accounts Do Not Match The Expected Layout")]
+    InvalidRemainingAccounts,
+
+    #[msg("User Account Does Not Match The Queued Request")]
+    RequestOrderMismatch,
+
+    #[msg("Request Queue Is Full")]
+    QueueFull,
+
+    #[msg("Request Not Found In Queue")]
+    RequestNotQueued,
+
+    #[msg("Request Epoch Has Not Been Settled Yet")]
+    EpochNotSettled,
+
+    #[msg("Request Epoch Is Closed, Request Can No Longer Change")]
+    RequestEpochClosed,
+
+    #[msg("Requests From The Closed Epoch Are Still Queued")]
+    UnsettledRequests,
+
+    #[msg("Math Operation Overflowed")]
+    MathOverflow,
+
+    #[msg("Attempted To Divide By Zero")]
+    DivideByZero,
+
+    #[msg("Failed To Load Mango Group")]
+    MangoGroupLoadFailed,
+
+    #[msg("Failed To Load Mango Account")]
+    MangoAccountLoadFailed,
+
+    #[msg("Failed To Load Mango Cache")]
+    MangoCacheLoadFailed,
+
+    #[msg("Root Bank Not Found In Mango Group")]
+    RootBankNotFound,
+
+    #[msg("Market Index Out Of Range")]
+    InvalidMarketIndex,
+
+    #[msg("Open Orders Already Created For Market")]
+    OpenOrdersAlreadyCreated,
+
+    #[msg("Open Orders Not Tracked By Vault")]
+    OpenOrdersNotTracked,
+
+    #[msg("Order Price And Quantities Must Be Non Zero")]
+    InvalidOrderQuantity,
+
+    #[msg("Market Not Allowed By Vault Risk Params")]
+    MarketNotAllowed,
+
+    #[msg("Order Exceeds Max Notional For Market")]
+    MaxNotionalExceeded,
+
+    #[msg("Order Exceeds Max Vault Leverage")]
+    MaxLeverageExceeded,
+
+    #[msg("Mango Health Below Vault Minimum")]
+    HealthBelowMinimum,
+
+    #[msg("Invalid Risk Params")]
+    InvalidRiskParams,
+
+    #[msg("Vault Halted")]
+    VaultHalted,
+
+    #[msg("Vault Not Halted")]
+    VaultNotHalted,
+
+    #[msg("Invalid Drawdown Limit")]
+    InvalidDrawdownLimit,
+
+    #[msg("Operation Paused")]
+    Paused,
+
+    #[msg("Not Guardian")]
+    NotGuardian,
+
+    #[msg("Invalid Pause Flags")]
+    InvalidPauseFlags,
+
+    #[msg("Missing Or Expired Gateway Token")]
+    InvalidGatewayToken,
+
+    #[msg("Wallet Not On Vault Allowlist")]
+    WalletNotAllowed,
+
+    #[msg("Wallet On Vault Denylist")]
+    WalletDenied,
+
+    #[msg("Stake Lockup Still Active")]
+    LockupActive,
+
+    #[msg("Unstake Cooldown Still Active")]
+    UnstakeCooldownActive,
+
+    #[msg("Invalid Lockup Period")]
+    InvalidLockupPeriod,
+
+    #[msg("Invalid Early Withdrawal Penalty")]
+    InvalidPenalty,
+
+    #[msg("Insurance Fund Already Created")]
+    InsuranceFundAlreadyCreated,
+
+    #[msg("Insurance Fund Not Created")]
+    InsuranceFundNotCreated,
+
+    #[msg("No Insurance To Collect")]
+    NoInsuranceToCollect,
+
+    #[msg("Insurance Injection Not Allowed")]
+    InsuranceInjectionNotAllowed,
+
+    #[msg("Fees Must Be Accrued By A Balance Update First")]
+    FeesNotAccrued,
+
+    #[msg("Accrued Fees Must Be Collected Before Changing Recipient")]
+    FeesNotCollected,
+
+    #[msg("Share Price Is Zero")]
+    ZeroSharePrice,
+
+    #[msg("Delegation Not Allowed Once Risk Params Exist")]
+    DelegationNotAllowed,
+
+    #[msg("Only The Guardian, Admin Or Trader Can Revoke The Delegate")]
+    NotDelegateAuthority,
+
+    #[msg("Failed To Load Open Orders")]
+    OpenOrdersLoadFailed,
+
+    #[msg("Mango Program Does Not Match The Vault")]
+    MangoProgramNotMatch,
+
+    #[msg("Mango Group Does Not Match The Vault")]
+    MangoGroupNotMatch,
 }
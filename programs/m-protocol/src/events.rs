@@ -0,0 +1,464 @@
+use crate::state::*;
+
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct ConfigInitialized {
+    pub config: Pubkey,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct AdminProposed {
+    pub config: Pubkey,
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
+#[event]
+pub struct AdminAccepted {
+    pub config: Pubkey,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct GuardianUpdated {
+    pub config: Pubkey,
+    pub guardian: Pubkey,
+}
+
+#[event]
+pub struct ProtocolPauseUpdated {
+    pub config: Pubkey,
+    pub authority: Pubkey,
+    pub paused: u8,
+}
+
+#[event]
+pub struct VaultCreatorAdded {
+    pub config: Pubkey,
+    pub creator: Pubkey,
+}
+
+#[event]
+pub struct VaultCreatorRemoved {
+    pub config: Pubkey,
+    pub creator: Pubkey,
+}
+
+#[event]
+pub struct VaultCreated {
+    pub vault: Pubkey,
+    pub creator: Pubkey,
+    pub name: String,
+    pub limit: u64,
+    pub mint: Pubkey,
+    pub share_mint: Pubkey,
+    pub stake_request_account: Pubkey,
+    pub unstake_request_account: Pubkey,
+}
+
+#[event]
+pub struct VaultRoleUpdated {
+    pub vault: Pubkey,
+    pub role: VaultRole,
+    pub old_key: Pubkey,
+    pub new_key: Pubkey,
+}
+
+#[event]
+pub struct VaultLimitUpdated {
+    pub vault: Pubkey,
+    pub old_limit: u64,
+    pub new_limit: u64,
+}
+
+#[event]
+pub struct VaultFeesUpdated {
+    pub vault: Pubkey,
+    pub management_fee_bps: u16,
+    pub performance_fee_bps: u16,
+    pub fee_recipient: Pubkey,
+}
+
+#[event]
+pub struct RiskParamsInitialized {
+    pub vault: Pubkey,
+    pub risk_params: Pubkey,
+    pub allowed_markets: u16,
+    pub max_leverage_bps: u64,
+    pub min_health_ratio_bps: u64,
+}
+
+#[event]
+pub struct MarketRiskUpdated {
+    pub vault: Pubkey,
+    pub market_index: u64,
+    pub allowed: bool,
+    pub max_notional: u64,
+}
+
+#[event]
+pub struct RiskLimitsUpdated {
+    pub vault: Pubkey,
+    pub max_leverage_bps: u64,
+    pub min_health_ratio_bps: u64,
+}
+
+#[event]
+pub struct VaultPauseUpdated {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub paused: u8,
+}
+
+#[event]
+pub struct GatekeeperNetworkUpdated {
+    pub vault: Pubkey,
+    pub gatekeeper_network: Option<Pubkey>,
+}
+
+#[event]
+pub struct AccessModeUpdated {
+    pub vault: Pubkey,
+    pub access_mode: AccessMode,
+}
+
+#[event]
+pub struct AccessEntryUpdated {
+    pub vault: Pubkey,
+    pub wallet: Pubkey,
+    pub allowed: bool,
+    pub denied: bool,
+}
+
+#[event]
+pub struct AccessEntryRemoved {
+    pub vault: Pubkey,
+    pub wallet: Pubkey,
+}
+
+#[event]
+pub struct LockupUpdated {
+    pub vault: Pubkey,
+    pub lockup_seconds: i64,
+    pub unstake_cooldown_seconds: i64,
+}
+
+#[event]
+pub struct WithdrawalPenaltyUpdated {
+    pub vault: Pubkey,
+    pub early_withdrawal_penalty_bps: u16,
+    pub penalty_period_seconds: i64,
+    pub penalty_recipient: PenaltyRecipient,
+}
+
+#[event]
+pub struct DrawdownLimitsUpdated {
+    pub vault: Pubkey,
+    pub max_daily_drawdown_bps: u16,
+    pub max_drawdown_bps: u16,
+}
+
+#[event]
+pub struct VaultHalted {
+    pub vault: Pubkey,
+    pub reason: HaltReason,
+    pub day_pnl: i128,
+    pub share_price: u64,
+    pub peak_share_price: u64,
+}
+
+#[event]
+pub struct VaultHaltCleared {
+    pub vault: Pubkey,
+    pub reason: HaltReason,
+    pub peak_share_price: u64,
+}
+
+#[event]
+pub struct FeesCollected {
+    pub vault: Pubkey,
+    pub fee_recipient_token_account: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct InsuranceFundCreated {
+    pub vault: Pubkey,
+    pub insurance_fund: Pubkey,
+}
+
+#[event]
+pub struct InsuranceParamsUpdated {
+    pub vault: Pubkey,
+    pub insurance_fee_bps: u16,
+    pub insurance_trigger_bps: u16,
+}
+
+#[event]
+pub struct InsuranceCollected {
+    pub vault: Pubkey,
+    pub insurance_fund: Pubkey,
+    pub amount: u64,
+    pub insurance_fund_balance: u64,
+}
+
+#[event]
+pub struct InsuranceInjectionUnlocked {
+    pub vault: Pubkey,
+    pub day_pnl: i128,
+    pub insurance_balance: u64,
+}
+
+#[event]
+pub struct InsuranceInjected {
+    pub vault: Pubkey,
+    pub mango_account: Pubkey,
+    pub amount: u64,
+    pub insurance_fund_balance: u64,
+}
+
+#[event]
+pub struct UserVaultAccountCreated {
+    pub vault: Pubkey,
+    pub user_vault_account: Pubkey,
+    pub authority: Pubkey,
+    pub deposit_limit: u64,
+}
+
+#[event]
+pub struct UserDeposited {
+    pub vault: Pubkey,
+    pub user_vault_account: Pubkey,
+    pub authority: Pubkey,
+    pub amount: u64,
+    pub deposit: u64,
+    pub deposit_limit: u64,
+}
+
+#[event]
+pub struct UserWithdrew {
+    pub vault: Pubkey,
+    pub user_vault_account: Pubkey,
+    pub authority: Pubkey,
+    pub amount: u64,
+    pub withdrawal: u64,
+}
+
+#[event]
+pub struct StakeRequested {
+    pub vault: Pubkey,
+    pub user_vault_account: Pubkey,
+    pub amount: u64,
+    pub max: bool,
+    pub epoch: u64,
+    pub status: RequestStatus,
+}
+
+#[event]
+pub struct StakeRequestUpdated {
+    pub vault: Pubkey,
+    pub user_vault_account: Pubkey,
+    pub amount: u64,
+    pub max: bool,
+    pub cancel: bool,
+    pub status: RequestStatus,
+}
+
+#[event]
+pub struct StakeRequestsCleared {
+    pub vault: Pubkey,
+    pub stake_request_account: Pubkey,
+    pub cleared: u32,
+}
+
+#[event]
+pub struct StakeProcessed {
+    pub vault: Pubkey,
+    pub user_vault_account: Pubkey,
+    pub amount: u64,
+    pub shares: u64,
+    pub equity: u64,
+    pub settled_epoch: u64,
+    pub settled_price: u64,
+    pub status: RequestStatus,
+}
+
+#[event]
+pub struct UnstakeRequested {
+    pub vault: Pubkey,
+    pub user_vault_account: Pubkey,
+    pub amount: u64,
+    pub max: bool,
+    pub epoch: u64,
+    pub status: RequestStatus,
+}
+
+#[event]
+pub struct UnstakeRequestUpdated {
+    pub vault: Pubkey,
+    pub user_vault_account: Pubkey,
+    pub amount: u64,
+    pub max: bool,
+    pub cancel: bool,
+    pub status: RequestStatus,
+}
+
+#[event]
+pub struct UnstakeRequestsCleared {
+    pub vault: Pubkey,
+    pub unstake_request_account: Pubkey,
+    pub cleared: u32,
+}
+
+#[event]
+pub struct UnstakeProcessed {
+    pub vault: Pubkey,
+    pub user_vault_account: Pubkey,
+    pub amount: u64,
+    pub shares: u64,
+    pub equity: u64,
+    pub settled_epoch: u64,
+    pub settled_price: u64,
+    pub penalty: u64,
+    pub status: RequestStatus,
+}
+
+#[event]
+pub struct VaultBalanceUpdated {
+    pub vault: Pubkey,
+    pub previous_total_equity: u64,
+    pub total_equity: u64,
+    pub day_pnl: i128,
+    pub management_fee: u64,
+    pub performance_fee: u64,
+    pub closed_epoch: u64,
+    pub settlement_price: u64,
+    pub insurance_balance: u64,
+}
+
+#[event]
+pub struct UserBalanceUpdated {
+    pub vault: Pubkey,
+    pub user_vault_account: Pubkey,
+    pub equity: u64,
+    pub user_pnl: i128,
+}
+
+#[event]
+pub struct MangoAccountCreated {
+    pub vault: Pubkey,
+    pub mango_account: Pubkey,
+    pub account_num: u64,
+}
+
+#[event]
+pub struct MangoAccountClosed {
+    pub vault: Pubkey,
+    pub mango_account: Pubkey,
+}
+
+#[event]
+pub struct MangoDeposited {
+    pub vault: Pubkey,
+    pub mango_account: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct MangoWithdrew {
+    pub vault: Pubkey,
+    pub mango_account: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct MangoAccountDelegated {
+    pub vault: Pubkey,
+    pub mango_account: Pubkey,
+    pub delegate: Pubkey,
+}
+
+#[event]
+pub struct MangoDelegateRevoked {
+    pub vault: Pubkey,
+    pub mango_account: Pubkey,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct PerpOrderPlaced {
+    pub vault: Pubkey,
+    pub mango_account: Pubkey,
+    pub perp_market: Pubkey,
+    pub side: OrderSide,
+    pub price: i64,
+    pub max_base_quantity: i64,
+    pub max_quote_quantity: i64,
+    pub client_order_id: u64,
+    pub order_type: PerpOrderType,
+    pub reduce_only: bool,
+}
+
+#[event]
+pub struct PerpOrderCancelled {
+    pub vault: Pubkey,
+    pub mango_account: Pubkey,
+    pub perp_market: Pubkey,
+    pub order_id: i128,
+}
+
+#[event]
+pub struct AllPerpOrdersCancelled {
+    pub vault: Pubkey,
+    pub mango_account: Pubkey,
+    pub perp_market: Pubkey,
+    pub limit: u8,
+}
+
+#[event]
+pub struct PnlSettled {
+    pub vault: Pubkey,
+    pub mango_account: Pubkey,
+    pub counterparty_mango_account: Pubkey,
+    pub market_index: u64,
+}
+
+#[event]
+pub struct SpotOpenOrdersCreated {
+    pub vault: Pubkey,
+    pub mango_account: Pubkey,
+    pub open_orders: Pubkey,
+    pub market_index: u64,
+}
+
+#[event]
+pub struct SpotOrderPlaced {
+    pub vault: Pubkey,
+    pub mango_account: Pubkey,
+    pub spot_market: Pubkey,
+    pub side: OrderSide,
+    pub limit_price: u64,
+    pub max_coin_qty: u64,
+    pub max_native_pc_qty_including_fees: u64,
+    pub order_type: SpotOrderType,
+    pub client_order_id: u64,
+    pub market_index: u64,
+}
+
+#[event]
+pub struct SpotOrderCancelled {
+    pub vault: Pubkey,
+    pub spot_market: Pubkey,
+    pub open_orders: Pubkey,
+    pub side: OrderSide,
+    pub order_id: u128,
+}
+
+#[event]
+pub struct SpotFundsSettled {
+    pub vault: Pubkey,
+    pub spot_market: Pubkey,
+    pub open_orders: Pubkey,
+}
@@ -0,0 +1,30 @@
+use crate::state::*;
+use crate::events::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        constraint=config.pending_admin == Some(pending_admin.key()) @ ErrorCode::NotPendingAdmin
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    pub pending_admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<AcceptAdmin>) -> Result<()> {
+    ctx.accounts.config.admin = ctx.accounts.pending_admin.key();
+    ctx.accounts.config.pending_admin = None;
+
+    emit!(AdminAccepted {
+        config: ctx.accounts.config.key(),
+        admin: ctx.accounts.config.admin,
+    });
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use crate::state::*;
+use crate::events::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AddVaultCreator<'info> {
+    #[account(
+        mut,
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        has_one=admin @ ErrorCode::NotAdmin
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<AddVaultCreator>, creator: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    if config.is_creator(&creator) {
+        return err!(ErrorCode::CreatorAlreadyApproved)
+    }
+
+    if config.creators.len() >= MAX_VAULT_CREATORS {
+        return err!(ErrorCode::MaxCreatorsLimit)
+    }
+
+    config.creators.push(creator);
+
+    emit!(VaultCreatorAdded {
+        config: config.key(),
+        creator,
+    });
+    Ok(())
+}
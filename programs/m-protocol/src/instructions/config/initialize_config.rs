@@ -0,0 +1,50 @@
+use crate::program::MProtocol;
+use crate::state::*;
+use crate::events::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer=admin,
+        seeds=[CONFIG_SEED.as_ref()],
+        bump,
+        space=8+ProtocolConfig::LEN
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    // * only the program upgrade authority can bootstrap the config
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        constraint=program.programdata_address()? == Some(program_data.key()) @ ErrorCode::NotAdmin
+    )]
+    pub program: Program<'info, MProtocol>,
+
+    #[account(
+        constraint=program_data.upgrade_authority_address == Some(admin.key()) @ ErrorCode::NotAdmin
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeConfig>) -> Result<()> {
+    ctx.accounts.config.admin = ctx.accounts.admin.key();
+    ctx.accounts.config.pending_admin = None;
+    ctx.accounts.config.creators = Vec::new();
+    ctx.accounts.config.bump = *ctx.bumps.get("config").unwrap();
+    ctx.accounts.config.guardian = ctx.accounts.admin.key();
+    ctx.accounts.config.paused = 0;
+
+    emit!(ConfigInitialized {
+        config: ctx.accounts.config.key(),
+        admin: ctx.accounts.config.admin,
+    });
+    Ok(())
+}
@@ -0,0 +1,17 @@
+pub mod initialize_config;
+pub mod propose_admin;
+pub mod accept_admin;
+pub mod add_vault_creator;
+pub mod remove_vault_creator;
+pub mod set_guardian;
+pub mod pause_protocol;
+pub mod unpause_protocol;
+
+pub use initialize_config::*;
+pub use propose_admin::*;
+pub use accept_admin::*;
+pub use add_vault_creator::*;
+pub use remove_vault_creator::*;
+pub use set_guardian::*;
+pub use pause_protocol::*;
+pub use unpause_protocol::*;
@@ -0,0 +1,35 @@
+use crate::state::*;
+use crate::events::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct PauseProtocol<'info> {
+    #[account(
+        mut,
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        constraint=config.guardian == authority.key() || config.admin == authority.key() @ ErrorCode::NotGuardian
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+// * pausing only adds bits, the guardian can never lift a pause
+pub fn handler(ctx: Context<PauseProtocol>, flags: u8) -> Result<()> {
+    if flags == 0 || flags & !PAUSE_ALL != 0 {
+        return err!(ErrorCode::InvalidPauseFlags)
+    }
+
+    ctx.accounts.config.paused |= flags;
+
+    emit!(ProtocolPauseUpdated {
+        config: ctx.accounts.config.key(),
+        authority: ctx.accounts.authority.key(),
+        paused: ctx.accounts.config.paused,
+    });
+    Ok(())
+}
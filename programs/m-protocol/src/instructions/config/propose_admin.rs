@@ -0,0 +1,30 @@
+use crate::state::*;
+use crate::events::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    #[account(
+        mut,
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        has_one=admin @ ErrorCode::NotAdmin
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+    ctx.accounts.config.pending_admin = Some(new_admin);
+
+    emit!(AdminProposed {
+        config: ctx.accounts.config.key(),
+        admin: ctx.accounts.config.admin,
+        pending_admin: new_admin,
+    });
+    Ok(())
+}
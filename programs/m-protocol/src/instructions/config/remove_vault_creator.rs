@@ -0,0 +1,37 @@
+use crate::state::*;
+use crate::events::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RemoveVaultCreator<'info> {
+    #[account(
+        mut,
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        has_one=admin @ ErrorCode::NotAdmin
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RemoveVaultCreator>, creator: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    let index = config
+        .creators
+        .iter()
+        .position(|key| *key == creator)
+        .ok_or(ErrorCode::CreatorNotFound)?;
+
+    config.creators.swap_remove(index);
+
+    emit!(VaultCreatorRemoved {
+        config: config.key(),
+        creator,
+    });
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use crate::state::*;
+use crate::events::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UnpauseProtocol<'info> {
+    #[account(
+        mut,
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        has_one=admin @ ErrorCode::NotAdmin
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<UnpauseProtocol>, flags: u8) -> Result<()> {
+    if flags == 0 || flags & !PAUSE_ALL != 0 {
+        return err!(ErrorCode::InvalidPauseFlags)
+    }
+
+    ctx.accounts.config.paused &= !flags;
+
+    emit!(ProtocolPauseUpdated {
+        config: ctx.accounts.config.key(),
+        authority: ctx.accounts.admin.key(),
+        paused: ctx.accounts.config.paused,
+    });
+    Ok(())
+}
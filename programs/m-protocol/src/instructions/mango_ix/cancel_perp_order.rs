@@ -0,0 +1,103 @@
+use crate::constants::*;
+use crate::cpi;
+use crate::error::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CancelPerpOrderOnMango<'info> {
+    #[account(
+        has_one=trader @ ErrorCode::NotTrader)]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        constraint=!config.is_paused(&vault, PAUSE_MANGO) @ ErrorCode::Paused
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    /// CHECK: Mango acoount info
+    pub mango_group: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(
+        mut,
+        seeds=[
+            mango_group.key().as_ref(),
+            vault_authority.key().as_ref(),
+            &vault.mango_account_num.to_le_bytes()
+        ],
+        bump=vault.mango_account_bump,
+        seeds::program=mango_program_id.key()
+    )]
+    pub mango_account: AccountInfo<'info>,
+
+    /// CHECK: vault authority
+    #[account(
+        seeds=[
+            vault.key().as_ref(),
+            VAULT_PDA_AUTHORITY_SEED.as_ref()
+        ],
+        bump=vault.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(mut)]
+    pub perp_market: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(mut)]
+    pub bids: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(mut)]
+    pub asks: AccountInfo<'info>,
+
+    pub trader: Signer<'info>,
+
+    /// CHECK: Mango acoount info
+    pub mango_program_id: AccountInfo<'info>,
+}
+
+impl <'info> CancelPerpOrderOnMango <'info> {
+
+    fn cancel_perp_order_context(&self) -> CpiContext<'_, '_, '_, 'info, cpi::CancelPerpOrder<'info>> {
+
+        let cpi_accounts = cpi::CancelPerpOrder {
+            mango_group: self.mango_group.to_account_info().clone(),
+            mango_account: self.mango_account.to_account_info().clone(),
+            owner: self.vault_authority.to_account_info().clone(),
+            perp_market: self.perp_market.to_account_info().clone(),
+            bids: self.bids.to_account_info().clone(),
+            asks: self.asks.to_account_info().clone(),
+            mango_program_id: self.mango_program_id.to_account_info().clone(),
+        };
+        let cpi_program = self.mango_program_id.to_account_info();
+
+        CpiContext::new(cpi_program, cpi_accounts)
+
+    }
+}
+
+pub fn handler(ctx: Context<CancelPerpOrderOnMango>, order_id: i128, invalid_id_ok: bool) -> Result<()> {
+
+    cpi::cancel_perp_order(ctx.accounts.cancel_perp_order_context().with_signer(
+        &[&[
+            ctx.accounts.vault.key().as_ref(),
+            VAULT_PDA_AUTHORITY_SEED.as_ref(),
+            &[ctx.accounts.vault.vault_authority_bump],
+        ]],
+    ), order_id, invalid_id_ok)?;
+
+    emit!(PerpOrderCancelled {
+        vault: ctx.accounts.vault.key(),
+        mango_account: ctx.accounts.mango_account.key(),
+        perp_market: ctx.accounts.perp_market.key(),
+        order_id,
+    });
+
+    Ok(())
+}
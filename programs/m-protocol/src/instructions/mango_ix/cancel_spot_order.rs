@@ -0,0 +1,128 @@
+use crate::constants::*;
+use crate::cpi;
+use crate::error::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+use serum_dex::instruction::CancelOrderInstructionV2;
+
+#[derive(Accounts)]
+pub struct CancelSpotOrderOnMango<'info> {
+    #[account(
+        has_one=trader @ ErrorCode::NotTrader)]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        constraint=!config.is_paused(&vault, PAUSE_MANGO) @ ErrorCode::Paused
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    /// CHECK: Mango acoount info
+    pub mango_group: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(
+        seeds=[
+            mango_group.key().as_ref(),
+            vault_authority.key().as_ref(),
+            &vault.mango_account_num.to_le_bytes()
+        ],
+        bump=vault.mango_account_bump,
+        seeds::program=mango_program_id.key()
+    )]
+    pub mango_account: AccountInfo<'info>,
+
+    /// CHECK: vault authority
+    #[account(
+        seeds=[
+            vault.key().as_ref(),
+            VAULT_PDA_AUTHORITY_SEED.as_ref()
+        ],
+        bump=vault.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Mango acoount info
+    pub dex_program: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(mut)]
+    pub spot_market: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(mut)]
+    pub bids: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(mut)]
+    pub asks: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(
+        mut,
+        constraint=vault.spot_open_orders.contains(&open_orders.key()) @ ErrorCode::OpenOrdersNotTracked
+    )]
+    pub open_orders: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    pub mango_signer: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(mut)]
+    pub dex_event_queue: AccountInfo<'info>,
+
+    pub trader: Signer<'info>,
+
+    /// CHECK: Mango acoount info
+    pub mango_program_id: AccountInfo<'info>,
+}
+
+impl <'info> CancelSpotOrderOnMango <'info> {
+
+    fn cancel_spot_order_context(&self) -> CpiContext<'_, '_, '_, 'info, cpi::CancelSpotOrder<'info>> {
+
+        let cpi_accounts = cpi::CancelSpotOrder {
+            mango_group: self.mango_group.to_account_info().clone(),
+            owner: self.vault_authority.to_account_info().clone(),
+            mango_account: self.mango_account.to_account_info().clone(),
+            dex_program: self.dex_program.to_account_info().clone(),
+            spot_market: self.spot_market.to_account_info().clone(),
+            bids: self.bids.to_account_info().clone(),
+            asks: self.asks.to_account_info().clone(),
+            open_orders: self.open_orders.to_account_info().clone(),
+            signer: self.mango_signer.to_account_info().clone(),
+            dex_event_queue: self.dex_event_queue.to_account_info().clone(),
+            mango_program_id: self.mango_program_id.to_account_info().clone(),
+        };
+        let cpi_program = self.mango_program_id.to_account_info();
+
+        CpiContext::new(cpi_program, cpi_accounts)
+
+    }
+}
+
+pub fn handler(ctx: Context<CancelSpotOrderOnMango>, side: OrderSide, order_id: u128) -> Result<()> {
+
+    cpi::cancel_spot_order(ctx.accounts.cancel_spot_order_context().with_signer(
+        &[&[
+            ctx.accounts.vault.key().as_ref(),
+            VAULT_PDA_AUTHORITY_SEED.as_ref(),
+            &[ctx.accounts.vault.vault_authority_bump],
+        ]],
+    ), CancelOrderInstructionV2 {
+        side: side.into(),
+        order_id,
+    })?;
+
+    emit!(SpotOrderCancelled {
+        vault: ctx.accounts.vault.key(),
+        spot_market: ctx.accounts.spot_market.key(),
+        open_orders: ctx.accounts.open_orders.key(),
+        side,
+        order_id,
+    });
+
+    Ok(())
+}
@@ -1,6 +1,8 @@
 use crate::constants::*;
 use crate::cpi;
+use crate::error::ErrorCode;
 use crate::state::*;
+use crate::events::*;
 use anchor_lang::prelude::*;
 // use solana_program::program::invoke_signed;
 
@@ -8,9 +10,15 @@ use anchor_lang::prelude::*;
 pub struct CloseAccountOnMango<'info> {
     #[account(
         mut,
-        has_one=manager
-    )]
+        has_one=admin @ ErrorCode::NotAdmin)]
     pub vault: Account<'info, Vault>,
+
+    #[account(
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        constraint=!config.is_paused(&vault, PAUSE_MANGO) @ ErrorCode::Paused
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
     
     /// CHECK: Mango acoount info
     #[account(mut)]
@@ -41,7 +49,7 @@ pub struct CloseAccountOnMango<'info> {
     pub vault_authority: UncheckedAccount<'info>,
 
     #[account(mut)]
-    pub manager: Signer<'info>,
+    pub admin: Signer<'info>,
 
     /// CHECK: Mango acoount info
     pub mango_program_id: AccountInfo<'info>,
@@ -74,6 +82,11 @@ pub fn handler(ctx: Context<CloseAccountOnMango>) -> Result<()> {
         ]],
     ))?;
 
+    emit!(MangoAccountClosed {
+        vault: ctx.accounts.vault.key(),
+        mango_account: ctx.accounts.mango_account.key(),
+    });
+
     Ok(())
 }
 
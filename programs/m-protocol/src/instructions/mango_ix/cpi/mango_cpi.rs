@@ -1,5 +1,9 @@
+use crate::error::ErrorCode;
+
 use anchor_lang::prelude::*;
-use mango::state::MAX_PAIRS;
+use mango::matching::{ExpiryType, OrderType, Side};
+use mango::state::{MangoAccount, MAX_PAIRS};
+use serum_dex::instruction::{CancelOrderInstructionV2, NewOrderInstructionV3};
 
 
 pub fn create_mango_account<'a, 'b, 'c, 'info>(
@@ -64,14 +68,34 @@ pub fn deposit<'a, 'b, 'c, 'info>(
     .map_err(Into::into)
 }
 
+/// Open orders of the mango account's margin basket, in market index order,
+/// as mango expects them packed after the withdraw accounts.
+pub fn margin_basket_open_orders(
+    mango_program_id: &Pubkey,
+    mango_group: &Pubkey,
+    mango_account: &AccountInfo,
+) -> Result<Vec<Pubkey>> {
+    let mango_account = MangoAccount::load_checked(mango_account, mango_program_id, mango_group)
+        .map_err(|_| error!(ErrorCode::MangoAccountLoadFailed))?;
+    Ok((0..MAX_PAIRS)
+        .filter(|index| mango_account.in_margin_basket[*index])
+        .map(|index| mango_account.spot_open_orders[index])
+        .collect())
+}
+
+/// The OpenOrders of the margin basket, see `margin_basket_open_orders`,
+/// go in the CpiContext's `remaining_accounts` Vec.
 pub fn withdraw<'a, 'b, 'c, 'info>(
     ctx: CpiContext<'a, 'b, 'c, 'info, Withdraw<'info>>,
     quantity: u64,
     allow_borrow: bool,
 ) -> Result<()> {
-    let remaining_accounts_iter = ctx.remaining_accounts.iter();
-    let mut open_orders = vec![Pubkey::default(); MAX_PAIRS];
-    remaining_accounts_iter.for_each(|ai| open_orders.push(*ai.key));
+    // * settled markets leave the basket, the vault's tracked open orders may no longer be in it
+    let open_orders = margin_basket_open_orders(
+        ctx.accounts.mango_program_id.key,
+        ctx.accounts.mango_group.key,
+        &ctx.accounts.mango_account,
+    )?;
     let ix = mango::instruction::withdraw(
         ctx.accounts.mango_program_id.key,
         ctx.accounts.mango_group.key,
@@ -83,7 +107,7 @@ pub fn withdraw<'a, 'b, 'c, 'info>(
         ctx.accounts.vault.key,
         ctx.accounts.token_account.key,
         ctx.accounts.signer.key,
-        open_orders.as_slice(),
+        &open_orders,
         quantity,
         allow_borrow,
     )?;
@@ -113,6 +137,243 @@ pub fn set_delegate<'a, 'b, 'c, 'info>(
     .map_err(Into::into)
 }
 
+/// To reference OpenOrders of the margin basket, add them to the
+/// CpiContext's `remaining_accounts` Vec.
+#[allow(clippy::too_many_arguments)]
+pub fn place_perp_order2<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, PlacePerpOrder2<'info>>,
+    side: Side,
+    price: i64,
+    max_base_quantity: i64,
+    max_quote_quantity: i64,
+    client_order_id: u64,
+    order_type: OrderType,
+    reduce_only: bool,
+    expiry_timestamp: Option<u64>,
+    limit: u8,
+) -> Result<()> {
+    let open_orders: Vec<Pubkey> = ctx.remaining_accounts.iter().map(|ai| *ai.key).collect();
+    let ix = mango::instruction::place_perp_order2(
+        ctx.accounts.mango_program_id.key,
+        ctx.accounts.mango_group.key,
+        ctx.accounts.mango_account.key,
+        ctx.accounts.owner.key,
+        ctx.accounts.mango_cache.key,
+        ctx.accounts.perp_market.key,
+        ctx.accounts.bids.key,
+        ctx.accounts.asks.key,
+        ctx.accounts.event_queue.key,
+        None,
+        open_orders.as_slice(),
+        side,
+        price,
+        max_base_quantity,
+        max_quote_quantity,
+        client_order_id,
+        order_type,
+        reduce_only,
+        expiry_timestamp,
+        limit,
+        ExpiryType::Absolute,
+    )?;
+    solana_program::program::invoke_signed(
+        &ix,
+        &ToAccountInfos::to_account_infos(&ctx),
+        ctx.signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+pub fn cancel_perp_order<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, CancelPerpOrder<'info>>,
+    order_id: i128,
+    invalid_id_ok: bool,
+) -> Result<()> {
+    let ix = mango::instruction::cancel_perp_order(
+        ctx.accounts.mango_program_id.key,
+        ctx.accounts.mango_group.key,
+        ctx.accounts.mango_account.key,
+        ctx.accounts.owner.key,
+        ctx.accounts.perp_market.key,
+        ctx.accounts.bids.key,
+        ctx.accounts.asks.key,
+        order_id,
+        invalid_id_ok,
+    )?;
+    solana_program::program::invoke_signed(
+        &ix,
+        &ToAccountInfos::to_account_infos(&ctx),
+        ctx.signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+pub fn cancel_all_perp_orders<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, CancelAllPerpOrders<'info>>,
+    limit: u8,
+) -> Result<()> {
+    let ix = mango::instruction::cancel_all_perp_orders(
+        ctx.accounts.mango_program_id.key,
+        ctx.accounts.mango_group.key,
+        ctx.accounts.mango_account.key,
+        ctx.accounts.owner.key,
+        ctx.accounts.perp_market.key,
+        ctx.accounts.bids.key,
+        ctx.accounts.asks.key,
+        limit,
+    )?;
+    solana_program::program::invoke_signed(
+        &ix,
+        &ToAccountInfos::to_account_infos(&ctx),
+        ctx.signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+pub fn settle_pnl<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, SettlePnl<'info>>,
+    market_index: usize,
+) -> Result<()> {
+    let ix = mango::instruction::settle_pnl(
+        ctx.accounts.mango_program_id.key,
+        ctx.accounts.mango_group.key,
+        ctx.accounts.mango_account_a.key,
+        ctx.accounts.mango_account_b.key,
+        ctx.accounts.mango_cache.key,
+        ctx.accounts.root_bank.key,
+        ctx.accounts.node_bank.key,
+        market_index,
+    )?;
+    solana_program::program::invoke_signed(
+        &ix,
+        &ToAccountInfos::to_account_infos(&ctx),
+        ctx.signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+/// `open_orders` holds one key per market index, `Pubkey::default()` when unused.
+/// The OpenOrders it references go in the CpiContext's `remaining_accounts` Vec.
+pub fn place_spot_order2<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, PlaceSpotOrder2<'info>>,
+    open_orders: &[Pubkey; MAX_PAIRS],
+    market_index: usize,
+    order: NewOrderInstructionV3,
+) -> Result<()> {
+    let ix = mango::instruction::place_spot_order2(
+        ctx.accounts.mango_program_id.key,
+        ctx.accounts.mango_group.key,
+        ctx.accounts.mango_account.key,
+        ctx.accounts.owner.key,
+        ctx.accounts.mango_cache.key,
+        ctx.accounts.dex_program.key,
+        ctx.accounts.spot_market.key,
+        ctx.accounts.bids.key,
+        ctx.accounts.asks.key,
+        ctx.accounts.dex_request_queue.key,
+        ctx.accounts.dex_event_queue.key,
+        ctx.accounts.dex_base.key,
+        ctx.accounts.dex_quote.key,
+        ctx.accounts.base_root_bank.key,
+        ctx.accounts.base_node_bank.key,
+        ctx.accounts.base_vault.key,
+        ctx.accounts.quote_root_bank.key,
+        ctx.accounts.quote_node_bank.key,
+        ctx.accounts.quote_vault.key,
+        ctx.accounts.signer.key,
+        ctx.accounts.dex_signer.key,
+        ctx.accounts.msrm_or_srm_vault.key,
+        open_orders,
+        market_index,
+        order,
+    )?;
+    solana_program::program::invoke_signed(
+        &ix,
+        &ToAccountInfos::to_account_infos(&ctx),
+        ctx.signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+pub fn cancel_spot_order<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, CancelSpotOrder<'info>>,
+    order: CancelOrderInstructionV2,
+) -> Result<()> {
+    let ix = mango::instruction::cancel_spot_order(
+        ctx.accounts.mango_program_id.key,
+        ctx.accounts.mango_group.key,
+        ctx.accounts.owner.key,
+        ctx.accounts.mango_account.key,
+        ctx.accounts.dex_program.key,
+        ctx.accounts.spot_market.key,
+        ctx.accounts.bids.key,
+        ctx.accounts.asks.key,
+        ctx.accounts.open_orders.key,
+        ctx.accounts.signer.key,
+        ctx.accounts.dex_event_queue.key,
+        order,
+    )?;
+    solana_program::program::invoke_signed(
+        &ix,
+        &ToAccountInfos::to_account_infos(&ctx),
+        ctx.signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+pub fn settle_funds<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, SettleFunds<'info>>,
+) -> Result<()> {
+    let ix = mango::instruction::settle_funds(
+        ctx.accounts.mango_program_id.key,
+        ctx.accounts.mango_group.key,
+        ctx.accounts.mango_cache.key,
+        ctx.accounts.owner.key,
+        ctx.accounts.mango_account.key,
+        ctx.accounts.dex_program.key,
+        ctx.accounts.spot_market.key,
+        ctx.accounts.open_orders.key,
+        ctx.accounts.signer.key,
+        ctx.accounts.dex_base.key,
+        ctx.accounts.dex_quote.key,
+        ctx.accounts.base_root_bank.key,
+        ctx.accounts.base_node_bank.key,
+        ctx.accounts.quote_root_bank.key,
+        ctx.accounts.quote_node_bank.key,
+        ctx.accounts.base_vault.key,
+        ctx.accounts.quote_vault.key,
+        ctx.accounts.dex_signer.key,
+    )?;
+    solana_program::program::invoke_signed(
+        &ix,
+        &ToAccountInfos::to_account_infos(&ctx),
+        ctx.signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+pub fn create_spot_open_orders<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, CreateSpotOpenOrders<'info>>,
+) -> Result<()> {
+    let ix = mango::instruction::create_spot_open_orders(
+        ctx.accounts.mango_program_id.key,
+        ctx.accounts.mango_group.key,
+        ctx.accounts.mango_account.key,
+        ctx.accounts.owner.key,
+        ctx.accounts.dex_program.key,
+        ctx.accounts.open_orders.key,
+        ctx.accounts.spot_market.key,
+        ctx.accounts.signer.key,
+        ctx.accounts.payer.key,
+    )?;
+    solana_program::program::invoke_signed(
+        &ix,
+        &ToAccountInfos::to_account_infos(&ctx),
+        ctx.signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
 #[derive(Accounts)]
 pub struct CreateMangoAccount<'info> {
     /// CHECK: Mango CPI
@@ -164,8 +425,6 @@ pub struct Deposit<'info> {
     pub mango_program_id: AccountInfo<'info>,
 }
 
-/// To reference OpenOrders, add them to the accounts [0-MAX_PAIRS] of the
-/// CpiContext's `remaining_accounts` Vec.
 #[derive(Accounts)]
 pub struct Withdraw<'info> {
     /// CHECK: Mango CPI
@@ -204,4 +463,222 @@ pub struct SetDelegate<'info> {
     pub delegate_pubkey: AccountInfo<'info>,
     /// CHECK: Mango CPI
     pub mango_program_id: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PlacePerpOrder2<'info> {
+    /// CHECK: Mango CPI
+    pub mango_group: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub mango_account: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub owner: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub mango_cache: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub perp_market: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub bids: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub asks: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub event_queue: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub mango_program_id: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelPerpOrder<'info> {
+    /// CHECK: Mango CPI
+    pub mango_group: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub mango_account: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub owner: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub perp_market: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub bids: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub asks: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub mango_program_id: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAllPerpOrders<'info> {
+    /// CHECK: Mango CPI
+    pub mango_group: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub mango_account: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub owner: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub perp_market: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub bids: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub asks: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub mango_program_id: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SettlePnl<'info> {
+    /// CHECK: Mango CPI
+    pub mango_group: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub mango_account_a: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub mango_account_b: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub mango_cache: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub root_bank: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub node_bank: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub mango_program_id: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PlaceSpotOrder2<'info> {
+    /// CHECK: Mango CPI
+    pub mango_group: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub mango_account: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub owner: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub mango_cache: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub dex_program: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub spot_market: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub bids: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub asks: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub dex_request_queue: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub dex_event_queue: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub dex_base: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub dex_quote: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub base_root_bank: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub base_node_bank: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub base_vault: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub quote_root_bank: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub quote_node_bank: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub quote_vault: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub token_program: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub signer: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub dex_signer: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub msrm_or_srm_vault: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub mango_program_id: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelSpotOrder<'info> {
+    /// CHECK: Mango CPI
+    pub mango_group: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub owner: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub mango_account: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub dex_program: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub spot_market: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub bids: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub asks: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub open_orders: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub signer: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub dex_event_queue: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub mango_program_id: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SettleFunds<'info> {
+    /// CHECK: Mango CPI
+    pub mango_group: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub mango_cache: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub owner: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub mango_account: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub dex_program: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub spot_market: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub open_orders: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub signer: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub dex_base: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub dex_quote: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub base_root_bank: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub base_node_bank: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub quote_root_bank: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub quote_node_bank: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub base_vault: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub quote_vault: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub dex_signer: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub token_program: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub mango_program_id: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CreateSpotOpenOrders<'info> {
+    /// CHECK: Mango CPI
+    pub mango_group: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub mango_account: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub owner: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub dex_program: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub open_orders: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub spot_market: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub signer: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub payer: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub system_program: AccountInfo<'info>,
+    /// CHECK: Mango CPI
+    pub mango_program_id: AccountInfo<'info>,
 }
\ No newline at end of file
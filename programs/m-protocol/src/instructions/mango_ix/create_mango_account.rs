@@ -1,6 +1,7 @@
 use crate::{constants::*, cpi};
 use crate::error::ErrorCode;
 use crate::state::*;
+use crate::events::*;
 use anchor_lang::prelude::*;
 // use solana_program::program::invoke_signed;
 
@@ -9,10 +10,17 @@ use anchor_lang::prelude::*;
 pub struct CreateAccountOnMango<'info> {
     #[account(
         mut, 
-        has_one=manager @ ErrorCode::NotAdmin
+        has_one=admin @ ErrorCode::NotAdmin
     )]
     pub vault: Account<'info, Vault>,
 
+    #[account(
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        constraint=!config.is_paused(&vault, PAUSE_MANGO) @ ErrorCode::Paused
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
      /// CHECK: vault authority
     #[account(
         mut,
@@ -38,7 +46,7 @@ pub struct CreateAccountOnMango<'info> {
     pub unverified_mango_account_pda: AccountInfo<'info>, // ^^ as above
     
     #[account(mut)]
-    pub manager: Signer<'info>,
+    pub admin: Signer<'info>,
     
     /// CHECK: Mango account info
     pub mango_program_id: AccountInfo<'info>,
@@ -59,7 +67,7 @@ impl <'info> CreateAccountOnMango <'info> {
             mango_group: self.mango_group_ai.to_account_info().clone(),
             mango_program_id: self.mango_program_id.to_account_info().clone(),
             owner: self.vault_authority.to_account_info().clone(),
-            payer: self.manager.to_account_info().clone(),
+            payer: self.admin.to_account_info().clone(),
             system_prog: self.system_program.to_account_info().clone()
         };
         let cpi_program = self.mango_program_id.to_account_info();
@@ -117,6 +125,14 @@ pub fn handler(
 
     ctx.accounts.vault.mango_account_bump = mango_account_bump;
     ctx.accounts.vault.mango_account_num = account_num;
+    ctx.accounts.vault.mango_program_id = ctx.accounts.mango_program_id.key();
+    ctx.accounts.vault.mango_group = ctx.accounts.mango_group_ai.key();
+
+    emit!(MangoAccountCreated {
+        vault: ctx.accounts.vault.key(),
+        mango_account: ctx.accounts.unverified_mango_account_pda.key(),
+        account_num,
+    });
 
     Ok(())
 }
@@ -0,0 +1,128 @@
+use crate::constants::*;
+use crate::cpi;
+use crate::error::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+use mango::state::MAX_PAIRS;
+
+#[derive(Accounts)]
+#[instruction(market_index: u64)]
+pub struct CreateSpotOpenOrdersOnMango<'info> {
+    #[account(
+        mut,
+        has_one=trader @ ErrorCode::NotTrader,
+        constraint=market_index < MAX_PAIRS as u64 @ ErrorCode::InvalidMarketIndex,
+        constraint=vault.spot_open_orders[market_index as usize] == Pubkey::default() @ ErrorCode::OpenOrdersAlreadyCreated
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        constraint=!config.is_paused(&vault, PAUSE_MANGO) @ ErrorCode::Paused
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    /// CHECK: Mango acoount info
+    pub mango_group: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(
+        mut,
+        seeds=[
+            mango_group.key().as_ref(),
+            vault_authority.key().as_ref(),
+            &vault.mango_account_num.to_le_bytes()
+        ],
+        bump=vault.mango_account_bump,
+        seeds::program=mango_program_id.key()
+    )]
+    pub mango_account: AccountInfo<'info>,
+
+    /// CHECK: vault authority
+    #[account(
+        seeds=[
+            vault.key().as_ref(),
+            VAULT_PDA_AUTHORITY_SEED.as_ref()
+        ],
+        bump=vault.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Mango acoount info
+    pub dex_program: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(
+        mut,
+        seeds=[
+            mango_account.key().as_ref(),
+            &market_index.to_le_bytes(),
+            OPEN_ORDERS_SEED.as_ref()
+        ],
+        bump,
+        seeds::program=mango_program_id.key()
+    )]
+    pub open_orders: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    pub spot_market: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    pub mango_signer: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub trader: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Mango acoount info
+    pub mango_program_id: AccountInfo<'info>,
+}
+
+impl <'info> CreateSpotOpenOrdersOnMango <'info> {
+
+    fn create_spot_open_orders_context(&self) -> CpiContext<'_, '_, '_, 'info, cpi::CreateSpotOpenOrders<'info>> {
+
+        let cpi_accounts = cpi::CreateSpotOpenOrders {
+            mango_group: self.mango_group.to_account_info().clone(),
+            mango_account: self.mango_account.to_account_info().clone(),
+            owner: self.vault_authority.to_account_info().clone(),
+            dex_program: self.dex_program.to_account_info().clone(),
+            open_orders: self.open_orders.to_account_info().clone(),
+            spot_market: self.spot_market.to_account_info().clone(),
+            signer: self.mango_signer.to_account_info().clone(),
+            payer: self.trader.to_account_info().clone(),
+            system_program: self.system_program.to_account_info().clone(),
+            mango_program_id: self.mango_program_id.to_account_info().clone(),
+        };
+        let cpi_program = self.mango_program_id.to_account_info();
+
+        CpiContext::new(cpi_program, cpi_accounts)
+
+    }
+}
+
+pub fn handler(ctx: Context<CreateSpotOpenOrdersOnMango>, market_index: u64) -> Result<()> {
+
+    cpi::create_spot_open_orders(ctx.accounts.create_spot_open_orders_context().with_signer(
+        &[&[
+            ctx.accounts.vault.key().as_ref(),
+            VAULT_PDA_AUTHORITY_SEED.as_ref(),
+            &[ctx.accounts.vault.vault_authority_bump],
+        ]],
+    ))?;
+
+    // * tracked so mango withdrawals can pass every open orders account
+    ctx.accounts.vault.spot_open_orders[market_index as usize] = ctx.accounts.open_orders.key();
+
+    emit!(SpotOpenOrdersCreated {
+        vault: ctx.accounts.vault.key(),
+        mango_account: ctx.accounts.mango_account.key(),
+        open_orders: ctx.accounts.open_orders.key(),
+        market_index,
+    });
+
+    Ok(())
+}
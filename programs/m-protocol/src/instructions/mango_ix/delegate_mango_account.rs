@@ -1,7 +1,9 @@
 // use crate::CloseMangoAccountCpi;
 use crate::constants::*;
 use crate::cpi;
+use crate::error::ErrorCode;
 use crate::state::*;
+use crate::events::*;
 use anchor_lang::prelude::*;
 // use solana_program::program::invoke_signed;
 
@@ -9,9 +11,28 @@ use anchor_lang::prelude::*;
 // #[instruction(mango_account_owner_bump: u8)]
 pub struct DelegateMangoAccount<'info> {
     #[account(
-        has_one=manager
-    )]
+        has_one=trader @ ErrorCode::NotTrader,
+        constraint=!vault.is_halted() @ ErrorCode::VaultHalted)]
     pub vault: Account<'info, Vault>,
+
+    // * a delegate trades the mango account directly, past every risk check of the vault
+    /// CHECK: must not be created yet
+    #[account(
+        seeds=[
+            vault.key().as_ref(),
+            RISK_PARAMS_SEED.as_ref()
+        ],
+        bump,
+        constraint=risk_params.data_is_empty() @ ErrorCode::DelegationNotAllowed
+    )]
+    pub risk_params: UncheckedAccount<'info>,
+
+    #[account(
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        constraint=!config.is_paused(&vault, PAUSE_MANGO) @ ErrorCode::Paused
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
     
     /// CHECK: Mango acoount info
     #[account(mut)]
@@ -43,7 +64,7 @@ pub struct DelegateMangoAccount<'info> {
     /// CHECK: Mango acoount info
     pub delegate_pubkey: UncheckedAccount<'info>,
 
-    pub manager: Signer<'info>,
+    pub trader: Signer<'info>,
 
     /// CHECK: Mango acoount info
     pub mango_program_id: AccountInfo<'info>,
@@ -77,5 +98,11 @@ pub fn handler(ctx: Context<DelegateMangoAccount>) -> Result<()> {
         ]],
     ))?;
 
+    emit!(MangoAccountDelegated {
+        vault: ctx.accounts.vault.key(),
+        mango_account: ctx.accounts.mango_account.key(),
+        delegate: ctx.accounts.delegate_pubkey.key(),
+    });
+
     Ok(())
 }
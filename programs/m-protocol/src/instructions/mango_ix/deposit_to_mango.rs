@@ -1,6 +1,7 @@
 use crate::constants::*;
 use crate::cpi;
 use crate::state::*;
+use crate::events::*;
 use crate::error::ErrorCode;
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount};
@@ -13,10 +14,17 @@ use anchor_spl::token::{Token, TokenAccount};
 pub struct DepositToMango<'info> {
     #[account(
         mut,
-        has_one=manager @ ErrorCode::NotAdmin
+        has_one=trader @ ErrorCode::NotTrader
     )]
     pub vault: Account<'info, Vault>,
 
+    #[account(
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        constraint=!config.is_paused(&vault, PAUSE_MANGO) @ ErrorCode::Paused
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
     /// CHECK: Mango account info
     pub mango_group: AccountInfo<'info>,
     
@@ -58,7 +66,7 @@ pub struct DepositToMango<'info> {
     )]
     pub vault_authority: AccountInfo<'info>,
     
-    pub manager: Signer<'info>,
+    pub trader: Signer<'info>,
     
     /// CHECK: Mango account info
     pub mango_program_id: AccountInfo<'info>,
@@ -102,6 +110,12 @@ pub fn handler(
         ]],
     ), amount)?;
 
+    emit!(MangoDeposited {
+        vault: ctx.accounts.vault.key(),
+        mango_account: ctx.accounts.mango_account.key(),
+        amount,
+    });
+
     Ok(())
 }
 
@@ -4,9 +4,27 @@ pub mod deposit_to_mango;
 pub mod withdraw_from_mango;
 pub mod cpi;
 pub mod delegate_mango_account;
+pub mod revoke_mango_delegate;
+pub mod place_perp_order;
+pub mod cancel_perp_order;
+pub mod cancel_all_perp_orders;
+pub mod settle_pnl;
+pub mod create_spot_open_orders;
+pub mod place_spot_order;
+pub mod cancel_spot_order;
+pub mod settle_funds;
 
 pub use create_mango_account::*;
 pub use close_mango_account::*;
 pub use deposit_to_mango::*;
 pub use withdraw_from_mango::*;
 pub use delegate_mango_account::*;
+pub use revoke_mango_delegate::*;
+pub use place_perp_order::*;
+pub use cancel_perp_order::*;
+pub use cancel_all_perp_orders::*;
+pub use settle_pnl::*;
+pub use create_spot_open_orders::*;
+pub use place_spot_order::*;
+pub use cancel_spot_order::*;
+pub use settle_funds::*;
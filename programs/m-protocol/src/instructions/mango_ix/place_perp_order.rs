@@ -0,0 +1,174 @@
+use crate::constants::*;
+use crate::cpi;
+use crate::error::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+
+/// Open orders of the margin basket are passed as remaining accounts
+#[derive(Accounts)]
+pub struct PlacePerpOrderOnMango<'info> {
+    #[account(
+        has_one=trader @ ErrorCode::NotTrader,
+        constraint=!vault.is_halted() @ ErrorCode::VaultHalted)]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        constraint=!config.is_paused(&vault, PAUSE_MANGO) @ ErrorCode::Paused
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(
+        seeds=[
+            vault.key().as_ref(),
+            RISK_PARAMS_SEED.as_ref()
+        ],
+        bump=risk_params.bump,
+        has_one=vault @ ErrorCode::VaultNotMatch,
+    )]
+    pub risk_params: Account<'info, RiskParams>,
+
+    /// CHECK: Mango acoount info
+    pub mango_group: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(
+        mut,
+        seeds=[
+            mango_group.key().as_ref(),
+            vault_authority.key().as_ref(),
+            &vault.mango_account_num.to_le_bytes()
+        ],
+        bump=vault.mango_account_bump,
+        seeds::program=mango_program_id.key()
+    )]
+    pub mango_account: AccountInfo<'info>,
+
+    /// CHECK: vault authority
+    #[account(
+        seeds=[
+            vault.key().as_ref(),
+            VAULT_PDA_AUTHORITY_SEED.as_ref()
+        ],
+        bump=vault.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Mango acoount info
+    pub mango_cache: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(mut)]
+    pub perp_market: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(mut)]
+    pub bids: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(mut)]
+    pub asks: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(mut)]
+    pub event_queue: AccountInfo<'info>,
+
+    pub trader: Signer<'info>,
+
+    /// CHECK: Mango acoount info
+    pub mango_program_id: AccountInfo<'info>,
+}
+
+impl <'info> PlacePerpOrderOnMango <'info> {
+
+    fn place_perp_order_context(&self) -> CpiContext<'_, '_, '_, 'info, cpi::PlacePerpOrder2<'info>> {
+
+        let cpi_accounts = cpi::PlacePerpOrder2 {
+            mango_group: self.mango_group.to_account_info().clone(),
+            mango_account: self.mango_account.to_account_info().clone(),
+            owner: self.vault_authority.to_account_info().clone(),
+            mango_cache: self.mango_cache.to_account_info().clone(),
+            perp_market: self.perp_market.to_account_info().clone(),
+            bids: self.bids.to_account_info().clone(),
+            asks: self.asks.to_account_info().clone(),
+            event_queue: self.event_queue.to_account_info().clone(),
+            mango_program_id: self.mango_program_id.to_account_info().clone(),
+        };
+        let cpi_program = self.mango_program_id.to_account_info();
+
+        CpiContext::new(cpi_program, cpi_accounts)
+
+    }
+
+    fn load_exposure(&self, open_orders: &[AccountInfo<'info>]) -> Result<MangoExposure> {
+        MangoExposure::load(
+            &self.mango_group,
+            &self.mango_account,
+            &self.mango_cache,
+            open_orders,
+            self.mango_program_id.key,
+        )
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, PlacePerpOrderOnMango<'info>>,
+    side: OrderSide,
+    price: i64,
+    max_base_quantity: i64,
+    max_quote_quantity: i64,
+    client_order_id: u64,
+    order_type: PerpOrderType,
+    reduce_only: bool,
+    expiry_timestamp: Option<u64>,
+    limit: u8,
+) -> Result<()> {
+
+    let exposure = ctx.accounts.load_exposure(ctx.remaining_accounts)?;
+    let market_index = exposure.perp_market_index(ctx.accounts.perp_market.key)?;
+    // * reduce only orders can always unwind a position
+    if !reduce_only {
+        let order_notional = exposure.perp_order_notional(market_index, max_base_quantity)?;
+        ctx.accounts.risk_params.check_order(&exposure, market_index, order_notional)?;
+    }
+
+    cpi::place_perp_order2(
+        ctx.accounts
+            .place_perp_order_context()
+            .with_remaining_accounts(ctx.remaining_accounts.to_vec())
+            .with_signer(&[&[
+                ctx.accounts.vault.key().as_ref(),
+                VAULT_PDA_AUTHORITY_SEED.as_ref(),
+                &[ctx.accounts.vault.vault_authority_bump],
+            ]]),
+        side.into(),
+        price,
+        max_base_quantity,
+        max_quote_quantity,
+        client_order_id,
+        order_type.into(),
+        reduce_only,
+        expiry_timestamp,
+        limit,
+    )?;
+
+    ctx.accounts.risk_params.check_health(&ctx.accounts.load_exposure(ctx.remaining_accounts)?)?;
+
+    emit!(PerpOrderPlaced {
+        vault: ctx.accounts.vault.key(),
+        mango_account: ctx.accounts.mango_account.key(),
+        perp_market: ctx.accounts.perp_market.key(),
+        side,
+        price,
+        max_base_quantity,
+        max_quote_quantity,
+        client_order_id,
+        order_type,
+        reduce_only,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,255 @@
+use crate::constants::*;
+use crate::cpi;
+use crate::error::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use mango::state::MAX_PAIRS;
+use serum_dex::instruction::{NewOrderInstructionV3, SelfTradeBehavior};
+use std::num::NonZeroU64;
+
+/// Open orders tracked on the vault are passed as remaining accounts
+#[derive(Accounts)]
+#[instruction(
+    side: OrderSide,
+    limit_price: u64,
+    max_coin_qty: u64,
+    max_native_pc_qty_including_fees: u64,
+    order_type: SpotOrderType,
+    client_order_id: u64,
+    limit: u16,
+    market_index: u64,
+)]
+pub struct PlaceSpotOrderOnMango<'info> {
+    #[account(
+        has_one=trader @ ErrorCode::NotTrader,
+        constraint=!vault.is_halted() @ ErrorCode::VaultHalted,
+        constraint=market_index < MAX_PAIRS as u64 @ ErrorCode::InvalidMarketIndex,
+        constraint=vault.spot_open_orders[market_index as usize] != Pubkey::default() @ ErrorCode::OpenOrdersNotTracked
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        constraint=!config.is_paused(&vault, PAUSE_MANGO) @ ErrorCode::Paused
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(
+        seeds=[
+            vault.key().as_ref(),
+            RISK_PARAMS_SEED.as_ref()
+        ],
+        bump=risk_params.bump,
+        has_one=vault @ ErrorCode::VaultNotMatch,
+    )]
+    pub risk_params: Account<'info, RiskParams>,
+
+    /// CHECK: Mango acoount info
+    pub mango_group: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(
+        mut,
+        seeds=[
+            mango_group.key().as_ref(),
+            vault_authority.key().as_ref(),
+            &vault.mango_account_num.to_le_bytes()
+        ],
+        bump=vault.mango_account_bump,
+        seeds::program=mango_program_id.key()
+    )]
+    pub mango_account: AccountInfo<'info>,
+
+    /// CHECK: vault authority
+    #[account(
+        seeds=[
+            vault.key().as_ref(),
+            VAULT_PDA_AUTHORITY_SEED.as_ref()
+        ],
+        bump=vault.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Mango acoount info
+    pub mango_cache: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    pub dex_program: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(mut)]
+    pub spot_market: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(mut)]
+    pub bids: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(mut)]
+    pub asks: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(mut)]
+    pub dex_request_queue: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(mut)]
+    pub dex_event_queue: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(mut)]
+    pub dex_base: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(mut)]
+    pub dex_quote: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    pub base_root_bank: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(mut)]
+    pub base_node_bank: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(mut)]
+    pub base_vault: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    pub quote_root_bank: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(mut)]
+    pub quote_node_bank: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(mut)]
+    pub quote_vault: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    pub mango_signer: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    pub dex_signer: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    pub msrm_or_srm_vault: AccountInfo<'info>,
+
+    pub trader: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    /// CHECK: Mango acoount info
+    pub mango_program_id: AccountInfo<'info>,
+}
+
+impl <'info> PlaceSpotOrderOnMango <'info> {
+
+    fn place_spot_order_context(&self) -> CpiContext<'_, '_, '_, 'info, cpi::PlaceSpotOrder2<'info>> {
+
+        let cpi_accounts = cpi::PlaceSpotOrder2 {
+            mango_group: self.mango_group.to_account_info().clone(),
+            mango_account: self.mango_account.to_account_info().clone(),
+            owner: self.vault_authority.to_account_info().clone(),
+            mango_cache: self.mango_cache.to_account_info().clone(),
+            dex_program: self.dex_program.to_account_info().clone(),
+            spot_market: self.spot_market.to_account_info().clone(),
+            bids: self.bids.to_account_info().clone(),
+            asks: self.asks.to_account_info().clone(),
+            dex_request_queue: self.dex_request_queue.to_account_info().clone(),
+            dex_event_queue: self.dex_event_queue.to_account_info().clone(),
+            dex_base: self.dex_base.to_account_info().clone(),
+            dex_quote: self.dex_quote.to_account_info().clone(),
+            base_root_bank: self.base_root_bank.to_account_info().clone(),
+            base_node_bank: self.base_node_bank.to_account_info().clone(),
+            base_vault: self.base_vault.to_account_info().clone(),
+            quote_root_bank: self.quote_root_bank.to_account_info().clone(),
+            quote_node_bank: self.quote_node_bank.to_account_info().clone(),
+            quote_vault: self.quote_vault.to_account_info().clone(),
+            token_program: self.token_program.to_account_info().clone(),
+            signer: self.mango_signer.to_account_info().clone(),
+            dex_signer: self.dex_signer.to_account_info().clone(),
+            msrm_or_srm_vault: self.msrm_or_srm_vault.to_account_info().clone(),
+            mango_program_id: self.mango_program_id.to_account_info().clone(),
+        };
+        let cpi_program = self.mango_program_id.to_account_info();
+
+        CpiContext::new(cpi_program, cpi_accounts)
+
+    }
+
+    fn load_exposure(&self, open_orders: &[AccountInfo<'info>]) -> Result<MangoExposure> {
+        MangoExposure::load(
+            &self.mango_group,
+            &self.mango_account,
+            &self.mango_cache,
+            open_orders,
+            self.mango_program_id.key,
+        )
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, PlaceSpotOrderOnMango<'info>>,
+    side: OrderSide,
+    limit_price: u64,
+    max_coin_qty: u64,
+    max_native_pc_qty_including_fees: u64,
+    order_type: SpotOrderType,
+    client_order_id: u64,
+    limit: u16,
+    market_index: u64,
+) -> Result<()> {
+
+    let exposure = ctx.accounts.load_exposure(ctx.remaining_accounts)?;
+    let order_notional = exposure.spot_order_notional(market_index as usize, max_coin_qty)?;
+    ctx.accounts.risk_params.check_order(&exposure, market_index as usize, order_notional)?;
+
+    let order = NewOrderInstructionV3 {
+        side: side.into(),
+        limit_price: NonZeroU64::new(limit_price).ok_or(ErrorCode::InvalidOrderQuantity)?,
+        max_coin_qty: NonZeroU64::new(max_coin_qty).ok_or(ErrorCode::InvalidOrderQuantity)?,
+        max_native_pc_qty_including_fees: NonZeroU64::new(max_native_pc_qty_including_fees)
+            .ok_or(ErrorCode::InvalidOrderQuantity)?,
+        self_trade_behavior: SelfTradeBehavior::DecrementTake,
+        order_type: order_type.into(),
+        client_order_id,
+        limit,
+        max_ts: i64::MAX,
+    };
+
+    cpi::place_spot_order2(
+        ctx.accounts
+            .place_spot_order_context()
+            .with_remaining_accounts(ctx.remaining_accounts.to_vec())
+            .with_signer(&[&[
+                ctx.accounts.vault.key().as_ref(),
+                VAULT_PDA_AUTHORITY_SEED.as_ref(),
+                &[ctx.accounts.vault.vault_authority_bump],
+            ]]),
+        &ctx.accounts.vault.spot_open_orders,
+        market_index as usize,
+        order,
+    )?;
+
+    ctx.accounts.risk_params.check_health(&ctx.accounts.load_exposure(ctx.remaining_accounts)?)?;
+
+    emit!(SpotOrderPlaced {
+        vault: ctx.accounts.vault.key(),
+        mango_account: ctx.accounts.mango_account.key(),
+        spot_market: ctx.accounts.spot_market.key(),
+        side,
+        limit_price,
+        max_coin_qty,
+        max_native_pc_qty_including_fees,
+        order_type,
+        client_order_id,
+        market_index,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use crate::constants::*;
+use crate::cpi;
+use crate::error::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RevokeMangoDelegate<'info> {
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        constraint=config.guardian == authority.key()
+            || vault.admin == authority.key()
+            || vault.trader == authority.key() @ ErrorCode::NotDelegateAuthority
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    /// CHECK: Mango acoount info
+    #[account(mut)]
+    pub mango_group: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(
+        mut,
+        seeds=[
+            mango_group.key().as_ref(),
+            vault_authority.key().as_ref(),
+            &vault.mango_account_num.to_le_bytes() 
+        ],
+        bump=vault.mango_account_bump,
+        seeds::program=mango_program_id.key()
+    )]
+    pub mango_account: AccountInfo<'info>,
+
+    /// CHECK: vault authority
+    #[account(
+        seeds=[
+            vault.key().as_ref(),
+            VAULT_PDA_AUTHORITY_SEED.as_ref()
+        ],
+        bump=vault.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+
+    /// CHECK: Mango acoount info
+    pub mango_program_id: AccountInfo<'info>,
+
+    // * mango clears the delegate when it is set to the default key, the system program's id
+    pub system_program: Program<'info, System>,
+}
+
+impl <'info> RevokeMangoDelegate <'info> {
+    
+    fn revoke_mango_delegate_context(&self) -> CpiContext<'_, '_, '_, 'info, cpi::SetDelegate<'info>> {
+
+        let cpi_accounts = cpi::SetDelegate {
+            mango_account: self.mango_account.to_account_info().clone(),
+            mango_group: self.mango_group.to_account_info().clone(),
+            mango_program_id: self.mango_program_id.to_account_info().clone(),
+            owner: self.vault_authority.to_account_info().clone(),
+            delegate_pubkey: self.system_program.to_account_info().clone()
+        };
+        let cpi_program = self.mango_program_id.to_account_info();
+
+        CpiContext::new(cpi_program, cpi_accounts)
+
+    }
+}
+
+// * not gated by pauses or halts, revoking is how a halted vault stops outside trading
+pub fn handler(ctx: Context<RevokeMangoDelegate>) -> Result<()> {
+    
+    cpi::mango_cpi::set_delegate(ctx.accounts.revoke_mango_delegate_context().with_signer(
+        &[&[
+            ctx.accounts.vault.key().as_ref(),
+            VAULT_PDA_AUTHORITY_SEED.as_ref(),
+            &[ctx.accounts.vault.vault_authority_bump],
+        ]],
+    ))?;
+
+    emit!(MangoDelegateRevoked {
+        vault: ctx.accounts.vault.key(),
+        mango_account: ctx.accounts.mango_account.key(),
+        authority: ctx.accounts.authority.key(),
+    });
+
+    Ok(())
+}
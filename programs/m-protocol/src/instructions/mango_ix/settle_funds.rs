@@ -0,0 +1,158 @@
+use crate::constants::*;
+use crate::cpi;
+use crate::error::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+
+#[derive(Accounts)]
+pub struct SettleFundsOnMango<'info> {
+    #[account(
+        has_one=trader @ ErrorCode::NotTrader)]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        constraint=!config.is_paused(&vault, PAUSE_MANGO) @ ErrorCode::Paused
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    /// CHECK: Mango acoount info
+    pub mango_group: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    pub mango_cache: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(
+        mut,
+        seeds=[
+            mango_group.key().as_ref(),
+            vault_authority.key().as_ref(),
+            &vault.mango_account_num.to_le_bytes()
+        ],
+        bump=vault.mango_account_bump,
+        seeds::program=mango_program_id.key()
+    )]
+    pub mango_account: AccountInfo<'info>,
+
+    /// CHECK: vault authority
+    #[account(
+        seeds=[
+            vault.key().as_ref(),
+            VAULT_PDA_AUTHORITY_SEED.as_ref()
+        ],
+        bump=vault.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Mango acoount info
+    pub dex_program: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(mut)]
+    pub spot_market: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(
+        mut,
+        constraint=vault.spot_open_orders.contains(&open_orders.key()) @ ErrorCode::OpenOrdersNotTracked
+    )]
+    pub open_orders: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    pub mango_signer: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(mut)]
+    pub dex_base: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(mut)]
+    pub dex_quote: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    pub base_root_bank: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(mut)]
+    pub base_node_bank: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    pub quote_root_bank: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(mut)]
+    pub quote_node_bank: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(mut)]
+    pub base_vault: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(mut)]
+    pub quote_vault: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    pub dex_signer: AccountInfo<'info>,
+
+    pub trader: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    /// CHECK: Mango acoount info
+    pub mango_program_id: AccountInfo<'info>,
+}
+
+impl <'info> SettleFundsOnMango <'info> {
+
+    fn settle_funds_context(&self) -> CpiContext<'_, '_, '_, 'info, cpi::SettleFunds<'info>> {
+
+        let cpi_accounts = cpi::SettleFunds {
+            mango_group: self.mango_group.to_account_info().clone(),
+            mango_cache: self.mango_cache.to_account_info().clone(),
+            owner: self.vault_authority.to_account_info().clone(),
+            mango_account: self.mango_account.to_account_info().clone(),
+            dex_program: self.dex_program.to_account_info().clone(),
+            spot_market: self.spot_market.to_account_info().clone(),
+            open_orders: self.open_orders.to_account_info().clone(),
+            signer: self.mango_signer.to_account_info().clone(),
+            dex_base: self.dex_base.to_account_info().clone(),
+            dex_quote: self.dex_quote.to_account_info().clone(),
+            base_root_bank: self.base_root_bank.to_account_info().clone(),
+            base_node_bank: self.base_node_bank.to_account_info().clone(),
+            quote_root_bank: self.quote_root_bank.to_account_info().clone(),
+            quote_node_bank: self.quote_node_bank.to_account_info().clone(),
+            base_vault: self.base_vault.to_account_info().clone(),
+            quote_vault: self.quote_vault.to_account_info().clone(),
+            dex_signer: self.dex_signer.to_account_info().clone(),
+            token_program: self.token_program.to_account_info().clone(),
+            mango_program_id: self.mango_program_id.to_account_info().clone(),
+        };
+        let cpi_program = self.mango_program_id.to_account_info();
+
+        CpiContext::new(cpi_program, cpi_accounts)
+
+    }
+}
+
+pub fn handler(ctx: Context<SettleFundsOnMango>) -> Result<()> {
+
+    cpi::settle_funds(ctx.accounts.settle_funds_context().with_signer(
+        &[&[
+            ctx.accounts.vault.key().as_ref(),
+            VAULT_PDA_AUTHORITY_SEED.as_ref(),
+            &[ctx.accounts.vault.vault_authority_bump],
+        ]],
+    ))?;
+
+    emit!(SpotFundsSettled {
+        vault: ctx.accounts.vault.key(),
+        spot_market: ctx.accounts.spot_market.key(),
+        open_orders: ctx.accounts.open_orders.key(),
+    });
+
+    Ok(())
+}
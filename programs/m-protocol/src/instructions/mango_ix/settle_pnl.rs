@@ -0,0 +1,100 @@
+use crate::constants::*;
+use crate::cpi;
+use crate::error::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SettlePnlOnMango<'info> {
+    #[account(
+        has_one=trader @ ErrorCode::NotTrader)]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        constraint=!config.is_paused(&vault, PAUSE_MANGO) @ ErrorCode::Paused
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    /// CHECK: Mango acoount info
+    pub mango_group: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(
+        mut,
+        seeds=[
+            mango_group.key().as_ref(),
+            vault_authority.key().as_ref(),
+            &vault.mango_account_num.to_le_bytes()
+        ],
+        bump=vault.mango_account_bump,
+        seeds::program=mango_program_id.key()
+    )]
+    pub mango_account: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(mut)]
+    pub counterparty_mango_account: AccountInfo<'info>,
+
+    /// CHECK: vault authority
+    #[account(
+        seeds=[
+            vault.key().as_ref(),
+            VAULT_PDA_AUTHORITY_SEED.as_ref()
+        ],
+        bump=vault.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Mango acoount info
+    pub mango_cache: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    pub mango_root_bank: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(mut)]
+    pub mango_node_bank: AccountInfo<'info>,
+
+    pub trader: Signer<'info>,
+
+    /// CHECK: Mango acoount info
+    pub mango_program_id: AccountInfo<'info>,
+}
+
+impl <'info> SettlePnlOnMango <'info> {
+
+    fn settle_pnl_context(&self) -> CpiContext<'_, '_, '_, 'info, cpi::SettlePnl<'info>> {
+
+        let cpi_accounts = cpi::SettlePnl {
+            mango_group: self.mango_group.to_account_info().clone(),
+            mango_account_a: self.mango_account.to_account_info().clone(),
+            mango_account_b: self.counterparty_mango_account.to_account_info().clone(),
+            mango_cache: self.mango_cache.to_account_info().clone(),
+            root_bank: self.mango_root_bank.to_account_info().clone(),
+            node_bank: self.mango_node_bank.to_account_info().clone(),
+            mango_program_id: self.mango_program_id.to_account_info().clone(),
+        };
+        let cpi_program = self.mango_program_id.to_account_info();
+
+        CpiContext::new(cpi_program, cpi_accounts)
+
+    }
+}
+
+// * settle_pnl is permissionless on mango, the vault authority does not sign
+pub fn handler(ctx: Context<SettlePnlOnMango>, market_index: u64) -> Result<()> {
+
+    cpi::settle_pnl(ctx.accounts.settle_pnl_context(), market_index as usize)?;
+
+    emit!(PnlSettled {
+        vault: ctx.accounts.vault.key(),
+        mango_account: ctx.accounts.mango_account.key(),
+        counterparty_mango_account: ctx.accounts.counterparty_mango_account.key(),
+        market_index,
+    });
+
+    Ok(())
+}
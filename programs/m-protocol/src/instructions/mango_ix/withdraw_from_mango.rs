@@ -3,19 +3,27 @@ use crate::cpi;
 use crate::cpi::*;
 use crate::error::ErrorCode;
 use crate::state::*;
+use crate::events::*;
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount};
 // use solana_program::program::invoke_signed;
 
+/// Open orders of the mango margin basket are passed as remaining accounts
 #[derive(Accounts)]
 // #[instruction(mango_account_owner_bump: u8)]
 pub struct WithdrawFromMango<'info> {
     #[account(
         mut,
-        has_one=manager
-    )]
+        has_one=trader @ ErrorCode::NotTrader)]
     pub vault: Account<'info, Vault>,
 
+    #[account(
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        constraint=!config.is_paused(&vault, PAUSE_MANGO) @ ErrorCode::Paused
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
     ///CHECK: Mango Account Info
     #[account(mut)]
     pub mango_group: AccountInfo<'info>,
@@ -68,7 +76,7 @@ pub struct WithdrawFromMango<'info> {
     ///CHECK: Mango Account Info
     pub signer: AccountInfo<'info>,
     
-    pub manager: Signer<'info>,
+    pub trader: Signer<'info>,
 
     pub token_program: Program<'info, Token>,
 
@@ -101,8 +109,8 @@ impl <'info> WithdrawFromMango <'info> {
     }
 }
 
-pub fn handler(
-    ctx: Context<WithdrawFromMango>,
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, WithdrawFromMango<'info>>,
     amount: u64,
     // mango_account_owner_bump: u8,
 ) -> Result<()> {
@@ -119,7 +127,9 @@ pub fn handler(
     // .unwrap_or_default();
 
 
-    cpi::withdraw(ctx.accounts.withdraw_from_mango_context().with_signer(
+    cpi::withdraw(ctx.accounts.withdraw_from_mango_context()
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec())
+        .with_signer(
         &[&[
             &ctx.accounts.vault.key().as_ref().to_owned(),
             VAULT_PDA_AUTHORITY_SEED.as_ref(),
@@ -127,6 +137,11 @@ pub fn handler(
         ]],
     ), amount, false)?;
 
+    emit!(MangoWithdrew {
+        vault: ctx.accounts.vault.key(),
+        mango_account: ctx.accounts.mango_account.key(),
+        amount,
+    });
 
     Ok(())
 }
@@ -1,5 +1,7 @@
+pub mod config;
 pub mod vault;
 pub mod mango_ix;
 
+pub use config::*;
 pub use vault::*;
 pub use mango_ix::*;
\ No newline at end of file
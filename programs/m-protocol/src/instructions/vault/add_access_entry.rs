@@ -0,0 +1,50 @@
+use crate::state::*;
+use crate::events::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct AddAccessEntry<'info> {
+    #[account(
+        has_one=admin @ ErrorCode::NotAdmin,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init,
+        payer=admin,
+        seeds=[
+            vault.key().as_ref(),
+            wallet.as_ref(),
+            ACCESS_ENTRY_SEED.as_ref()
+        ],
+        bump,
+        space=8+AccessEntry::LEN
+    )]
+    pub access_entry: Account<'info, AccessEntry>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AddAccessEntry>, wallet: Pubkey, allowed: bool, denied: bool) -> Result<()> {
+    let access_entry = &mut ctx.accounts.access_entry;
+    access_entry.vault = ctx.accounts.vault.key();
+    access_entry.wallet = wallet;
+    access_entry.allowed = allowed;
+    access_entry.denied = denied;
+    access_entry.bump = *ctx.bumps.get("access_entry").unwrap();
+
+    emit!(AccessEntryUpdated {
+        vault: ctx.accounts.vault.key(),
+        wallet,
+        allowed,
+        denied,
+    });
+    Ok(())
+}
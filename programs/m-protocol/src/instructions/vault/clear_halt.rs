@@ -0,0 +1,35 @@
+use crate::state::*;
+use crate::events::*;
+use crate::error::ErrorCode;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ClearHalt<'info> {
+    #[account(
+        mut, 
+        has_one=admin @ ErrorCode::NotAdmin,
+        constraint=vault.is_halted() @ ErrorCode::VaultNotHalted,
+        constraint=vault.epoch.settlement_price > 0 @ ErrorCode::ZeroSharePrice
+    )]
+    pub vault: Account<'info, Vault>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ClearHalt>) -> Result<()> {
+    let reason = ctx.accounts.vault.halt_reason;
+
+    // * drawdown is measured again from the last settlement price, otherwise the next update halts again
+    ctx.accounts.vault.status = VaultStatus::Active;
+    ctx.accounts.vault.halt_reason = HaltReason::None;
+    ctx.accounts.vault.halted_at = 0;
+    ctx.accounts.vault.peak_share_price = ctx.accounts.vault.epoch.settlement_price;
+
+    emit!(VaultHaltCleared {
+        vault: ctx.accounts.vault.key(),
+        reason,
+        peak_share_price: ctx.accounts.vault.peak_share_price,
+    });
+    Ok(())
+}
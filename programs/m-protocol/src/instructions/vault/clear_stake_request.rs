@@ -1,29 +1,64 @@
-use crate::constants::MAX_REQUESTS;
 use crate::state::*;
+use crate::constants::*;
+use crate::events::*;
 use crate::error::ErrorCode;
 
 use anchor_lang::prelude::*;
 
+// * remaining accounts, the user vault accounts of the queued requests to clear,
+// * only requests no longer active or whose user account was closed leave the queue
 #[derive(Accounts)]
 pub struct ClearStakeRequest<'info> {
     #[account(
-        has_one=manager@ErrorCode::NotAdmin, 
+        has_one=keeper @ ErrorCode::NotKeeper, 
         has_one=stake_request_account @ErrorCode::VaultNotMatch
     )]
     pub vault: Account<'info, Vault>,
 
+    #[account(
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        constraint=!config.is_paused(&vault, PAUSE_KEEPER) @ ErrorCode::Paused
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
     #[account(
         mut, 
         has_one=vault@ErrorCode::VaultNotMatch,
     )]
     pub stake_request_account: AccountLoader<'info, StakeReq>,
 
-    pub manager: Signer<'info>,
+    pub keeper: Signer<'info>,
 }
 
-pub fn handler (ctx: Context<ClearStakeRequest>) -> Result<()> {
-    let mut stake_req = ctx.accounts.stake_request_account.load_mut()?;
-    stake_req.count = 0;
-    stake_req.orders = [Pubkey::default(); MAX_REQUESTS];
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ClearStakeRequest<'info>>) -> Result<()> {
+    let vault_key = ctx.accounts.vault.key();
+    let mut cleared = 0;
+
+    for account in ctx.remaining_accounts {
+        if ctx.accounts.stake_request_account.load()?.queue.find(account.key).is_none() {
+            return err!(ErrorCode::RequestNotQueued)
+        }
+
+        if !account.data_is_empty() {
+            let user_vault_account: Account<'info, UserVaultAccount> = Account::try_from(account)?;
+            if user_vault_account.vault != vault_key {
+                return err!(ErrorCode::VaultNotMatch)
+            }
+            // * live requests stay queued until they settle
+            if user_vault_account.user_stake.stake_request_active {
+                continue;
+            }
+        }
+
+        ctx.accounts.stake_request_account.load_mut()?.queue.remove(account.key);
+        cleared += 1;
+    }
+
+    emit!(StakeRequestsCleared {
+        vault: vault_key,
+        stake_request_account: ctx.accounts.stake_request_account.key(),
+        cleared,
+    });
     Ok(())
-}
\ No newline at end of file
+}
@@ -1,30 +1,64 @@
-use crate::constants::MAX_REQUESTS;
 use crate::state::*;
+use crate::constants::*;
+use crate::events::*;
 use crate::error::ErrorCode;
 
 use anchor_lang::prelude::*;
 
+// * remaining accounts, the user vault accounts of the queued requests to clear,
+// * only requests no longer active or whose user account was closed leave the queue
 #[derive(Accounts)]
 pub struct ClearUnstakeRequest<'info> {
     #[account(
-        has_one=manager @ ErrorCode::NotAdmin, 
+        has_one=keeper @ ErrorCode::NotKeeper, 
         has_one=unstake_request_account @ ErrorCode::VaultNotMatch
     )]
     pub vault: Account<'info, Vault>,
 
+    #[account(
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        constraint=!config.is_paused(&vault, PAUSE_KEEPER) @ ErrorCode::Paused
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
     #[account(
         mut, 
         has_one=vault @ ErrorCode::VaultNotMatch,
     )]
     pub unstake_request_account: AccountLoader<'info, UnstakeReq>,
 
-    pub manager: Signer<'info>,
+    pub keeper: Signer<'info>,
 }
 
-pub fn handler (ctx: Context<ClearUnstakeRequest>) -> Result<()> {
-    let mut unstake_req = ctx.accounts.unstake_request_account.load_mut()?;
-    unstake_req.count = 0;
-    unstake_req.orders = [Pubkey::default(); MAX_REQUESTS];
-    
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ClearUnstakeRequest<'info>>) -> Result<()> {
+    let vault_key = ctx.accounts.vault.key();
+    let mut cleared = 0;
+
+    for account in ctx.remaining_accounts {
+        if ctx.accounts.unstake_request_account.load()?.queue.find(account.key).is_none() {
+            return err!(ErrorCode::RequestNotQueued)
+        }
+
+        if !account.data_is_empty() {
+            let user_vault_account: Account<'info, UserVaultAccount> = Account::try_from(account)?;
+            if user_vault_account.vault != vault_key {
+                return err!(ErrorCode::VaultNotMatch)
+            }
+            // * live requests stay queued until they settle
+            if user_vault_account.user_unstake.unstake_request_active {
+                continue;
+            }
+        }
+
+        ctx.accounts.unstake_request_account.load_mut()?.queue.remove(account.key);
+        cleared += 1;
+    }
+
+    emit!(UnstakeRequestsCleared {
+        vault: vault_key,
+        unstake_request_account: ctx.accounts.unstake_request_account.key(),
+        cleared,
+    });
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,134 @@
+use crate::mango_ix;
+use crate::state::*;
+use crate::events::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{ Token, TokenAccount };
+
+#[derive(Accounts)]
+pub struct CollectInsurance<'info> {
+    #[account(
+        mut, 
+        has_one=keeper @ ErrorCode::NotKeeper,
+        has_one=insurance_fund @ ErrorCode::TokenAccountNotMatch,
+        constraint=vault.accrued_insurance > 0 @ ErrorCode::NoInsuranceToCollect
+    )]
+    pub vault: Box<Account<'info, Vault>>,
+
+    #[account(
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        constraint=!config.is_paused(&vault, PAUSE_MANGO) @ ErrorCode::Paused
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    /// CHECK: vault authority
+     #[account(
+        seeds=[
+            vault.key().as_ref(),
+            VAULT_PDA_AUTHORITY_SEED.as_ref()
+        ],
+        bump=vault.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+     ///CHECK: Mango Account Info
+    #[account(mut)]
+    pub mango_group: AccountInfo<'info>,
+    
+    ///CHECK: Mango Account Info
+    #[account(
+        mut,
+        seeds=[
+            mango_group.key().as_ref(),
+            vault_authority.key().as_ref(),
+            &vault.mango_account_num.to_le_bytes()
+            ],
+        bump=vault.mango_account_bump,
+        seeds::program=mango_program_id.key()
+    )]
+    pub mango_account: AccountInfo<'info>,
+    
+    ///CHECK: Mango Account Info
+    pub mango_cache: AccountInfo<'info>,
+    
+    ///CHECK: Mango Account Info
+    pub mango_root_bank: AccountInfo<'info>,
+    
+    ///CHECK: Mango Account Info
+    #[account(mut)]
+    pub mango_node_bank: AccountInfo<'info>,
+    
+    ///CHECK: Mango Account Info
+    #[account(mut)]
+    pub mango_vault: AccountInfo<'info>,
+
+    pub keeper: Signer<'info>,
+
+    #[account(
+        mut, 
+        token::mint=vault.mint,
+        token::authority=vault_authority,
+    )]
+    pub insurance_fund: Account<'info, TokenAccount>,
+
+    ///CHECK: Mango Account Info
+    pub signer: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    ///CHECK: Mango Account Info
+    pub mango_program_id: AccountInfo<'info>,
+}
+
+impl<'info> CollectInsurance<'info> {
+    fn withdraw_from_mango_context(&self) -> CpiContext<'_, '_, '_, 'info, mango_ix::cpi::Withdraw<'info>> {
+
+        let cpi_accounts = mango_ix::cpi::Withdraw {
+            mango_account: self.mango_account.to_account_info().clone(),
+            mango_cache: self.mango_cache.to_account_info().clone(),
+            mango_group: self.mango_group.to_account_info().clone(),
+            mango_program_id: self.mango_program_id.to_account_info().clone(),
+            node_bank: self.mango_node_bank.to_account_info().clone(),
+            owner: self.vault_authority.to_account_info().clone(),
+            root_bank: self.mango_root_bank.to_account_info().clone(),
+            signer: self.signer.to_account_info().clone(),
+            token_account: self.insurance_fund.to_account_info().clone(),
+            vault: self.mango_vault.to_account_info().clone(),
+            token_program: self.token_program.to_account_info().clone(),
+        };
+        let cpi_program = self.mango_program_id.to_account_info();
+
+        CpiContext::new(cpi_program, cpi_accounts)
+
+    }
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, CollectInsurance<'info>>) -> Result<()> {
+    let amount = ctx.accounts.vault.accrued_insurance;
+
+    mango_ix::cpi::withdraw(ctx.accounts.withdraw_from_mango_context()
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec())
+        .with_signer(
+        &[&[
+            &ctx.accounts.vault.key().as_ref().to_owned(),
+            VAULT_PDA_AUTHORITY_SEED.as_ref(),
+            &[ctx.accounts.vault.vault_authority_bump],
+        ]],
+    ), amount, false)?;
+
+    ctx.accounts.insurance_fund.reload()?;
+    ctx.accounts.vault.accrued_insurance = 0;
+    ctx.accounts.vault.insurance_fund_balance = ctx.accounts.insurance_fund.amount;
+
+    emit!(InsuranceCollected {
+        vault: ctx.accounts.vault.key(),
+        insurance_fund: ctx.accounts.insurance_fund.key(),
+        amount,
+        insurance_fund_balance: ctx.accounts.vault.insurance_fund_balance,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use crate::state::*;
+use crate::events::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct CreateInsuranceFund<'info> {
+    #[account(
+        mut,
+        has_one=admin @ ErrorCode::NotAdmin,
+        constraint=vault.insurance_fund == Pubkey::default() @ ErrorCode::InsuranceFundAlreadyCreated
+    )]
+    pub vault: Box<Account<'info, Vault>>,
+
+    #[account(
+        init,
+        payer=admin,
+        seeds=[
+            vault.key().as_ref(),
+            INSURANCE_SEED.as_ref()
+        ],
+        bump,
+        token::mint = token_mint,
+        token::authority = vault_authority,
+    )]
+    pub insurance_fund: Account<'info, TokenAccount>,
+
+    #[account(
+        address=vault.mint @ ErrorCode::WrongMintProvided
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    /// CHECK: vault authority
+    #[account(
+        seeds=[
+            vault.key().as_ref(),
+            VAULT_PDA_AUTHORITY_SEED.as_ref()
+        ],
+        bump=vault.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handler(ctx: Context<CreateInsuranceFund>) -> Result<()> {
+    ctx.accounts.vault.insurance_fund = ctx.accounts.insurance_fund.key();
+    ctx.accounts.vault.insurance_fund_balance = 0;
+
+    emit!(InsuranceFundCreated {
+        vault: ctx.accounts.vault.key(),
+        insurance_fund: ctx.accounts.insurance_fund.key(),
+    });
+    Ok(())
+}
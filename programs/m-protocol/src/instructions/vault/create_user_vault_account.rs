@@ -1,4 +1,5 @@
 use crate::state::*;
+use crate::events::*;
 use crate::constants::*;
 use crate::error::ErrorCode;
 
@@ -34,9 +35,30 @@ pub struct CreateUserVaultAccount<'info> {
         token::authority = vault_pda_authority, // * should be pda
         )]
     pub token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer=user_account_authority,
+        seeds=[
+            vault.key().as_ref(), 
+            user_account_authority.key().as_ref(),
+            SHARES_SEED.as_ref()
+        ],
+        bump,
+        token::mint = share_mint,
+        token::authority = vault_pda_authority,
+        )]
+    pub share_token_account: Box<Account<'info, TokenAccount>>,
         
     pub vault: Box<Account<'info, Vault>>,
 
+    #[account(
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        constraint=!config.is_paused(&vault, PAUSE_DEPOSITS) @ ErrorCode::Paused
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
     // #[account(mut)]
     // pub payer: Signer<'info>,
 
@@ -53,6 +75,23 @@ pub struct CreateUserVaultAccount<'info> {
     /// CHECK: user authority 
     #[account(mut)]
     pub user_account_authority: Signer<'info>,
+
+    /// CHECK: gateway token of the user, any account when the vault has no gatekeeper network
+    #[account(
+        constraint=vault.has_valid_gateway_token(&gateway_token, user_account_authority.key) @ ErrorCode::InvalidGatewayToken
+    )]
+    pub gateway_token: UncheckedAccount<'info>,
+
+    /// CHECK: access entry pda of the user, may not exist
+    #[account(
+        seeds=[
+            vault.key().as_ref(),
+            user_account_authority.key().as_ref(),
+            ACCESS_ENTRY_SEED.as_ref()
+        ],
+        bump
+    )]
+    pub access_entry: UncheckedAccount<'info>,
     
     #[account(
         mut, 
@@ -60,6 +99,11 @@ pub struct CreateUserVaultAccount<'info> {
     )]
     pub token_mint: Account<'info, Mint>,
 
+    #[account(
+        address=vault.share_mint @ ErrorCode::WrongMintProvided
+    )]
+    pub share_mint: Account<'info, Mint>,
+
     pub token_program: Program<'info, Token>,
 
     pub rent: Sysvar<'info, Rent>,
@@ -82,10 +126,13 @@ pub fn handler ( ctx: Context<CreateUserVaultAccount>, limit: u64, user_account_
         return err!(ErrorCode::BumpNotMatch)
     }
 
+    AccessEntry::check(&ctx.accounts.vault, &ctx.accounts.access_entry, ctx.program_id)?;
+
     // user_vault_account
     // ctx.accounts.user_vault_account.publickey = ctx.accounts.user_vault_account.key();
     ctx.accounts.user_vault_account.vault = ctx.accounts.vault.key();
     ctx.accounts.user_vault_account.token_account = ctx.accounts.token_account.key();
+    ctx.accounts.user_vault_account.share_token_account = ctx.accounts.share_token_account.key();
     ctx.accounts.user_vault_account.authority = ctx.accounts.user_account_authority.key();
     ctx.accounts.user_vault_account.user_account_bump = user_account_bump;
 
@@ -101,6 +148,19 @@ pub fn handler ( ctx: Context<CreateUserVaultAccount>, limit: u64, user_account_
     ctx.accounts.user_vault_account.user_unstake.cancel = false;
     ctx.accounts.user_vault_account.user_unstake.unstake_request_active = false;
     ctx.accounts.user_vault_account.user_unstake.status = RequestStatus::Inactive;
+    ctx.accounts.user_vault_account.user_unstake.requested_at = 0;
+    ctx.accounts.user_vault_account.user_unstake.cooldown_ends_at = 0;
+    ctx.accounts.user_vault_account.last_stake_timestamp = 0;
+    ctx.accounts.user_vault_account.user_total_penalty = 0;
+    ctx.accounts.user_vault_account.penalized_stake = 0;
+    ctx.accounts.user_vault_account.penalized_since = 0;
+
+    emit!(UserVaultAccountCreated {
+        vault: ctx.accounts.vault.key(),
+        user_vault_account: ctx.accounts.user_vault_account.key(),
+        authority: ctx.accounts.user_vault_account.authority,
+        deposit_limit: limit,
+    });
     
     Ok(())
 }
\ No newline at end of file
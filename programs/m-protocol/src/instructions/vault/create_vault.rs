@@ -1,9 +1,11 @@
 use crate::state::*;
+use crate::events::*;
 use crate::error::ErrorCode;
 use crate::constants::*;
 
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
+use mango::state::MAX_PAIRS;
 
 
 #[derive(Accounts)]
@@ -12,13 +14,13 @@ pub struct CreateVault <'info> {
     // vault account
     #[account(
         init, 
-        payer=manager,
+        payer=creator,
         seeds=[
             name.as_ref(),
             VAULT_SEED.as_ref()
             ],
         bump, 
-        space=8+512, // todo: adjust space later
+        space=8+Vault::LEN,
     )]
     pub vault: Box<Account<'info, Vault>>,
 
@@ -30,17 +32,21 @@ pub struct CreateVault <'info> {
     #[account(zero)]
     pub unstake_req: AccountLoader<'info, UnstakeReq>,
 
-    //* account that trades for the vault e.g on mango markets
     #[account(
-        mut, 
-        constraint = *manager.key==Vault::manager() @ ErrorCode::NotAdmin
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        constraint = config.is_creator(creator.key) @ ErrorCode::NotVaultCreator
     )]
-    pub manager: Signer<'info>,
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    //* initial admin, trader and keeper of the vault
+    #[account(mut)]
+    pub creator: Signer<'info>,
     
     // usdc token account to hold usdc tokens
     #[account(
         init,
-        payer=manager,
+        payer=creator,
         seeds=[
             vault.key().as_ref(),
             USDC_SEED.as_ref()
@@ -50,6 +56,20 @@ pub struct CreateVault <'info> {
         token::authority = vault_pda_authority,
         )]
     pub token_account: Account<'info, TokenAccount>,
+
+    // share mint representing ownership of the vault equity
+    #[account(
+        init,
+        payer=creator,
+        seeds=[
+            vault.key().as_ref(),
+            SHARES_SEED.as_ref()
+            ],
+        bump,
+        mint::decimals = token_mint.decimals,
+        mint::authority = vault_pda_authority,
+        )]
+    pub share_mint: Box<Account<'info, Mint>>,
     
     // * seeds=[vault.key().as_ref(), b"pdaauthority".as_ref()]
     ///CHECK: pda authority
@@ -107,14 +127,14 @@ pub fn handler (
 
     let mut stake_req = ctx.accounts.stake_req.load_init()?;
     stake_req.vault=ctx.accounts.vault.key();
-    stake_req.max_requests=1000;
     
     let mut unstake_req = ctx.accounts.unstake_req.load_init()?;
     unstake_req.vault=ctx.accounts.vault.key();
-    unstake_req.max_requests=1000;
     
     ctx.accounts.vault.publickey = ctx.accounts.vault.key();
-    ctx.accounts.vault.manager = ctx.accounts.manager.key();
+    ctx.accounts.vault.admin = ctx.accounts.creator.key();
+    ctx.accounts.vault.trader = ctx.accounts.creator.key();
+    ctx.accounts.vault.keeper = ctx.accounts.creator.key();
     ctx.accounts.vault.name = name;
     ctx.accounts.vault.vault_bump = vault_bump;
     ctx.accounts.vault.vault_authority_bump = vault_pda_bump;
@@ -126,7 +146,53 @@ pub fn handler (
     ctx.accounts.vault.stake_request_account = ctx.accounts.stake_req.key();
     ctx.accounts.vault.unstake_request_account = ctx.accounts.unstake_req.key();
     ctx.accounts.vault.mint = ctx.accounts.token_mint.key();
+    ctx.accounts.vault.share_mint = ctx.accounts.share_mint.key();
+
+    ctx.accounts.vault.fee_recipient = ctx.accounts.creator.key();
+    ctx.accounts.vault.management_fee_bps = 0;
+    ctx.accounts.vault.performance_fee_bps = 0;
+    ctx.accounts.vault.high_water_mark = SHARE_PRICE_SCALE;
+    ctx.accounts.vault.accrued_fees = 0;
+    ctx.accounts.vault.last_fee_timestamp = Clock::get()?.unix_timestamp;
+
+    ctx.accounts.vault.epoch = Epoch {
+        current: 0,
+        started_at: Clock::get()?.unix_timestamp,
+        settlement_price: SHARE_PRICE_SCALE,
+    };
+    ctx.accounts.vault.spot_open_orders = [Pubkey::default(); MAX_PAIRS];
+    ctx.accounts.vault.status = VaultStatus::Active;
+    ctx.accounts.vault.halt_reason = HaltReason::None;
+    ctx.accounts.vault.halted_at = 0;
+    ctx.accounts.vault.max_daily_drawdown_bps = 0;
+    ctx.accounts.vault.max_drawdown_bps = 0;
+    ctx.accounts.vault.peak_share_price = SHARE_PRICE_SCALE;
+    ctx.accounts.vault.paused = 0;
+    ctx.accounts.vault.gatekeeper_network = None;
+    ctx.accounts.vault.access_mode = AccessMode::Open;
+    ctx.accounts.vault.lockup_seconds = 0;
+    ctx.accounts.vault.unstake_cooldown_seconds = 0;
+    ctx.accounts.vault.early_withdrawal_penalty_bps = 0;
+    ctx.accounts.vault.penalty_period_seconds = 0;
+    ctx.accounts.vault.penalty_recipient = PenaltyRecipient::Stakers;
+    ctx.accounts.vault.accrued_insurance = 0;
+    ctx.accounts.vault.insurance_fund = Pubkey::default();
+    ctx.accounts.vault.insurance_fee_bps = 0;
+    ctx.accounts.vault.insurance_trigger_bps = 0;
+    ctx.accounts.vault.insurance_injection_allowed = false;
+    ctx.accounts.vault.insurance_fund_balance = 0;
     // ctx.accounts.vault.vault_pda_authority = ctx.accounts.vault_pda_authority.key();
 
+    emit!(VaultCreated {
+        vault: ctx.accounts.vault.key(),
+        creator: ctx.accounts.creator.key(),
+        name: ctx.accounts.vault.name.clone(),
+        limit,
+        mint: ctx.accounts.vault.mint,
+        share_mint: ctx.accounts.vault.share_mint,
+        stake_request_account: ctx.accounts.vault.stake_request_account,
+        unstake_request_account: ctx.accounts.vault.unstake_request_account,
+    });
+
     Ok(())
 }
\ No newline at end of file
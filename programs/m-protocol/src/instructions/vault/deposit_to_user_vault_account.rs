@@ -1,4 +1,5 @@
 use crate::state::*;
+use crate::events::*;
 use crate::constants::*;
 use crate::error::ErrorCode;
 
@@ -14,6 +15,13 @@ pub struct DepositToUserVaultAccount<'info> {
         constraint= vault.limit >= amount @ ErrorCode::MaxVaultLimit
     )]
     pub vault: Account<'info, Vault>, 
+
+    #[account(
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        constraint=!config.is_paused(&vault, PAUSE_DEPOSITS) @ ErrorCode::Paused
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
     
     #[account(
         mut,
@@ -43,6 +51,12 @@ pub struct DepositToUserVaultAccount<'info> {
         address=user_vault_account.authority @ ErrorCode::WrongUserAccountAuthority
     )]
     pub authority: Signer<'info>,
+
+    /// CHECK: gateway token of the user, any account when the vault has no gatekeeper network
+    #[account(
+        constraint=vault.has_valid_gateway_token(&gateway_token, authority.key) @ ErrorCode::InvalidGatewayToken
+    )]
+    pub gateway_token: UncheckedAccount<'info>,
     
     /// CHECK: user authority
     #[account(
@@ -84,11 +98,20 @@ pub fn handler (
 ) -> Result<()> {
     token::transfer(ctx.accounts.into_deposit_to_user_vault_account_context(), amount)?;
 
-    ctx.accounts.user_vault_account.deposit = ctx.accounts.user_vault_account.deposit.checked_add(amount).unwrap();
-    ctx.accounts.user_vault_account.deposit_limit = ctx.accounts.user_vault_account.deposit_limit.checked_sub(amount).unwrap();
+    ctx.accounts.user_vault_account.deposit = ctx.accounts.user_vault_account.deposit.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+    ctx.accounts.user_vault_account.deposit_limit = ctx.accounts.user_vault_account.deposit_limit.checked_sub(amount).ok_or(ErrorCode::MaxDepositLimit)?;
     
-    ctx.accounts.vault.deposit = ctx.accounts.vault.deposit.checked_add(amount).unwrap();
-    ctx.accounts.vault.limit = ctx.accounts.vault.limit.checked_sub(amount).unwrap();
+    ctx.accounts.vault.deposit = ctx.accounts.vault.deposit.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+    ctx.accounts.vault.limit = ctx.accounts.vault.limit.checked_sub(amount).ok_or(ErrorCode::MaxVaultLimit)?;
+
+    emit!(UserDeposited {
+        vault: ctx.accounts.vault.key(),
+        user_vault_account: ctx.accounts.user_vault_account.key(),
+        authority: ctx.accounts.authority.key(),
+        amount,
+        deposit: ctx.accounts.user_vault_account.deposit,
+        deposit_limit: ctx.accounts.user_vault_account.deposit_limit,
+    });
 
     Ok(())
 }
\ No newline at end of file
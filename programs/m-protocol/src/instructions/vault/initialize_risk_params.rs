@@ -0,0 +1,131 @@
+use crate::state::*;
+use crate::events::*;
+use crate::constants::*;
+use crate::cpi;
+use crate::error::ErrorCode;
+
+use anchor_lang::prelude::*;
+use mango::state::MAX_PAIRS;
+
+#[derive(Accounts)]
+pub struct InitializeRiskParams<'info> {
+    #[account(
+        has_one=admin @ ErrorCode::NotAdmin,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init,
+        payer=admin,
+        seeds=[
+            vault.key().as_ref(),
+            RISK_PARAMS_SEED.as_ref()
+        ],
+        bump,
+        space=8+RiskParams::LEN
+    )]
+    pub risk_params: Account<'info, RiskParams>,
+
+    /// CHECK: Mango acoount info
+    #[account(
+        mut,
+        address=vault.mango_group @ ErrorCode::MangoGroupNotMatch
+    )]
+    pub mango_group: AccountInfo<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(
+        mut,
+        seeds=[
+            mango_group.key().as_ref(),
+            vault_authority.key().as_ref(),
+            &vault.mango_account_num.to_le_bytes()
+        ],
+        bump=vault.mango_account_bump,
+        seeds::program=mango_program_id.key()
+    )]
+    pub mango_account: AccountInfo<'info>,
+
+    /// CHECK: vault authority
+    #[account(
+        seeds=[
+            vault.key().as_ref(),
+            VAULT_PDA_AUTHORITY_SEED.as_ref()
+        ],
+        bump=vault.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: Mango acoount info
+    #[account(
+        address=vault.mango_program_id @ ErrorCode::MangoProgramNotMatch
+    )]
+    pub mango_program_id: AccountInfo<'info>,
+
+    // * also the default key mango clears the delegate with
+    pub system_program: Program<'info, System>,
+}
+
+impl <'info> InitializeRiskParams <'info> {
+    
+    fn revoke_mango_delegate_context(&self) -> CpiContext<'_, '_, '_, 'info, cpi::SetDelegate<'info>> {
+
+        let cpi_accounts = cpi::SetDelegate {
+            mango_account: self.mango_account.to_account_info().clone(),
+            mango_group: self.mango_group.to_account_info().clone(),
+            mango_program_id: self.mango_program_id.to_account_info().clone(),
+            owner: self.vault_authority.to_account_info().clone(),
+            delegate_pubkey: self.system_program.to_account_info().clone()
+        };
+        let cpi_program = self.mango_program_id.to_account_info();
+
+        CpiContext::new(cpi_program, cpi_accounts)
+
+    }
+}
+
+// * max notional starts at zero, markets need `set_market_risk` before they can be traded,
+// * a delegate set before the params existed is revoked, it would trade past every limit
+pub fn handler(
+    ctx: Context<InitializeRiskParams>,
+    allowed_markets: u16,
+    max_leverage_bps: u64,
+    min_health_ratio_bps: u64,
+) -> Result<()> {
+    if allowed_markets >> MAX_PAIRS != 0 {
+        return err!(ErrorCode::InvalidRiskParams)
+    }
+
+    cpi::mango_cpi::set_delegate(ctx.accounts.revoke_mango_delegate_context().with_signer(
+        &[&[
+            ctx.accounts.vault.key().as_ref(),
+            VAULT_PDA_AUTHORITY_SEED.as_ref(),
+            &[ctx.accounts.vault.vault_authority_bump],
+        ]],
+    ))?;
+
+    let risk_params = &mut ctx.accounts.risk_params;
+    risk_params.vault = ctx.accounts.vault.key();
+    risk_params.allowed_markets = allowed_markets;
+    risk_params.max_notional = [0; MAX_PAIRS];
+    risk_params.max_leverage_bps = max_leverage_bps;
+    risk_params.min_health_ratio_bps = min_health_ratio_bps;
+    risk_params.bump = *ctx.bumps.get("risk_params").unwrap();
+
+    emit!(RiskParamsInitialized {
+        vault: ctx.accounts.vault.key(),
+        risk_params: ctx.accounts.risk_params.key(),
+        allowed_markets,
+        max_leverage_bps,
+        min_health_ratio_bps,
+    });
+    emit!(MangoDelegateRevoked {
+        vault: ctx.accounts.vault.key(),
+        mango_account: ctx.accounts.mango_account.key(),
+        authority: ctx.accounts.admin.key(),
+    });
+    Ok(())
+}
@@ -0,0 +1,132 @@
+use crate::mango_ix;
+use crate::state::*;
+use crate::events::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{ Token, TokenAccount };
+
+#[derive(Accounts)]
+pub struct InjectInsurance<'info> {
+    #[account(
+        mut, 
+        has_one=admin @ ErrorCode::NotAdmin,
+        has_one=insurance_fund @ ErrorCode::TokenAccountNotMatch,
+        constraint=vault.insurance_injection_allowed @ ErrorCode::InsuranceInjectionNotAllowed
+    )]
+    pub vault: Box<Account<'info, Vault>>,
+
+    #[account(
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        constraint=!config.is_paused(&vault, PAUSE_MANGO) @ ErrorCode::Paused
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    /// CHECK: vault authority
+     #[account(
+        seeds=[
+            vault.key().as_ref(),
+            VAULT_PDA_AUTHORITY_SEED.as_ref()
+        ],
+        bump=vault.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Mango account info
+    pub mango_group: AccountInfo<'info>,
+    
+    /// CHECK: Mango account info
+    #[account(
+        mut,
+        seeds=[
+            mango_group.key().as_ref(),
+            vault_authority.key().as_ref(),
+            &vault.mango_account_num.to_le_bytes()
+            ],
+        bump=vault.mango_account_bump,
+        seeds::program=mango_program_id.key()
+    )]
+    pub mango_account: AccountInfo<'info>,
+    
+    /// CHECK: Mango account info
+    #[account(mut)]
+    pub mango_cache: AccountInfo<'info>,
+    
+    /// CHECK: Mango account info
+    #[account(mut)]
+    pub mango_root_bank: AccountInfo<'info>,
+    
+    /// CHECK: Mango account info
+    #[account(mut)]
+    pub mango_node_bank: AccountInfo<'info>,
+    
+    /// CHECK: Mango account info
+    #[account(mut)]
+    pub mango_vault: AccountInfo<'info>,
+
+    #[account(
+        mut, 
+        token::mint=vault.mint,
+        token::authority=vault_authority,
+    )]
+    pub insurance_fund: Account<'info, TokenAccount>,
+
+    pub admin: Signer<'info>,
+
+    /// CHECK: Mango account info
+    pub mango_program_id: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> InjectInsurance<'info> {
+    fn deposit_to_mango_context(&self) -> CpiContext<'_, '_, '_, 'info, mango_ix::cpi::Deposit<'info>> {
+
+        let cpi_accounts = mango_ix::cpi::Deposit {
+            mango_account: self.mango_account.to_account_info().clone(),
+            mango_cache: self.mango_cache.to_account_info().clone(),
+            mango_group: self.mango_group.to_account_info().clone(),
+            mango_program_id: self.mango_program_id.to_account_info().clone(),
+            node_bank: self.mango_node_bank.to_account_info().clone(),
+            owner: self.vault_authority.to_account_info().clone(),
+            owner_token_account: self.insurance_fund.to_account_info().clone(),
+            root_bank: self.mango_root_bank.to_account_info().clone(),
+            vault: self.mango_vault.to_account_info().clone(),
+            token_program: self.token_program.to_account_info().clone(),
+        };
+        let cpi_program = self.mango_program_id.to_account_info();
+
+        CpiContext::new(cpi_program, cpi_accounts)
+
+    }
+}
+
+// * the injection shows up as a gain at the next balance update, one injection per unlock
+pub fn handler(ctx: Context<InjectInsurance>, amount: u64) -> Result<()> {
+    if amount > ctx.accounts.insurance_fund.amount {
+        return err!(ErrorCode::InsufficientBalance)
+    }
+
+    mango_ix::cpi::deposit(ctx.accounts.deposit_to_mango_context().with_signer(
+        &[&[
+            &ctx.accounts.vault.key().as_ref().to_owned(),
+            VAULT_PDA_AUTHORITY_SEED.as_ref(),
+            &[ctx.accounts.vault.vault_authority_bump],
+        ]],
+    ), amount)?;
+
+    ctx.accounts.insurance_fund.reload()?;
+    ctx.accounts.vault.insurance_injection_allowed = false;
+    ctx.accounts.vault.insurance_fund_balance = ctx.accounts.insurance_fund.amount;
+
+    emit!(InsuranceInjected {
+        vault: ctx.accounts.vault.key(),
+        mango_account: ctx.accounts.mango_account.key(),
+        amount,
+        insurance_fund_balance: ctx.accounts.vault.insurance_fund_balance,
+    });
+
+    Ok(())
+}
@@ -6,6 +6,8 @@ pub mod request_to_stake;
 pub mod request_to_unstake;
 pub mod process_stake;
 pub mod process_unstake;
+pub mod process_stake_batch;
+pub mod process_unstake_batch;
 pub mod update_user_balance;
 pub mod update_vault_balance;
 pub mod update_stake_request;
@@ -13,6 +15,27 @@ pub mod update_unstake_request;
 pub mod update_vault_limit;
 pub mod clear_stake_request;
 pub mod clear_unstake_request;
+pub mod set_vault_role;
+pub mod update_vault_fees;
+pub mod collect_fees;
+pub mod initialize_risk_params;
+pub mod set_market_risk;
+pub mod update_risk_limits;
+pub mod update_drawdown_limits;
+pub mod clear_halt;
+pub mod pause_vault;
+pub mod unpause_vault;
+pub mod set_gatekeeper_network;
+pub mod set_access_mode;
+pub mod add_access_entry;
+pub mod update_access_entry;
+pub mod remove_access_entry;
+pub mod update_lockup;
+pub mod update_withdrawal_penalty;
+pub mod create_insurance_fund;
+pub mod update_insurance_params;
+pub mod collect_insurance;
+pub mod inject_insurance;
 // pub mod constants;
 // pub mod mango_ix;
 
@@ -24,6 +47,8 @@ pub use request_to_stake::*;
 pub use request_to_unstake::*;
 pub use process_stake::*;
 pub use process_unstake::*;
+pub use process_stake_batch::*;
+pub use process_unstake_batch::*;
 pub use update_user_balance::*;
 pub use update_vault_balance::*;
 pub use update_stake_request::*;
@@ -31,5 +56,26 @@ pub use update_unstake_request::*;
 pub use update_vault_limit::*;
 pub use clear_stake_request::*;
 pub use clear_unstake_request::*;
+pub use set_vault_role::*;
+pub use update_vault_fees::*;
+pub use collect_fees::*;
+pub use initialize_risk_params::*;
+pub use set_market_risk::*;
+pub use update_risk_limits::*;
+pub use update_drawdown_limits::*;
+pub use clear_halt::*;
+pub use pause_vault::*;
+pub use unpause_vault::*;
+pub use set_gatekeeper_network::*;
+pub use set_access_mode::*;
+pub use add_access_entry::*;
+pub use update_access_entry::*;
+pub use remove_access_entry::*;
+pub use update_lockup::*;
+pub use update_withdrawal_penalty::*;
+pub use create_insurance_fund::*;
+pub use update_insurance_params::*;
+pub use collect_insurance::*;
+pub use inject_insurance::*;
 // pub use constants::*;
 // pub use mango_ix::*;
@@ -0,0 +1,37 @@
+use crate::state::*;
+use crate::events::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct PauseVault<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        constraint=config.guardian == authority.key() || vault.admin == authority.key() @ ErrorCode::NotGuardian
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    pub authority: Signer<'info>,
+}
+
+// * the protocol guardian or the vault admin can pause, only the vault admin unpauses
+pub fn handler(ctx: Context<PauseVault>, flags: u8) -> Result<()> {
+    if flags == 0 || flags & !PAUSE_ALL != 0 {
+        return err!(ErrorCode::InvalidPauseFlags)
+    }
+
+    ctx.accounts.vault.paused |= flags;
+
+    emit!(VaultPauseUpdated {
+        vault: ctx.accounts.vault.key(),
+        authority: ctx.accounts.authority.key(),
+        paused: ctx.accounts.vault.paused,
+    });
+    Ok(())
+}
@@ -1,20 +1,28 @@
 use crate::mango_ix;
 use crate::state::*;
+use crate::events::*;
 use crate::constants::*;
 use crate::error::ErrorCode;
 
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount};
+use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount};
 
 
 #[derive(Accounts)]
 pub struct ProcessStake<'info> {
     #[account(
         mut, 
-        has_one=manager @ ErrorCode::NotAdmin
+        has_one=keeper @ ErrorCode::NotKeeper
     )]
     pub vault: Box<Account<'info, Vault>>,
 
+    #[account(
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        constraint=!config.is_paused(&vault, PAUSE_KEEPER) @ ErrorCode::Paused
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
     #[account(
         mut,
         seeds=[
@@ -23,10 +31,28 @@ pub struct ProcessStake<'info> {
         ],
         bump=user_vault_account.user_account_bump,
         has_one=vault @ ErrorCode::VaultNotMatch,
-        constraint=user_vault_account.user_stake.stake_request_active==true @ ErrorCode::NoRequestSent
+        constraint=user_vault_account.user_stake.stake_request_active==true @ ErrorCode::NoRequestSent,
+        constraint=vault.epoch.is_settled(user_vault_account.user_stake.epoch) @ ErrorCode::EpochNotSettled
     )]
     pub user_vault_account: Box<Account<'info, UserVaultAccount>>,
 
+    /// CHECK: access entry pda of the user, may not exist
+    #[account(
+        seeds=[
+            vault.key().as_ref(),
+            user_vault_account.authority.key().as_ref(),
+            ACCESS_ENTRY_SEED.as_ref()
+        ],
+        bump
+    )]
+    pub access_entry: UncheckedAccount<'info>,
+
+    #[account(
+        mut, 
+        address=vault.stake_request_account @ ErrorCode::VaultNotMatch,
+    )]
+    pub stake_request_account: AccountLoader<'info, StakeReq>,
+
     /// CHECK: vault authority
      #[account(
         seeds=[
@@ -38,14 +64,13 @@ pub struct ProcessStake<'info> {
     pub vault_authority: UncheckedAccount<'info>,
 
 
-    pub manager: Signer<'info>,
+    pub keeper: Signer<'info>,
 
     #[account(
         mut, 
         token::mint=vault.mint,
         token::authority=vault_authority,
         address=user_vault_account.token_account @ ErrorCode::TokenAccountNotMatch,
-        constraint = user_token_account.amount >= user_vault_account.user_stake.stake_amount @ ErrorCode::InsufficientBalance
     )]
     pub user_token_account: Account<'info, TokenAccount>,
     
@@ -57,11 +82,34 @@ pub struct ProcessStake<'info> {
     )]
     pub vault_token_account: Account<'info, TokenAccount>,
 
+    #[account(
+        mut,
+        address=vault.share_mint @ ErrorCode::WrongMintProvided
+    )]
+    pub share_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut, 
+        token::mint=share_mint,
+        token::authority=vault_authority,
+        address=user_vault_account.share_token_account @ ErrorCode::TokenAccountNotMatch
+    )]
+    pub user_share_token_account: Box<Account<'info, TokenAccount>>,
+
     /// CHECK: Mango account info
     pub mango_group: AccountInfo<'info>,
     
     /// CHECK: Mango account info
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds=[
+            mango_group.key().as_ref(),
+            vault_authority.key().as_ref(),
+            &vault.mango_account_num.to_le_bytes()
+            ],
+        bump=vault.mango_account_bump,
+        seeds::program=mango_program_id.key()
+    )]
     pub mango_account: AccountInfo<'info>,
     // pub mango_account: AccountLoader<'info, MangoAccount>,
     
@@ -99,6 +147,16 @@ impl<'info> ProcessStake<'info> {
     //     CpiContext::new(cpi_program, cpi_accounts)
     // }
 
+    fn mint_shares_context(&self) -> CpiContext<'_, '_, '_, 'info, MintTo<'info>> {
+        let cpi_accounts = MintTo {
+            mint: self.share_mint.to_account_info().clone(),
+            to: self.user_share_token_account.to_account_info().clone(),
+            authority: self.vault_authority.to_account_info().clone(),
+        };
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
     fn deposit_to_mango_context(&self) -> CpiContext<'_, '_, '_, 'info, mango_ix::cpi::Deposit<'info>> {
 
         let cpi_accounts = mango_ix::cpi::Deposit {
@@ -122,15 +180,48 @@ impl<'info> ProcessStake<'info> {
 }
 
 pub fn handler(ctx: Context<ProcessStake>) -> Result<()> {
-    // * cancel stake
-    if ctx.accounts.user_vault_account.user_stake.cancel {
+    // * settled requests leave the queue wherever they sit in it
+    ctx.accounts
+        .stake_request_account
+        .load_mut()?
+        .queue
+        .remove(&ctx.accounts.user_vault_account.key());
+
+    // * wallets denied since they queued are turned away like a cancellation
+    let denied = AccessEntry::check(&ctx.accounts.vault, &ctx.accounts.access_entry, ctx.program_id).is_err();
+
+    // * cancel stake, requests queued before a halt are cancelled and the funds stay with the user
+    if ctx.accounts.user_vault_account.user_stake.cancel || ctx.accounts.vault.is_halted() || denied {
         ctx.accounts.user_vault_account.user_stake.status = RequestStatus::Cancelled;
+
+        emit!(StakeProcessed {
+            vault: ctx.accounts.vault.key(),
+            user_vault_account: ctx.accounts.user_vault_account.key(),
+            amount: 0,
+            shares: 0,
+            equity: ctx.accounts.user_vault_account.equity,
+            settled_epoch: ctx.accounts.vault.epoch.last_closed(),
+            settled_price: ctx.accounts.vault.epoch.settlement_price,
+            status: RequestStatus::Cancelled,
+        });
     } 
     
-    // // * check if there is enough balance in token account
-    // else if ctx.accounts.user_token_account.amount < ctx.accounts.user_vault_account.user_stake.stake_amount{
-    //     ctx.accounts.user_vault_account.user_stake.status = RequestStatus::InsufficientBalance;
-    // }
+    // * funds withdrawn since the request leave it unfilled, it is dequeued like any other
+    else if !ctx.accounts.user_vault_account.user_stake.max
+        && ctx.accounts.user_token_account.amount < ctx.accounts.user_vault_account.user_stake.stake_amount {
+        ctx.accounts.user_vault_account.user_stake.status = RequestStatus::InsufficientBalance;
+
+        emit!(StakeProcessed {
+            vault: ctx.accounts.vault.key(),
+            user_vault_account: ctx.accounts.user_vault_account.key(),
+            amount: 0,
+            shares: 0,
+            equity: ctx.accounts.user_vault_account.equity,
+            settled_epoch: ctx.accounts.vault.epoch.last_closed(),
+            settled_price: ctx.accounts.vault.epoch.settlement_price,
+            status: RequestStatus::InsufficientBalance,
+        });
+    }
     
     // * process stake
     else {
@@ -164,12 +255,42 @@ pub fn handler(ctx: Context<ProcessStake>) -> Result<()> {
         ]],
     ), amount)?;
     
-        ctx.accounts.user_vault_account.equity = ctx.accounts.user_vault_account.equity.checked_add(amount).unwrap();
-        ctx.accounts.user_vault_account.user_total_stake = ctx.accounts.user_vault_account.user_total_stake.checked_add(amount).unwrap();
-        ctx.accounts.vault.total_equity = ctx.accounts.vault.total_equity.checked_add(amount).unwrap();
+        // * every request of an epoch is priced at the share price recorded when it closed
+        let shares = ctx.accounts.vault.calculate_shares_to_mint(amount)?;
+
+        token::mint_to(ctx.accounts.mint_shares_context().with_signer(
+            &[&[
+                &ctx.accounts.vault.key().as_ref().to_owned(),
+                VAULT_PDA_AUTHORITY_SEED.as_ref(),
+                &[ctx.accounts.vault.vault_authority_bump],
+            ]],
+        ), shares)?;
+
+        ctx.accounts.vault.total_equity = ctx.accounts.vault.total_equity.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+        ctx.accounts.user_vault_account.user_total_stake = ctx.accounts.user_vault_account.user_total_stake.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
 
-        ctx.accounts.user_vault_account.user_pnl = ctx.accounts.user_vault_account.calculate_pnl();
+        let user_shares = ctx.accounts.user_share_token_account.amount.checked_add(shares).ok_or(ErrorCode::MathOverflow)?;
+        let share_supply = ctx.accounts.share_mint.supply.checked_add(shares).ok_or(ErrorCode::MathOverflow)?;
+        ctx.accounts.user_vault_account.equity = ctx.accounts.vault.calculate_equity(user_shares, share_supply)?;
+
+        ctx.accounts.user_vault_account.user_pnl = ctx.accounts.user_vault_account.calculate_pnl()?;
         ctx.accounts.user_vault_account.user_stake.status = RequestStatus::Successful;
+        ctx.accounts.user_vault_account.user_stake.settled_epoch = ctx.accounts.vault.epoch.last_closed();
+        ctx.accounts.user_vault_account.user_stake.settled_price = ctx.accounts.vault.epoch.settlement_price;
+        let now = Clock::get()?.unix_timestamp;
+        ctx.accounts.user_vault_account.last_stake_timestamp = now;
+        ctx.accounts.user_vault_account.add_penalized_stake(amount, ctx.accounts.vault.penalty_period_seconds, now)?;
+
+        emit!(StakeProcessed {
+            vault: ctx.accounts.vault.key(),
+            user_vault_account: ctx.accounts.user_vault_account.key(),
+            amount,
+            shares,
+            equity: ctx.accounts.user_vault_account.equity,
+            settled_epoch: ctx.accounts.user_vault_account.user_stake.settled_epoch,
+            settled_price: ctx.accounts.user_vault_account.user_stake.settled_price,
+            status: RequestStatus::Successful,
+        });
     }
 
     // ctx.accounts
@@ -0,0 +1,243 @@
+use crate::mango_ix;
+use crate::state::*;
+use crate::events::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount};
+
+// * remaining accounts, per queued user:
+// * [user_vault_account (mut), user_token_account (mut), user_share_token_account (mut), access_entry]
+#[derive(Accounts)]
+pub struct ProcessStakeBatch<'info> {
+    #[account(
+        mut, 
+        has_one=keeper @ ErrorCode::NotKeeper,
+        has_one=stake_request_account @ ErrorCode::VaultNotMatch
+    )]
+    pub vault: Box<Account<'info, Vault>>,
+
+    #[account(
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        constraint=!config.is_paused(&vault, PAUSE_KEEPER) @ ErrorCode::Paused
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(
+        mut, 
+        has_one=vault @ ErrorCode::VaultNotMatch,
+    )]
+    pub stake_request_account: AccountLoader<'info, StakeReq>,
+
+    /// CHECK: vault authority
+     #[account(
+        seeds=[
+            vault.key().as_ref(),
+            VAULT_PDA_AUTHORITY_SEED.as_ref()
+        ],
+        bump=vault.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    pub keeper: Signer<'info>,
+
+    #[account(
+        mut,
+        address=vault.share_mint @ ErrorCode::WrongMintProvided
+    )]
+    pub share_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: Mango account info
+    pub mango_group: AccountInfo<'info>,
+    
+    /// CHECK: Mango account info
+    #[account(
+        mut,
+        seeds=[
+            mango_group.key().as_ref(),
+            vault_authority.key().as_ref(),
+            &vault.mango_account_num.to_le_bytes()
+            ],
+        bump=vault.mango_account_bump,
+        seeds::program=mango_program_id.key()
+    )]
+    pub mango_account: AccountInfo<'info>,
+    
+    /// CHECK: Mango account info
+    #[account(mut)]
+    pub mango_cache: AccountInfo<'info>,
+    
+    /// CHECK: Mango account info
+    #[account(mut)]
+    pub mango_root_bank: AccountInfo<'info>,
+    
+    /// CHECK: Mango account info
+    #[account(mut)]
+    pub mango_node_bank: AccountInfo<'info>,
+    
+    /// CHECK: Mango account info
+    #[account(mut)]
+    pub mango_vault: AccountInfo<'info>,
+
+    /// CHECK: Mango account info
+    pub mango_program_id: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ProcessStakeBatch<'info> {
+    fn mint_shares_context(&self, to: AccountInfo<'info>) -> CpiContext<'_, '_, '_, 'info, MintTo<'info>> {
+        let cpi_accounts = MintTo {
+            mint: self.share_mint.to_account_info().clone(),
+            to,
+            authority: self.vault_authority.to_account_info().clone(),
+        };
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
+    fn deposit_to_mango_context(&self, owner_token_account: AccountInfo<'info>) -> CpiContext<'_, '_, '_, 'info, mango_ix::cpi::Deposit<'info>> {
+
+        let cpi_accounts = mango_ix::cpi::Deposit {
+            mango_account: self.mango_account.to_account_info().clone(),
+            mango_cache: self.mango_cache.to_account_info().clone(),
+            mango_group: self.mango_group.to_account_info().clone(),
+            mango_program_id: self.mango_program_id.to_account_info().clone(),
+            node_bank: self.mango_node_bank.to_account_info().clone(),
+            owner: self.vault_authority.to_account_info().clone(),
+            owner_token_account,
+            root_bank: self.mango_root_bank.to_account_info().clone(),
+            vault: self.mango_vault.to_account_info().clone(),
+            token_program: self.token_program.to_account_info().clone(),
+        };
+        let cpi_program = self.mango_program_id.to_account_info();
+
+        CpiContext::new(cpi_program, cpi_accounts)
+
+    }
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ProcessStakeBatch<'info>>) -> Result<()> {
+    if ctx.remaining_accounts.len() % STAKE_BATCH_ACCOUNTS_PER_USER != 0 {
+        return err!(ErrorCode::InvalidRemainingAccounts)
+    }
+
+    let vault_key = ctx.accounts.vault.key();
+    let vault_authority_bump = ctx.accounts.vault.vault_authority_bump;
+    let signer_seeds: &[&[u8]] = &[
+        vault_key.as_ref(),
+        VAULT_PDA_AUTHORITY_SEED.as_ref(),
+        &[vault_authority_bump],
+    ];
+
+    let mut share_supply = ctx.accounts.share_mint.supply;
+
+    for accounts in ctx.remaining_accounts.chunks(STAKE_BATCH_ACCOUNTS_PER_USER) {
+        // * every user must have a queued request, batches settle independently of each other
+        let entry = {
+            let stake_req = ctx.accounts.stake_request_account.load()?;
+            let index = stake_req
+                .queue
+                .find(&accounts[0].key())
+                .ok_or(ErrorCode::RequestNotQueued)?;
+            stake_req.queue.get(index).ok_or(ErrorCode::RequestNotQueued)?
+        };
+
+        if !ctx.accounts.vault.epoch.is_settled(entry.epoch) {
+            return err!(ErrorCode::EpochNotSettled)
+        }
+
+        let mut user_vault_account: Account<'info, UserVaultAccount> = Account::try_from(&accounts[0])?;
+        let user_token_account: Account<'info, TokenAccount> = Account::try_from(&accounts[1])?;
+        let user_share_token_account: Account<'info, TokenAccount> = Account::try_from(&accounts[2])?;
+
+        if user_vault_account.vault != vault_key {
+            return err!(ErrorCode::VaultNotMatch)
+        }
+
+        if user_token_account.key() != user_vault_account.token_account
+            || user_share_token_account.key() != user_vault_account.share_token_account {
+            return err!(ErrorCode::TokenAccountNotMatch)
+        }
+
+        let (access_entry, _) = Pubkey::find_program_address(
+            &[vault_key.as_ref(), user_vault_account.authority.as_ref(), ACCESS_ENTRY_SEED.as_ref()],
+            ctx.program_id,
+        );
+        if accounts[3].key() != access_entry {
+            return err!(ErrorCode::InvalidRemainingAccounts)
+        }
+
+        // * requests no longer active are only dequeued
+        if user_vault_account.user_stake.stake_request_active {
+            let amount: u64;
+            let mut settled: (u64, u64) = (0, 0);
+
+            if user_vault_account.user_stake.max {
+                amount = user_token_account.amount
+            } else {
+                amount = user_vault_account.user_stake.stake_amount;
+            }
+
+            // * a halted vault cancels queued stakes, so does a wallet denied since it queued
+            if user_vault_account.user_stake.cancel
+                || ctx.accounts.vault.is_halted()
+                || AccessEntry::check(&ctx.accounts.vault, &accounts[3], ctx.program_id).is_err() {
+                user_vault_account.user_stake.status = RequestStatus::Cancelled;
+            } else if user_token_account.amount < amount {
+                user_vault_account.user_stake.status = RequestStatus::InsufficientBalance;
+            } else {
+                let shares = ctx.accounts.vault.calculate_shares_to_mint(amount)?;
+
+                mango_ix::cpi::deposit(
+                    ctx.accounts
+                        .deposit_to_mango_context(user_token_account.to_account_info())
+                        .with_signer(&[signer_seeds]),
+                    amount,
+                )?;
+
+                token::mint_to(
+                    ctx.accounts
+                        .mint_shares_context(user_share_token_account.to_account_info())
+                        .with_signer(&[signer_seeds]),
+                    shares,
+                )?;
+
+                ctx.accounts.vault.total_equity = ctx.accounts.vault.total_equity.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+                share_supply = share_supply.checked_add(shares).ok_or(ErrorCode::MathOverflow)?;
+
+                let user_shares = user_share_token_account.amount.checked_add(shares).ok_or(ErrorCode::MathOverflow)?;
+                user_vault_account.user_total_stake = user_vault_account.user_total_stake.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+                user_vault_account.equity = ctx.accounts.vault.calculate_equity(user_shares, share_supply)?;
+                user_vault_account.user_pnl = user_vault_account.calculate_pnl()?;
+                user_vault_account.user_stake.status = RequestStatus::Successful;
+                user_vault_account.user_stake.settled_epoch = ctx.accounts.vault.epoch.last_closed();
+                user_vault_account.user_stake.settled_price = ctx.accounts.vault.epoch.settlement_price;
+                let now = Clock::get()?.unix_timestamp;
+                user_vault_account.last_stake_timestamp = now;
+                user_vault_account.add_penalized_stake(amount, ctx.accounts.vault.penalty_period_seconds, now)?;
+                settled = (amount, shares);
+            }
+
+            emit!(StakeProcessed {
+                vault: vault_key,
+                user_vault_account: user_vault_account.key(),
+                amount: settled.0,
+                shares: settled.1,
+                equity: user_vault_account.equity,
+                settled_epoch: ctx.accounts.vault.epoch.last_closed(),
+                settled_price: ctx.accounts.vault.epoch.settlement_price,
+                status: user_vault_account.user_stake.status,
+            });
+
+            user_vault_account.user_stake.stake_request_active = false;
+            user_vault_account.exit(ctx.program_id)?;
+        }
+
+        ctx.accounts.stake_request_account.load_mut()?.queue.remove(&entry.user_vault_account);
+    }
+
+    Ok(())
+}
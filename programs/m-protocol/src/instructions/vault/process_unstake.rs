@@ -1,16 +1,24 @@
 use crate::mango_ix;
 use crate::state::*;
+use crate::events::*;
 use crate::constants::*;
 use crate::error::ErrorCode;
 
 use anchor_lang::prelude::*;
-use anchor_spl::token::{ Token, TokenAccount };
+use anchor_spl::token::{ self, Burn, Mint, Token, TokenAccount };
 
 #[derive(Accounts)]
 pub struct ProcessUnstake<'info> {
-    #[account(mut, has_one=manager)]
+    #[account(mut, has_one=keeper @ ErrorCode::NotKeeper)]
     pub vault: Box<Account<'info, Vault>>,
 
+    #[account(
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        constraint=!config.is_paused(&vault, PAUSE_KEEPER) @ ErrorCode::Paused
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
     #[account(
         mut, 
         seeds=[
@@ -20,10 +28,16 @@ pub struct ProcessUnstake<'info> {
         bump=user_vault_account.user_account_bump,
         has_one=vault @ ErrorCode::VaultNotMatch,
         constraint=user_vault_account.user_unstake.unstake_request_active==true @ ErrorCode::NoRequestSent,
-        constraint = user_vault_account.equity >= user_vault_account.user_unstake.unstake_amount @ ErrorCode::InsufficientBalance
+        constraint=vault.epoch.is_settled(user_vault_account.user_unstake.epoch) @ ErrorCode::EpochNotSettled
     )]
     pub user_vault_account: Box<Account<'info, UserVaultAccount>>,
 
+    #[account(
+        mut, 
+        address=vault.unstake_request_account @ ErrorCode::VaultNotMatch,
+    )]
+    pub unstake_request_account: AccountLoader<'info, UnstakeReq>,
+
     /// CHECK: vault authority
      #[account(
         seeds=[
@@ -67,7 +81,7 @@ pub struct ProcessUnstake<'info> {
     pub mango_vault: AccountInfo<'info>,
 
 
-    pub manager: Signer<'info>,
+    pub keeper: Signer<'info>,
 
     #[account(
         mut, 
@@ -88,6 +102,20 @@ pub struct ProcessUnstake<'info> {
     )]
     pub vault_token_account: Account<'info, TokenAccount>,
 
+    #[account(
+        mut,
+        address=vault.share_mint @ ErrorCode::WrongMintProvided
+    )]
+    pub share_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut, 
+        token::mint=share_mint,
+        token::authority=vault_authority,
+        address=user_vault_account.share_token_account @ ErrorCode::TokenAccountNotMatch
+    )]
+    pub user_share_token_account: Box<Account<'info, TokenAccount>>,
+
     ///CHECK: Mango Account Info
     pub signer: AccountInfo<'info>,
 
@@ -110,6 +138,16 @@ impl<'info> ProcessUnstake<'info> {
     //     CpiContext::new(cpi_program, cpi_accounts)
     // }
 
+    fn burn_shares_context(&self) -> CpiContext<'_, '_, '_, 'info, Burn<'info>> {
+        let cpi_accounts = Burn {
+            mint: self.share_mint.to_account_info().clone(),
+            from: self.user_share_token_account.to_account_info().clone(),
+            authority: self.vault_authority.to_account_info().clone(),
+        };
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
     fn withdraw_from_mango_context(&self) -> CpiContext<'_, '_, '_, 'info, mango_ix::cpi::Withdraw<'info>> {
 
         let cpi_accounts = mango_ix::cpi::Withdraw {
@@ -133,11 +171,58 @@ impl<'info> ProcessUnstake<'info> {
 
 }
 
-pub fn handler(ctx: Context<ProcessUnstake>) -> Result<()> {
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ProcessUnstake<'info>>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    // * cancelled requests settle straight away
+    let cooldown_remaining = Vault::cooldown_remaining(
+        ctx.accounts.user_vault_account.user_unstake.cooldown_ends_at,
+        now,
+    );
+    if !ctx.accounts.user_vault_account.user_unstake.cancel && cooldown_remaining > 0 {
+        msg!("Unstake cooldown active for {} more seconds", cooldown_remaining);
+        return err!(ErrorCode::UnstakeCooldownActive)
+    }
+
+    // * settled requests leave the queue wherever they sit in it
+    ctx.accounts
+        .unstake_request_account
+        .load_mut()?
+        .queue
+        .remove(&ctx.accounts.user_vault_account.key());
+
     // * cancel unstake
 
     if ctx.accounts.user_vault_account.user_unstake.cancel {
         ctx.accounts.user_vault_account.user_unstake.status = RequestStatus::Cancelled;
+
+        emit!(UnstakeProcessed {
+            vault: ctx.accounts.vault.key(),
+            user_vault_account: ctx.accounts.user_vault_account.key(),
+            amount: 0,
+            shares: 0,
+            equity: ctx.accounts.user_vault_account.equity,
+            settled_epoch: ctx.accounts.vault.epoch.last_closed(),
+            settled_price: ctx.accounts.vault.epoch.settlement_price,
+            penalty: 0,
+            status: RequestStatus::Cancelled,
+        });
+    }
+    // * a stake settled since the request restarts the lockup, the unstake is dequeued unfilled
+    else if ctx.accounts.vault.lockup_remaining(ctx.accounts.user_vault_account.last_stake_timestamp, now) > 0 {
+        ctx.accounts.user_vault_account.user_unstake.status = RequestStatus::LockupActive;
+
+        emit!(UnstakeProcessed {
+            vault: ctx.accounts.vault.key(),
+            user_vault_account: ctx.accounts.user_vault_account.key(),
+            amount: 0,
+            shares: 0,
+            equity: ctx.accounts.user_vault_account.equity,
+            settled_epoch: ctx.accounts.vault.epoch.last_closed(),
+            settled_price: ctx.accounts.vault.epoch.settlement_price,
+            penalty: 0,
+            status: RequestStatus::LockupActive,
+        });
     }
     // * process unstake
     else {
@@ -147,37 +232,98 @@ pub fn handler(ctx: Context<ProcessUnstake>) -> Result<()> {
         //     &[ctx.accounts.vault.vault_authority_bump],
         // ];
 
+        let user_shares = ctx.accounts.user_share_token_account.amount;
+        let share_supply = ctx.accounts.share_mint.supply;
+
         let amount:u64;
+        let shares:u64;
 
         if ctx.accounts.user_vault_account.user_unstake.max {
-            amount = ctx.accounts.user_vault_account.equity;
+            shares = user_shares;
+            amount = ctx.accounts.vault.calculate_settlement_amount(shares)?;
         } else {
             amount = ctx.accounts.user_vault_account.user_unstake.unstake_amount;
+            shares = ctx.accounts.vault.calculate_shares_to_burn(amount)?;
         }
 
-        mango_ix::cpi::withdraw(ctx.accounts.withdraw_from_mango_context().with_signer(
-            &[&[
-                &ctx.accounts.vault.key().as_ref().to_owned(),
-                VAULT_PDA_AUTHORITY_SEED.as_ref(),
-                &[ctx.accounts.vault.vault_authority_bump],
-            ]],
-        ), amount, false)?;
-
-        // token::transfer(
-        //     ctx.accounts
-        //         .usdc_transfer_context()
-        //         .with_signer(&[&seed_signature[..]]),
-        //     amount,
-        // )?;
-        
-        ctx.accounts.user_vault_account.equity = ctx.accounts.user_vault_account.equity.checked_sub(amount).unwrap(); 
-        ctx.accounts.user_vault_account.user_total_unstake = ctx.accounts.user_vault_account.user_total_unstake.checked_add(amount).unwrap() ; 
-        ctx.accounts.vault.total_equity = ctx.accounts.vault.total_equity.checked_sub(amount).unwrap(); 
-        
-        ctx.accounts.user_vault_account.user_unstake.status = RequestStatus::Successful;
-
-        if ctx.accounts.user_vault_account.equity == 0 {
-            ctx.accounts.user_vault_account.refresh_stats()
+        // * shares redeemed since the request leave it unfilled, it is dequeued like any other
+        if shares > user_shares {
+            ctx.accounts.user_vault_account.user_unstake.status = RequestStatus::InsufficientBalance;
+
+            emit!(UnstakeProcessed {
+                vault: ctx.accounts.vault.key(),
+                user_vault_account: ctx.accounts.user_vault_account.key(),
+                amount: 0,
+                shares: 0,
+                equity: ctx.accounts.user_vault_account.equity,
+                settled_epoch: ctx.accounts.vault.epoch.last_closed(),
+                settled_price: ctx.accounts.vault.epoch.settlement_price,
+                penalty: 0,
+                status: RequestStatus::InsufficientBalance,
+            });
+        } else {
+            // * the penalty is withheld from the payout, the shares for the full amount are still burned
+            let equity = ctx.accounts.vault.calculate_settlement_amount(user_shares)?;
+            let penalty = ctx.accounts.vault.take_early_withdrawal_penalty(
+                &mut ctx.accounts.user_vault_account,
+                amount,
+                equity,
+                now,
+            )?;
+            let payout = amount.checked_sub(penalty).ok_or(ErrorCode::MathOverflow)?;
+
+            token::burn(ctx.accounts.burn_shares_context().with_signer(
+                &[&[
+                    &ctx.accounts.vault.key().as_ref().to_owned(),
+                    VAULT_PDA_AUTHORITY_SEED.as_ref(),
+                    &[ctx.accounts.vault.vault_authority_bump],
+                ]],
+            ), shares)?;
+
+            mango_ix::cpi::withdraw(ctx.accounts.withdraw_from_mango_context()
+                .with_remaining_accounts(ctx.remaining_accounts.to_vec())
+                .with_signer(
+                &[&[
+                    &ctx.accounts.vault.key().as_ref().to_owned(),
+                    VAULT_PDA_AUTHORITY_SEED.as_ref(),
+                    &[ctx.accounts.vault.vault_authority_bump],
+                ]],
+            ), payout, false)?;
+
+            // token::transfer(
+            //     ctx.accounts
+            //         .usdc_transfer_context()
+            //         .with_signer(&[&seed_signature[..]]),
+            //     amount,
+            // )?;
+
+            let outflow = ctx.accounts.vault.unstake_equity_outflow(amount, penalty)?;
+            ctx.accounts.vault.total_equity = ctx.accounts.vault.total_equity.checked_sub(outflow).ok_or(ErrorCode::MathOverflow)?; 
+            ctx.accounts.user_vault_account.record_unstake(payout, penalty)?;
+
+            let user_shares = user_shares.checked_sub(shares).ok_or(ErrorCode::MathOverflow)?;
+            let share_supply = share_supply.checked_sub(shares).ok_or(ErrorCode::MathOverflow)?;
+            ctx.accounts.user_vault_account.equity = ctx.accounts.vault.calculate_equity(user_shares, share_supply)?;
+
+            ctx.accounts.user_vault_account.user_unstake.status = RequestStatus::Successful;
+            ctx.accounts.user_vault_account.user_unstake.settled_epoch = ctx.accounts.vault.epoch.last_closed();
+            ctx.accounts.user_vault_account.user_unstake.settled_price = ctx.accounts.vault.epoch.settlement_price;
+
+            emit!(UnstakeProcessed {
+                vault: ctx.accounts.vault.key(),
+                user_vault_account: ctx.accounts.user_vault_account.key(),
+                amount,
+                shares,
+                equity: ctx.accounts.user_vault_account.equity,
+                settled_epoch: ctx.accounts.user_vault_account.user_unstake.settled_epoch,
+                settled_price: ctx.accounts.user_vault_account.user_unstake.settled_price,
+                penalty,
+                status: RequestStatus::Successful,
+            });
+
+            if ctx.accounts.user_vault_account.equity == 0 {
+                ctx.accounts.user_vault_account.refresh_stats()?;
+            }
         }
     }
 
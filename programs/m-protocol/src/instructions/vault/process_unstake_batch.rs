@@ -0,0 +1,275 @@
+use crate::mango_ix;
+use crate::state::*;
+use crate::events::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{ self, Burn, Mint, Token, TokenAccount };
+
+// * remaining accounts, the open orders of the mango margin basket in market index order, then
+// * per queued user:
+// * [user_vault_account (mut), user_token_account (mut), user_share_token_account (mut)]
+// * requests still cooling down are left queued, so they never hold back the ones behind them
+#[derive(Accounts)]
+pub struct ProcessUnstakeBatch<'info> {
+    #[account(
+        mut, 
+        has_one=keeper @ ErrorCode::NotKeeper,
+        has_one=unstake_request_account @ ErrorCode::VaultNotMatch
+    )]
+    pub vault: Box<Account<'info, Vault>>,
+
+    #[account(
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        constraint=!config.is_paused(&vault, PAUSE_KEEPER) @ ErrorCode::Paused
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(
+        mut, 
+        has_one=vault @ ErrorCode::VaultNotMatch,
+    )]
+    pub unstake_request_account: AccountLoader<'info, UnstakeReq>,
+
+    /// CHECK: vault authority
+     #[account(
+        seeds=[
+            vault.key().as_ref(),
+            VAULT_PDA_AUTHORITY_SEED.as_ref()
+        ],
+        bump=vault.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+     ///CHECK: Mango Account Info
+    #[account(mut)]
+    pub mango_group: AccountInfo<'info>,
+    
+    ///CHECK: Mango Account Info
+    #[account(
+        mut,
+        seeds=[
+            mango_group.key().as_ref(),
+            vault_authority.key().as_ref(),
+            &vault.mango_account_num.to_le_bytes()
+            ],
+        bump=vault.mango_account_bump,
+        seeds::program=mango_program_id.key()
+    )]
+    pub mango_account: AccountInfo<'info>,
+    
+    ///CHECK: Mango Account Info
+    pub mango_cache: AccountInfo<'info>,
+    
+    ///CHECK: Mango Account Info
+    pub mango_root_bank: AccountInfo<'info>,
+    
+    ///CHECK: Mango Account Info
+    #[account(mut)]
+    pub mango_node_bank: AccountInfo<'info>,
+    
+    ///CHECK: Mango Account Info
+    #[account(mut)]
+    pub mango_vault: AccountInfo<'info>,
+
+    pub keeper: Signer<'info>,
+
+    #[account(
+        mut,
+        address=vault.share_mint @ ErrorCode::WrongMintProvided
+    )]
+    pub share_mint: Box<Account<'info, Mint>>,
+
+    ///CHECK: Mango Account Info
+    pub signer: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    ///CHECK: Mango Account Info
+    pub mango_program_id: AccountInfo<'info>,
+}
+
+impl<'info> ProcessUnstakeBatch<'info> {
+    fn burn_shares_context(&self, from: AccountInfo<'info>) -> CpiContext<'_, '_, '_, 'info, Burn<'info>> {
+        let cpi_accounts = Burn {
+            mint: self.share_mint.to_account_info().clone(),
+            from,
+            authority: self.vault_authority.to_account_info().clone(),
+        };
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
+    fn withdraw_from_mango_context(&self, token_account: AccountInfo<'info>) -> CpiContext<'_, '_, '_, 'info, mango_ix::cpi::Withdraw<'info>> {
+
+        let cpi_accounts = mango_ix::cpi::Withdraw {
+            mango_account: self.mango_account.to_account_info().clone(),
+            mango_cache: self.mango_cache.to_account_info().clone(),
+            mango_group: self.mango_group.to_account_info().clone(),
+            mango_program_id: self.mango_program_id.to_account_info().clone(),
+            node_bank: self.mango_node_bank.to_account_info().clone(),
+            owner: self.vault_authority.to_account_info().clone(),
+            root_bank: self.mango_root_bank.to_account_info().clone(),
+            signer: self.signer.to_account_info().clone(),
+            token_account,
+            vault: self.mango_vault.to_account_info().clone(),
+            token_program: self.token_program.to_account_info().clone(),
+        };
+        let cpi_program = self.mango_program_id.to_account_info();
+
+        CpiContext::new(cpi_program, cpi_accounts)
+
+    }
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ProcessUnstakeBatch<'info>>) -> Result<()> {
+    // * the open orders of the mango margin basket lead the remaining accounts, followed by the users
+    let open_orders_count = mango_ix::cpi::margin_basket_open_orders(
+        ctx.accounts.mango_program_id.key,
+        ctx.accounts.mango_group.key,
+        &ctx.accounts.mango_account,
+    )?.len();
+    if ctx.remaining_accounts.len() < open_orders_count {
+        return err!(ErrorCode::InvalidRemainingAccounts)
+    }
+    let (open_orders_accounts, user_accounts) = ctx.remaining_accounts.split_at(open_orders_count);
+
+    if user_accounts.len() % BATCH_ACCOUNTS_PER_USER != 0 {
+        return err!(ErrorCode::InvalidRemainingAccounts)
+    }
+
+    let vault_key = ctx.accounts.vault.key();
+    let vault_authority_bump = ctx.accounts.vault.vault_authority_bump;
+    let signer_seeds: &[&[u8]] = &[
+        vault_key.as_ref(),
+        VAULT_PDA_AUTHORITY_SEED.as_ref(),
+        &[vault_authority_bump],
+    ];
+
+    let mut share_supply = ctx.accounts.share_mint.supply;
+    let now = Clock::get()?.unix_timestamp;
+
+    for accounts in user_accounts.chunks(BATCH_ACCOUNTS_PER_USER) {
+        // * every user must have a queued request
+        let entry = {
+            let unstake_req = ctx.accounts.unstake_request_account.load()?;
+            let index = unstake_req
+                .queue
+                .find(&accounts[0].key())
+                .ok_or(ErrorCode::RequestNotQueued)?;
+            unstake_req.queue.get(index).ok_or(ErrorCode::RequestNotQueued)?
+        };
+
+        if !ctx.accounts.vault.epoch.is_settled(entry.epoch) {
+            return err!(ErrorCode::EpochNotSettled)
+        }
+
+        let mut user_vault_account: Account<'info, UserVaultAccount> = Account::try_from(&accounts[0])?;
+        let user_token_account: Account<'info, TokenAccount> = Account::try_from(&accounts[1])?;
+        let user_share_token_account: Account<'info, TokenAccount> = Account::try_from(&accounts[2])?;
+
+        if user_vault_account.vault != vault_key {
+            return err!(ErrorCode::VaultNotMatch)
+        }
+
+        if user_token_account.key() != user_vault_account.token_account
+            || user_share_token_account.key() != user_vault_account.share_token_account {
+            return err!(ErrorCode::TokenAccountNotMatch)
+        }
+
+        // * requests still cooling down stay queued, cancelled ones settle straight away
+        let cooldown_remaining = Vault::cooldown_remaining(entry.cooldown_ends_at, now);
+        if user_vault_account.user_unstake.unstake_request_active
+            && !user_vault_account.user_unstake.cancel
+            && cooldown_remaining > 0 {
+            msg!("Unstake cooldown active for {} more seconds, left queued", cooldown_remaining);
+            continue;
+        }
+
+        // * requests no longer active are only dequeued
+        if user_vault_account.user_unstake.unstake_request_active {
+            let mut settled: (u64, u64, u64) = (0, 0, 0);
+
+            if user_vault_account.user_unstake.cancel {
+                user_vault_account.user_unstake.status = RequestStatus::Cancelled;
+            } else if ctx.accounts.vault.lockup_remaining(user_vault_account.last_stake_timestamp, now) > 0 {
+                // * a stake settled since the request restarts the lockup
+                user_vault_account.user_unstake.status = RequestStatus::LockupActive;
+            } else {
+                let user_shares = user_share_token_account.amount;
+
+                let amount: u64;
+                let shares: u64;
+
+                if user_vault_account.user_unstake.max {
+                    shares = user_shares;
+                    amount = ctx.accounts.vault.calculate_settlement_amount(shares)?;
+                } else {
+                    amount = user_vault_account.user_unstake.unstake_amount;
+                    shares = ctx.accounts.vault.calculate_shares_to_burn(amount)?;
+                }
+
+                if shares > user_shares {
+                    user_vault_account.user_unstake.status = RequestStatus::InsufficientBalance;
+                } else {
+                    let equity = ctx.accounts.vault.calculate_settlement_amount(user_shares)?;
+                    let penalty = ctx.accounts.vault.take_early_withdrawal_penalty(&mut user_vault_account, amount, equity, now)?;
+                    let payout = amount.checked_sub(penalty).ok_or(ErrorCode::MathOverflow)?;
+
+                    token::burn(
+                        ctx.accounts
+                            .burn_shares_context(user_share_token_account.to_account_info())
+                            .with_signer(&[signer_seeds]),
+                        shares,
+                    )?;
+
+                    mango_ix::cpi::withdraw(
+                        ctx.accounts
+                            .withdraw_from_mango_context(user_token_account.to_account_info())
+                            .with_remaining_accounts(open_orders_accounts.to_vec())
+                            .with_signer(&[signer_seeds]),
+                        payout,
+                        false,
+                    )?;
+
+                    let outflow = ctx.accounts.vault.unstake_equity_outflow(amount, penalty)?;
+                    ctx.accounts.vault.total_equity = ctx.accounts.vault.total_equity.checked_sub(outflow).ok_or(ErrorCode::MathOverflow)?;
+                    share_supply = share_supply.checked_sub(shares).ok_or(ErrorCode::MathOverflow)?;
+
+                    let user_shares = user_shares.checked_sub(shares).ok_or(ErrorCode::MathOverflow)?;
+                    user_vault_account.record_unstake(payout, penalty)?;
+                    user_vault_account.equity = ctx.accounts.vault.calculate_equity(user_shares, share_supply)?;
+                    user_vault_account.user_unstake.status = RequestStatus::Successful;
+                    user_vault_account.user_unstake.settled_epoch = ctx.accounts.vault.epoch.last_closed();
+                    user_vault_account.user_unstake.settled_price = ctx.accounts.vault.epoch.settlement_price;
+                    settled = (amount, shares, penalty);
+
+                    if user_vault_account.equity == 0 {
+                        user_vault_account.refresh_stats()?;
+                    }
+                }
+            }
+
+            emit!(UnstakeProcessed {
+                vault: vault_key,
+                user_vault_account: user_vault_account.key(),
+                amount: settled.0,
+                shares: settled.1,
+                equity: user_vault_account.equity,
+                settled_epoch: ctx.accounts.vault.epoch.last_closed(),
+                settled_price: ctx.accounts.vault.epoch.settlement_price,
+                penalty: settled.2,
+                status: user_vault_account.user_unstake.status,
+            });
+
+            user_vault_account.user_unstake.unstake_request_active = false;
+            user_vault_account.exit(ctx.program_id)?;
+        }
+
+        ctx.accounts.unstake_request_account.load_mut()?.queue.remove(&entry.user_vault_account);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use crate::state::*;
+use crate::events::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RemoveAccessEntry<'info> {
+    #[account(
+        has_one=admin @ ErrorCode::NotAdmin,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        close=admin,
+        seeds=[
+            vault.key().as_ref(),
+            access_entry.wallet.as_ref(),
+            ACCESS_ENTRY_SEED.as_ref()
+        ],
+        bump=access_entry.bump,
+        has_one=vault @ ErrorCode::VaultNotMatch,
+    )]
+    pub access_entry: Account<'info, AccessEntry>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+// * the wallet loses its allowlist entry and any denial with it
+pub fn handler(ctx: Context<RemoveAccessEntry>) -> Result<()> {
+    emit!(AccessEntryRemoved {
+        vault: ctx.accounts.vault.key(),
+        wallet: ctx.accounts.access_entry.wallet,
+    });
+    Ok(())
+}
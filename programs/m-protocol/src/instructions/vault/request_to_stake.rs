@@ -1,4 +1,6 @@
 use crate::state::*;
+use crate::constants::*;
+use crate::events::*;
 use crate::error::ErrorCode;
 
 use anchor_lang::prelude::*;
@@ -17,19 +19,59 @@ pub struct RequestToStake<'info> {
     )]
     pub user_vault_account: Account<'info, UserVaultAccount>,
 
+    #[account(
+        address=user_vault_account.vault @ ErrorCode::VaultNotMatch,
+        constraint=!vault.is_halted() @ ErrorCode::VaultHalted
+    )]
+    pub vault: Box<Account<'info, Vault>>,
+
+    #[account(
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        constraint=!config.is_paused(&vault, PAUSE_STAKE_REQUESTS) @ ErrorCode::Paused
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
     pub authority: Signer<'info>,
 
+    /// CHECK: gateway token of the user, any account when the vault has no gatekeeper network
+    #[account(
+        constraint=vault.has_valid_gateway_token(&gateway_token, authority.key) @ ErrorCode::InvalidGatewayToken
+    )]
+    pub gateway_token: UncheckedAccount<'info>,
+
+    /// CHECK: access entry pda of the user, may not exist
+    #[account(
+        seeds=[
+            vault.key().as_ref(),
+            authority.key().as_ref(),
+            ACCESS_ENTRY_SEED.as_ref()
+        ],
+        bump
+    )]
+    pub access_entry: UncheckedAccount<'info>,
+
     #[account(
         mut,
         constraint=vault_stake_req_account.load()?.vault==user_vault_account.vault@ErrorCode::VaultNotMatch,
-        constraint=vault_stake_req_account.load()?.max_requests>=vault_stake_req_account.load()?.count @ErrorCode::MaxRequestLimit
     )]
     pub vault_stake_req_account: AccountLoader<'info, StakeReq>,
 }
 
 pub fn handler(ctx: Context<RequestToStake>, amount: u64, max: bool) -> Result<()> {
+    AccessEntry::check(&ctx.accounts.vault, &ctx.accounts.access_entry, ctx.program_id)?;
+
     let mut stake_req_account = ctx.accounts.vault_stake_req_account.load_mut()?;
-    let index = stake_req_account.count;
+
+    stake_req_account.queue.push(RequestEntry {
+        user_vault_account: ctx.accounts.user_vault_account.key(),
+        amount,
+        slot: Clock::get()?.slot,
+        timestamp: Clock::get()?.unix_timestamp,
+        epoch: ctx.accounts.vault.epoch.current,
+        cooldown_ends_at: 0,
+        max: max as u8,
+    })?;
 
     ctx.accounts
         .user_vault_account
@@ -39,8 +81,16 @@ pub fn handler(ctx: Context<RequestToStake>, amount: u64, max: bool) -> Result<(
     ctx.accounts.user_vault_account.user_stake.max = max;
     ctx.accounts.user_vault_account.user_stake.cancel = false;
     ctx.accounts.user_vault_account.user_stake.status = RequestStatus::Pending;
+    ctx.accounts.user_vault_account.user_stake.epoch = ctx.accounts.vault.epoch.current;
+
+    emit!(StakeRequested {
+        vault: ctx.accounts.vault.key(),
+        user_vault_account: ctx.accounts.user_vault_account.key(),
+        amount,
+        max,
+        epoch: ctx.accounts.vault.epoch.current,
+        status: RequestStatus::Pending,
+    });
 
-    stake_req_account.orders[index as usize] = ctx.accounts.user_vault_account.key();
-    stake_req_account.count = index + 1;
     Ok(())
 }
@@ -1,4 +1,6 @@
 use crate::state::*;
+use crate::constants::*;
+use crate::events::*;
 use crate::error::ErrorCode;
 
 use anchor_lang::prelude::*;
@@ -17,30 +19,67 @@ pub struct RequestToUnstake <'info> {
         
     )]
     pub user_vault_account: Account<'info, UserVaultAccount>,
+
+    #[account(
+        address=user_vault_account.vault @ ErrorCode::VaultNotMatch
+    )]
+    pub vault: Box<Account<'info, Vault>>,
+
+    #[account(
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        constraint=!config.is_paused(&vault, PAUSE_UNSTAKE_REQUESTS) @ ErrorCode::Paused
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
     
     pub authority: Signer<'info>,
     
     #[account(
         mut,
         constraint=vault_unstake_req_account.load()?.vault==user_vault_account.vault @ ErrorCode::VaultNotMatch,
-        constraint=vault_unstake_req_account.load()?.max_requests>=vault_unstake_req_account.load()?.count @ ErrorCode::MaxRequestLimit
     )]
     pub vault_unstake_req_account: AccountLoader<'info, UnstakeReq>
 }
 
 pub fn handler ( ctx: Context<RequestToUnstake>, amount: u64, max: bool) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
 
+    let lockup_remaining = ctx.accounts.vault.lockup_remaining(ctx.accounts.user_vault_account.last_stake_timestamp, now);
+    if lockup_remaining > 0 {
+        msg!("Lockup active for {} more seconds", lockup_remaining);
+        return err!(ErrorCode::LockupActive)
+    }
+
+    let cooldown_ends_at = ctx.accounts.vault.cooldown_ends_at(now);
     let mut unstake_req_account = ctx.accounts.vault_unstake_req_account.load_mut()?;
-    let index = unstake_req_account.count;
+
+    unstake_req_account.queue.push(RequestEntry {
+        user_vault_account: ctx.accounts.user_vault_account.key(),
+        amount,
+        slot: Clock::get()?.slot,
+        timestamp: now,
+        epoch: ctx.accounts.vault.epoch.current,
+        cooldown_ends_at,
+        max: max as u8,
+    })?;
 
     ctx.accounts.user_vault_account.user_unstake.unstake_request_active = true;
     ctx.accounts.user_vault_account.user_unstake.unstake_amount = amount;
     ctx.accounts.user_vault_account.user_unstake.max = max;
     ctx.accounts.user_vault_account.user_unstake.cancel = false;
     ctx.accounts.user_vault_account.user_unstake.status = RequestStatus::Pending;
+    ctx.accounts.user_vault_account.user_unstake.epoch = ctx.accounts.vault.epoch.current;
+    ctx.accounts.user_vault_account.user_unstake.requested_at = now;
+    ctx.accounts.user_vault_account.user_unstake.cooldown_ends_at = cooldown_ends_at;
 
-    unstake_req_account.orders[index as usize] = ctx.accounts.user_vault_account.key();
-    unstake_req_account.count = index + 1;
+    emit!(UnstakeRequested {
+        vault: ctx.accounts.vault.key(),
+        user_vault_account: ctx.accounts.user_vault_account.key(),
+        amount,
+        max,
+        epoch: ctx.accounts.vault.epoch.current,
+        status: RequestStatus::Pending,
+    });
 
     Ok(())
-} 
\ No newline at end of file
+}
\ No newline at end of file
@@ -0,0 +1,26 @@
+use crate::state::*;
+use crate::events::*;
+use crate::error::ErrorCode;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetAccessMode<'info> {
+    #[account(
+        mut, 
+        has_one=admin @ ErrorCode::NotAdmin,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetAccessMode>, access_mode: AccessMode) -> Result<()> {
+    ctx.accounts.vault.access_mode = access_mode;
+
+    emit!(AccessModeUpdated {
+        vault: ctx.accounts.vault.key(),
+        access_mode,
+    });
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use crate::state::*;
+use crate::events::*;
+use crate::error::ErrorCode;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetGatekeeperNetwork<'info> {
+    #[account(
+        mut, 
+        has_one=admin @ ErrorCode::NotAdmin,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    pub admin: Signer<'info>,
+}
+
+// * `None` opens the vault to users without a gateway token
+pub fn handler(ctx: Context<SetGatekeeperNetwork>, gatekeeper_network: Option<Pubkey>) -> Result<()> {
+    ctx.accounts.vault.gatekeeper_network = gatekeeper_network;
+
+    emit!(GatekeeperNetworkUpdated {
+        vault: ctx.accounts.vault.key(),
+        gatekeeper_network,
+    });
+    Ok(())
+}
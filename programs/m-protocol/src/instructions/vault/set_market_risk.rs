@@ -0,0 +1,49 @@
+use crate::state::*;
+use crate::events::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+
+use anchor_lang::prelude::*;
+use mango::state::MAX_PAIRS;
+
+#[derive(Accounts)]
+pub struct SetMarketRisk<'info> {
+    #[account(
+        has_one=admin @ ErrorCode::NotAdmin,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds=[
+            vault.key().as_ref(),
+            RISK_PARAMS_SEED.as_ref()
+        ],
+        bump=risk_params.bump,
+        has_one=vault @ ErrorCode::VaultNotMatch,
+    )]
+    pub risk_params: Account<'info, RiskParams>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<SetMarketRisk>,
+    market_index: u64,
+    allowed: bool,
+    max_notional: u64,
+) -> Result<()> {
+    if market_index >= MAX_PAIRS as u64 {
+        return err!(ErrorCode::InvalidMarketIndex)
+    }
+
+    ctx.accounts.risk_params.set_market(market_index as usize, allowed, max_notional);
+
+    emit!(MarketRiskUpdated {
+        vault: ctx.accounts.vault.key(),
+        market_index,
+        allowed,
+        max_notional,
+    });
+    Ok(())
+}
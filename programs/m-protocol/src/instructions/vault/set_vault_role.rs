@@ -0,0 +1,34 @@
+use crate::state::*;
+use crate::events::*;
+use crate::error::ErrorCode;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetVaultRole<'info> {
+    #[account(
+        mut, 
+        has_one=admin @ ErrorCode::NotAdmin,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetVaultRole>, role: VaultRole, new_key: Pubkey) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+
+    let old_key = match role {
+        VaultRole::Admin => std::mem::replace(&mut vault.admin, new_key),
+        VaultRole::Trader => std::mem::replace(&mut vault.trader, new_key),
+        VaultRole::Keeper => std::mem::replace(&mut vault.keeper, new_key),
+    };
+
+    emit!(VaultRoleUpdated {
+        vault: vault.key(),
+        role,
+        old_key,
+        new_key,
+    });
+    Ok(())
+}
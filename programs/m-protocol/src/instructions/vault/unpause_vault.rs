@@ -0,0 +1,32 @@
+use crate::state::*;
+use crate::events::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UnpauseVault<'info> {
+    #[account(
+        mut, 
+        has_one=admin @ ErrorCode::NotAdmin,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<UnpauseVault>, flags: u8) -> Result<()> {
+    if flags == 0 || flags & !PAUSE_ALL != 0 {
+        return err!(ErrorCode::InvalidPauseFlags)
+    }
+
+    ctx.accounts.vault.paused &= !flags;
+
+    emit!(VaultPauseUpdated {
+        vault: ctx.accounts.vault.key(),
+        authority: ctx.accounts.admin.key(),
+        paused: ctx.accounts.vault.paused,
+    });
+    Ok(())
+}
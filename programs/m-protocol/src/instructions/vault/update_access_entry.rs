@@ -0,0 +1,41 @@
+use crate::state::*;
+use crate::events::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateAccessEntry<'info> {
+    #[account(
+        has_one=admin @ ErrorCode::NotAdmin,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds=[
+            vault.key().as_ref(),
+            access_entry.wallet.as_ref(),
+            ACCESS_ENTRY_SEED.as_ref()
+        ],
+        bump=access_entry.bump,
+        has_one=vault @ ErrorCode::VaultNotMatch,
+    )]
+    pub access_entry: Account<'info, AccessEntry>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<UpdateAccessEntry>, allowed: bool, denied: bool) -> Result<()> {
+    ctx.accounts.access_entry.allowed = allowed;
+    ctx.accounts.access_entry.denied = denied;
+
+    emit!(AccessEntryUpdated {
+        vault: ctx.accounts.vault.key(),
+        wallet: ctx.accounts.access_entry.wallet,
+        allowed,
+        denied,
+    });
+    Ok(())
+}
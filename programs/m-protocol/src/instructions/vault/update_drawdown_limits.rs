@@ -0,0 +1,37 @@
+use crate::state::*;
+use crate::events::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateDrawdownLimits<'info> {
+    #[account(
+        mut, 
+        has_one=admin @ ErrorCode::NotAdmin,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateDrawdownLimits>,
+    max_daily_drawdown_bps: u16,
+    max_drawdown_bps: u16,
+) -> Result<()> {
+    if max_daily_drawdown_bps as u64 > BPS_DENOMINATOR || max_drawdown_bps as u64 > BPS_DENOMINATOR {
+        return err!(ErrorCode::InvalidDrawdownLimit)
+    }
+
+    ctx.accounts.vault.max_daily_drawdown_bps = max_daily_drawdown_bps;
+    ctx.accounts.vault.max_drawdown_bps = max_drawdown_bps;
+
+    emit!(DrawdownLimitsUpdated {
+        vault: ctx.accounts.vault.key(),
+        max_daily_drawdown_bps,
+        max_drawdown_bps,
+    });
+    Ok(())
+}
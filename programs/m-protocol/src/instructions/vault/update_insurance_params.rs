@@ -0,0 +1,37 @@
+use crate::state::*;
+use crate::events::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateInsuranceParams<'info> {
+    #[account(
+        mut, 
+        has_one=admin @ ErrorCode::NotAdmin,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateInsuranceParams>,
+    insurance_fee_bps: u16,
+    insurance_trigger_bps: u16,
+) -> Result<()> {
+    if insurance_fee_bps as u64 > BPS_DENOMINATOR || insurance_trigger_bps as u64 > BPS_DENOMINATOR {
+        return err!(ErrorCode::InvalidFeeRate)
+    }
+
+    ctx.accounts.vault.insurance_fee_bps = insurance_fee_bps;
+    ctx.accounts.vault.insurance_trigger_bps = insurance_trigger_bps;
+
+    emit!(InsuranceParamsUpdated {
+        vault: ctx.accounts.vault.key(),
+        insurance_fee_bps,
+        insurance_trigger_bps,
+    });
+    Ok(())
+}
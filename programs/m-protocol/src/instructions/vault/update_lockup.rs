@@ -0,0 +1,38 @@
+use crate::state::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::error::ErrorCode;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateLockup<'info> {
+    #[account(
+        mut, 
+        has_one=admin @ ErrorCode::NotAdmin,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateLockup>,
+    lockup_seconds: i64,
+    unstake_cooldown_seconds: i64,
+) -> Result<()> {
+    if !(0..=MAX_LOCKUP_SECONDS).contains(&lockup_seconds)
+        || !(0..=MAX_UNSTAKE_COOLDOWN_SECONDS).contains(&unstake_cooldown_seconds) {
+        return err!(ErrorCode::InvalidLockupPeriod)
+    }
+
+    ctx.accounts.vault.lockup_seconds = lockup_seconds;
+    ctx.accounts.vault.unstake_cooldown_seconds = unstake_cooldown_seconds;
+
+    emit!(LockupUpdated {
+        vault: ctx.accounts.vault.key(),
+        lockup_seconds,
+        unstake_cooldown_seconds,
+    });
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use crate::state::*;
+use crate::events::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateRiskLimits<'info> {
+    #[account(
+        has_one=admin @ ErrorCode::NotAdmin,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds=[
+            vault.key().as_ref(),
+            RISK_PARAMS_SEED.as_ref()
+        ],
+        bump=risk_params.bump,
+        has_one=vault @ ErrorCode::VaultNotMatch,
+    )]
+    pub risk_params: Account<'info, RiskParams>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateRiskLimits>,
+    max_leverage_bps: u64,
+    min_health_ratio_bps: u64,
+) -> Result<()> {
+    ctx.accounts.risk_params.max_leverage_bps = max_leverage_bps;
+    ctx.accounts.risk_params.min_health_ratio_bps = min_health_ratio_bps;
+
+    emit!(RiskLimitsUpdated {
+        vault: ctx.accounts.vault.key(),
+        max_leverage_bps,
+        min_health_ratio_bps,
+    });
+    Ok(())
+}
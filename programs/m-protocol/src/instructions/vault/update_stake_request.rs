@@ -1,4 +1,6 @@
 use crate::state::*;
+use crate::constants::*;
+use crate::events::*;
 use crate::error::ErrorCode;
 
 use anchor_lang::prelude::*;
@@ -6,8 +8,18 @@ use anchor_lang::prelude::*;
 #[derive(Accounts)]
 pub struct UpdateStakeRequest <'info> {
 
+    #[account(
+        has_one=stake_request_account @ ErrorCode::VaultNotMatch
+    )]
     pub vault: Account<'info, Vault>,
 
+    #[account(
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        constraint=!config.is_paused(&vault, PAUSE_STAKE_REQUESTS) @ ErrorCode::Paused
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
     #[account(
         mut, 
         seeds=[
@@ -16,20 +28,69 @@ pub struct UpdateStakeRequest <'info> {
         ],
         bump=user_vault_account.user_account_bump,
         has_one=authority @ ErrorCode::WrongUserAccountAuthority, 
-        has_one=vault @ ErrorCode::VaultNotMatch
+        has_one=vault @ ErrorCode::VaultNotMatch,
+        constraint=user_vault_account.user_stake.stake_request_active==true @ ErrorCode::NoRequestSent,
+        constraint=user_vault_account.user_stake.epoch==vault.epoch.current @ ErrorCode::RequestEpochClosed
     )]
     pub user_vault_account: Account<'info, UserVaultAccount>,
 
+    #[account(
+        mut, 
+        has_one=vault @ ErrorCode::VaultNotMatch,
+    )]
+    pub stake_request_account: AccountLoader<'info, StakeReq>,
+
+    /// CHECK: access entry pda of the user, may not exist
+    #[account(
+        seeds=[
+            vault.key().as_ref(),
+            authority.key().as_ref(),
+            ACCESS_ENTRY_SEED.as_ref()
+        ],
+        bump
+    )]
+    pub access_entry: UncheckedAccount<'info>,
+
     pub authority: Signer<'info>,
 
 }
 
 pub fn handler ( ctx: Context<UpdateStakeRequest>, amount: u64, max: bool, cancel: bool) -> Result<()> {
+    let mut stake_req = ctx.accounts.stake_request_account.load_mut()?;
+    let user_vault_account_key = ctx.accounts.user_vault_account.key();
+
+    // * a cancelled request leaves the queue straight away, denied wallets can only cancel
+    if cancel {
+        stake_req.queue.remove(&user_vault_account_key).ok_or(ErrorCode::RequestNotQueued)?;
+
+        ctx.accounts.user_vault_account.user_stake.stake_request_active = false;
+        ctx.accounts.user_vault_account.user_stake.status = RequestStatus::Cancelled;
+    } else {
+        AccessEntry::check(&ctx.accounts.vault, &ctx.accounts.access_entry, ctx.program_id)?;
+
+        stake_req.queue.update(RequestEntry {
+            user_vault_account: user_vault_account_key,
+            amount,
+            slot: Clock::get()?.slot,
+            timestamp: Clock::get()?.unix_timestamp,
+            epoch: ctx.accounts.vault.epoch.current,
+            cooldown_ends_at: 0,
+            max: max as u8,
+        })?;
+    }
 
     ctx.accounts.user_vault_account.user_stake.stake_amount = amount;
     ctx.accounts.user_vault_account.user_stake.cancel = cancel;
     ctx.accounts.user_vault_account.user_stake.max = max;
-    
+
+    emit!(StakeRequestUpdated {
+        vault: ctx.accounts.vault.key(),
+        user_vault_account: user_vault_account_key,
+        amount,
+        max,
+        cancel,
+        status: ctx.accounts.user_vault_account.user_stake.status,
+    });
 
     Ok(())
-} 
\ No newline at end of file
+}
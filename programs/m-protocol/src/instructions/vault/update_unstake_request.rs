@@ -1,4 +1,6 @@
 use crate::state::*;
+use crate::constants::*;
+use crate::events::*;
 use crate::error::ErrorCode;
 
 use anchor_lang::prelude::*;
@@ -6,8 +8,18 @@ use anchor_lang::prelude::*;
 #[derive(Accounts)]
 pub struct UpdateUnstakeRequest <'info> {
 
+    #[account(
+        has_one=unstake_request_account @ ErrorCode::VaultNotMatch
+    )]
     pub vault: Account<'info, Vault>,
 
+    #[account(
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        constraint=!config.is_paused(&vault, PAUSE_UNSTAKE_REQUESTS) @ ErrorCode::Paused
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
     #[account(
         mut, 
         seeds=[
@@ -16,19 +28,61 @@ pub struct UpdateUnstakeRequest <'info> {
         ],
         bump=user_vault_account.user_account_bump,
         has_one=authority @ ErrorCode::WrongUserAccountAuthority, 
-        has_one=vault @ ErrorCode::VaultNotMatch
+        has_one=vault @ ErrorCode::VaultNotMatch,
+        constraint=user_vault_account.user_unstake.unstake_request_active==true @ ErrorCode::NoRequestSent,
+        constraint=user_vault_account.user_unstake.epoch==vault.epoch.current @ ErrorCode::RequestEpochClosed
     )]
     pub user_vault_account: Account<'info, UserVaultAccount>,
 
+    #[account(
+        mut, 
+        has_one=vault @ ErrorCode::VaultNotMatch,
+    )]
+    pub unstake_request_account: AccountLoader<'info, UnstakeReq>,
+
     pub authority: Signer<'info>,
 
 }
 
 pub fn handler ( ctx: Context<UpdateUnstakeRequest>, amount: u64, max: bool, cancel: bool) -> Result<()> {
+    let mut unstake_req = ctx.accounts.unstake_request_account.load_mut()?;
+    let user_vault_account_key = ctx.accounts.user_vault_account.key();
+    let now = Clock::get()?.unix_timestamp;
+    // * an updated request waits out the cooldown again under the current terms, from the back of the queue
+    let cooldown_ends_at = ctx.accounts.vault.cooldown_ends_at(now);
+
+    unstake_req.queue.remove(&user_vault_account_key).ok_or(ErrorCode::RequestNotQueued)?;
+
+    // * a cancelled request leaves the queue straight away
+    if cancel {
+        ctx.accounts.user_vault_account.user_unstake.unstake_request_active = false;
+        ctx.accounts.user_vault_account.user_unstake.status = RequestStatus::Cancelled;
+    } else {
+        unstake_req.queue.push(RequestEntry {
+            user_vault_account: user_vault_account_key,
+            amount,
+            slot: Clock::get()?.slot,
+            timestamp: now,
+            epoch: ctx.accounts.vault.epoch.current,
+            cooldown_ends_at,
+            max: max as u8,
+        })?;
+    }
 
     ctx.accounts.user_vault_account.user_unstake.unstake_amount = amount;
     ctx.accounts.user_vault_account.user_unstake.cancel = cancel;
     ctx.accounts.user_vault_account.user_unstake.max = max;
+    ctx.accounts.user_vault_account.user_unstake.requested_at = now;
+    ctx.accounts.user_vault_account.user_unstake.cooldown_ends_at = cooldown_ends_at;
+
+    emit!(UnstakeRequestUpdated {
+        vault: ctx.accounts.vault.key(),
+        user_vault_account: user_vault_account_key,
+        amount,
+        max,
+        cancel,
+        status: ctx.accounts.user_vault_account.user_unstake.status,
+    });
 
     Ok(())
-} 
\ No newline at end of file
+}
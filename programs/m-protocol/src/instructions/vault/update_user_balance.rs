@@ -1,18 +1,29 @@
 use crate::state::*;
+use crate::constants::*;
+use crate::events::*;
 use crate::error::ErrorCode;
 
 use anchor_lang::prelude::*;
-// use rust_decimal::prelude::ToPrimitive;
+use anchor_spl::token::{Mint, TokenAccount};
 
+// * user equity is derived from shares, this only refreshes the stored snapshot
 #[derive(Accounts)]
 pub struct UpdateUserBalance<'info> {
     #[account(
-        has_one=manager @ ErrorCode::NotAdmin
+        has_one=keeper @ ErrorCode::NotKeeper,
+        has_one=share_mint @ ErrorCode::WrongMintProvided
     )]
     pub vault: Account<'info, Vault>,
 
     #[account(
-        mut, 
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        constraint=!config.is_paused(&vault, PAUSE_KEEPER) @ ErrorCode::Paused
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(
+        mut,
         has_one=vault @ ErrorCode::VaultNotMatch,
         seeds=[
             user_vault_account.vault.key().as_ref(),
@@ -22,19 +33,33 @@ pub struct UpdateUserBalance<'info> {
     )]
     pub user_vault_account: Account<'info, UserVaultAccount>,
 
-    pub manager: Signer<'info>,
+    pub share_mint: Account<'info, Mint>,
+
+    #[account(
+        token::mint=share_mint,
+        address=user_vault_account.share_token_account @ ErrorCode::TokenAccountNotMatch
+    )]
+    pub user_share_token_account: Account<'info, TokenAccount>,
+
+    pub keeper: Signer<'info>,
 }
 
 impl<'info> UpdateUserBalance<'info> {
-    fn calculate_user_equity(&self) -> u64 {
-        self.user_vault_account.equity
-        .checked_mul(self.vault.total_equity).unwrap() 
-        .checked_div(self.vault.previous_total_equity).unwrap()
+    fn calculate_user_equity(&self) -> Result<u64> {
+        self.vault
+            .calculate_equity(self.user_share_token_account.amount, self.share_mint.supply)
     }
 }
 
 pub fn handler(ctx: Context<UpdateUserBalance>) -> Result<()> {
-    ctx.accounts.user_vault_account.equity = ctx.accounts.calculate_user_equity();
-    ctx.accounts.user_vault_account.user_pnl = ctx.accounts.user_vault_account.calculate_pnl();
+    ctx.accounts.user_vault_account.equity = ctx.accounts.calculate_user_equity()?;
+    ctx.accounts.user_vault_account.user_pnl = ctx.accounts.user_vault_account.calculate_pnl()?;
+
+    emit!(UserBalanceUpdated {
+        vault: ctx.accounts.vault.key(),
+        user_vault_account: ctx.accounts.user_vault_account.key(),
+        equity: ctx.accounts.user_vault_account.equity,
+        user_pnl: ctx.accounts.user_vault_account.user_pnl,
+    });
     Ok(())
 }
@@ -1,19 +1,34 @@
 use crate::state::*;
+use crate::events::*;
 use crate::error::ErrorCode;
 use crate::constants::*;
+use crate::cpi;
 
 use anchor_lang::prelude::*;
-use anchor_spl::token::TokenAccount;
+use anchor_spl::token::{Mint, TokenAccount};
 use mango::state::{MangoAccount, MangoGroup, MangoCache};
 
 #[derive(Accounts)]
 pub struct UpdateVaultBalance<'info> {
     #[account(
         mut, 
-        has_one=manager @ ErrorCode::NotAdmin
+        has_one=keeper @ ErrorCode::NotKeeper,
+        has_one=stake_request_account @ ErrorCode::VaultNotMatch,
+        has_one=unstake_request_account @ ErrorCode::VaultNotMatch
     )]
     pub vault: Account<'info, Vault>,
 
+    #[account(
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        constraint=!config.is_paused(&vault, PAUSE_KEEPER) @ ErrorCode::Paused
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    pub stake_request_account: AccountLoader<'info, StakeReq>,
+
+    pub unstake_request_account: AccountLoader<'info, UnstakeReq>,
+
     #[account(
         token::mint=vault.mint,
         token::authority=vault_authority,
@@ -21,6 +36,11 @@ pub struct UpdateVaultBalance<'info> {
     )]
     pub vault_token_account: Account<'info, TokenAccount>,
 
+    #[account(
+        address=vault.share_mint @ ErrorCode::WrongMintProvided
+    )]
+    pub share_mint: Account<'info, Mint>,
+
      /// CHECK: vault authority
      #[account(
         seeds=[
@@ -31,58 +51,96 @@ pub struct UpdateVaultBalance<'info> {
     )]
     pub vault_authority: AccountInfo<'info>,
 
-    /// CHECK: Mango Account CPI
-    pub mango_account: AccountInfo<'info>,
-    
-    /// CHECK: Mango Account CPI
-    #[account(mut)]
-    pub mango_cache: AccountInfo<'info>,
-    
+    // * the balance sets the share price, so every mango account is pinned to the vault's
     /// CHECK: Mango Account CPI    
+    #[account(
+        address=vault.mango_program_id @ ErrorCode::MangoProgramNotMatch
+    )]
     pub mango_program_id: AccountInfo<'info>,
     
     /// CHECK: Mango Account CPI    
+    #[account(
+        mut,
+        address=vault.mango_group @ ErrorCode::MangoGroupNotMatch
+    )]
     pub mango_group: AccountInfo<'info>,
+
+    /// CHECK: Mango Account CPI
+    #[account(
+        mut,
+        seeds=[
+            mango_group.key().as_ref(),
+            vault_authority.key().as_ref(),
+            &vault.mango_account_num.to_le_bytes()
+        ],
+        bump=vault.mango_account_bump,
+        seeds::program=mango_program_id.key()
+    )]
+    pub mango_account: AccountInfo<'info>,
+    
+    /// CHECK: Mango Account CPI, checked against the group on load
+    #[account(mut)]
+    pub mango_cache: AccountInfo<'info>,
    
     /// CHECK: Mango Account CPI    
     pub mango_root_bank: AccountInfo<'info>,
 
-    pub manager: Signer<'info>,
+    pub keeper: Signer<'info>,
+
+    // * the default key mango clears the delegate with when the vault halts
+    pub system_program: Program<'info, System>,
 }
 
 
 impl<'info> UpdateVaultBalance<'info> {
 
-    pub fn get_mango_balance (&self) -> u64 {
+    pub fn get_mango_balance (&self) -> Result<u64> {
         let mango_group = MangoGroup::load_checked(
             &self.mango_group, 
             self.mango_program_id.key
-        ).unwrap();
+        ).map_err(|_| error!(ErrorCode::MangoGroupLoadFailed))?;
 
-        // let acct = &self.mango_account;
         let mango_account =
-            MangoAccount::load_mut_checked(
+            MangoAccount::load_checked(
                 &self.mango_account, 
                 self.mango_program_id.key, 
                 &self.mango_group.key
-            ).unwrap();
+            ).map_err(|_| error!(ErrorCode::MangoAccountLoadFailed))?;
         
         let token_index = mango_group
-            .find_root_bank_index(self.mango_root_bank.key).unwrap(); // todo: change this error
-
-        // let group = &mango_group;
+            .find_root_bank_index(self.mango_root_bank.key)
+            .ok_or(ErrorCode::RootBankNotFound)?;
 
         let mango_cache = MangoCache::load_checked(
             &self.mango_cache, 
             self.mango_program_id.key, 
             &mango_group
-        ).unwrap();
+        ).map_err(|_| error!(ErrorCode::MangoCacheLoadFailed))?;
 
         let root_bank_cache = &mango_cache.root_bank_cache[token_index];
 
-        let native_deposit = mango_account.get_native_deposit(root_bank_cache, token_index).unwrap();
+        let native_deposit = mango_account
+            .get_native_deposit(root_bank_cache, token_index)
+            .map_err(|_| error!(ErrorCode::MathOverflow))?;
+
+        native_deposit
+            .checked_floor()
+            .and_then(|deposit| deposit.checked_to_num::<u64>())
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))
+    }
+
+    fn revoke_mango_delegate_context(&self) -> CpiContext<'_, '_, '_, 'info, cpi::SetDelegate<'info>> {
+
+        let cpi_accounts = cpi::SetDelegate {
+            mango_account: self.mango_account.to_account_info().clone(),
+            mango_group: self.mango_group.to_account_info().clone(),
+            mango_program_id: self.mango_program_id.to_account_info().clone(),
+            owner: self.vault_authority.to_account_info().clone(),
+            delegate_pubkey: self.system_program.to_account_info().clone()
+        };
+        let cpi_program = self.mango_program_id.to_account_info();
 
-        native_deposit.checked_floor().unwrap().to_num::<u64>()
+        CpiContext::new(cpi_program, cpi_accounts)
 
     }
 }
@@ -91,15 +149,111 @@ pub fn handler(
     ctx: Context<UpdateVaultBalance>, 
     // new_balance: u64
 ) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    // * every settleable request must settle before the next epoch closes, wherever it sits in the queue,
+    // * unstakes still cooling down settle at a later epoch
+    let current_epoch = ctx.accounts.vault.epoch.current;
+    if ctx.accounts.stake_request_account.load()?.queue.iter().any(|entry| entry.epoch < current_epoch)
+        || ctx.accounts.unstake_request_account.load()?.queue.iter().any(|entry| {
+            entry.epoch < current_epoch && Vault::cooldown_remaining(entry.cooldown_ends_at, now) == 0
+        }) {
+        return err!(ErrorCode::UnsettledRequests)
+    }
+
     ctx.accounts.vault.previous_total_equity = ctx.accounts.vault.total_equity;
     
-    let new_balance = ctx.accounts.get_mango_balance();
-    
-    // todo: operating costs will be debited here
+    // * uncollected fees and insurance penalties are still held on mango but no longer belong to stakers
+    let gross_balance = ctx.accounts.get_mango_balance()?
+        .saturating_sub(ctx.accounts.vault.accrued_fees)
+        .saturating_sub(ctx.accounts.vault.accrued_insurance);
+
+    let share_supply = ctx.accounts.share_mint.supply;
+
+    let management_fee = ctx.accounts.vault.calculate_management_fee(gross_balance, now)?;
+    let balance_after_management_fee = gross_balance.checked_sub(management_fee).ok_or(ErrorCode::MathOverflow)?;
+
+    let performance_fee = ctx.accounts.vault.calculate_performance_fee(balance_after_management_fee, share_supply)?;
+    let new_balance = balance_after_management_fee.checked_sub(performance_fee).ok_or(ErrorCode::MathOverflow)?;
+
+    let share_price = Vault::calculate_share_price(new_balance, share_supply)?;
+    if share_supply > 0 && share_price > ctx.accounts.vault.high_water_mark {
+        ctx.accounts.vault.high_water_mark = share_price;
+    }
+    if share_price > ctx.accounts.vault.peak_share_price {
+        ctx.accounts.vault.peak_share_price = share_price;
+    }
+
+    // * the insurance slice of the performance fee is collected into the insurance fund instead
+    let insurance_share = ctx.accounts.vault.calculate_insurance_share(performance_fee)?;
+    ctx.accounts.vault.accrued_fees = ctx.accounts.vault.accrued_fees
+        .checked_add(management_fee).ok_or(ErrorCode::MathOverflow)?
+        .checked_add(performance_fee - insurance_share).ok_or(ErrorCode::MathOverflow)?;
+    ctx.accounts.vault.accrued_insurance = ctx.accounts.vault.accrued_insurance
+        .checked_add(insurance_share).ok_or(ErrorCode::MathOverflow)?;
+    ctx.accounts.vault.last_fee_timestamp = now;
     
     ctx.accounts.vault.total_equity = new_balance;
     ctx.accounts.vault.total_equity_before_settlements = new_balance;
     
-    ctx.accounts.vault.day_pnl = ctx.accounts.vault.calculate_pnl_ratio();
+    ctx.accounts.vault.day_pnl = ctx.accounts.vault.calculate_pnl_ratio()?;
+
+    // * a large enough loss lets the admin inject the insurance fund into mango, until the next epoch closes without one
+    if ctx.accounts.vault.insurance_trigger_bps > 0
+        && ctx.accounts.vault.daily_loss_exceeds(ctx.accounts.vault.insurance_trigger_bps)? {
+        ctx.accounts.vault.insurance_injection_allowed = true;
+
+        emit!(InsuranceInjectionUnlocked {
+            vault: ctx.accounts.vault.key(),
+            day_pnl: ctx.accounts.vault.day_pnl,
+            insurance_balance: ctx.accounts.vault.insurance_balance()?,
+        });
+    } else {
+        ctx.accounts.vault.insurance_injection_allowed = false;
+    }
+
+    // * a halted vault stays halted until the admin clears it, a delegate would keep trading through the halt
+    if !ctx.accounts.vault.is_halted() {
+        if let Some(reason) = ctx.accounts.vault.check_drawdown(share_price)? {
+            ctx.accounts.vault.status = VaultStatus::Halted;
+            ctx.accounts.vault.halt_reason = reason;
+            ctx.accounts.vault.halted_at = now;
+
+            cpi::mango_cpi::set_delegate(ctx.accounts.revoke_mango_delegate_context().with_signer(
+                &[&[
+                    ctx.accounts.vault.key().as_ref(),
+                    VAULT_PDA_AUTHORITY_SEED.as_ref(),
+                    &[ctx.accounts.vault.vault_authority_bump],
+                ]],
+            ))?;
+
+            emit!(VaultHalted {
+                vault: ctx.accounts.vault.key(),
+                reason,
+                day_pnl: ctx.accounts.vault.day_pnl,
+                share_price,
+                peak_share_price: ctx.accounts.vault.peak_share_price,
+            });
+        }
+    }
+
+    // * close the epoch, its requests settle at this share price
+    ctx.accounts.vault.epoch = Epoch {
+        current: current_epoch.checked_add(1).ok_or(ErrorCode::MathOverflow)?,
+        started_at: now,
+        settlement_price: share_price,
+    };
+
+    emit!(VaultBalanceUpdated {
+        vault: ctx.accounts.vault.key(),
+        previous_total_equity: ctx.accounts.vault.previous_total_equity,
+        total_equity: new_balance,
+        day_pnl: ctx.accounts.vault.day_pnl,
+        management_fee,
+        performance_fee,
+        closed_epoch: current_epoch,
+        settlement_price: share_price,
+        insurance_balance: ctx.accounts.vault.insurance_balance()?,
+    });
     Ok(())
 }
@@ -0,0 +1,48 @@
+use crate::state::*;
+use crate::events::*;
+use crate::error::ErrorCode;
+use crate::constants::*;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateVaultFees<'info> {
+    #[account(
+        mut, 
+        has_one=admin @ ErrorCode::NotAdmin,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateVaultFees>,
+    management_fee_bps: u16,
+    performance_fee_bps: u16,
+    fee_recipient: Pubkey,
+) -> Result<()> {
+    if management_fee_bps > MAX_MANAGEMENT_FEE_BPS || performance_fee_bps > MAX_PERFORMANCE_FEE_BPS {
+        return err!(ErrorCode::InvalidFeeRate)
+    }
+
+    // * fees earned so far accrue at the old terms, a balance update in the same transaction settles them
+    if ctx.accounts.vault.last_fee_timestamp != Clock::get()?.unix_timestamp {
+        return err!(ErrorCode::FeesNotAccrued)
+    }
+    if fee_recipient != ctx.accounts.vault.fee_recipient && ctx.accounts.vault.accrued_fees > 0 {
+        return err!(ErrorCode::FeesNotCollected)
+    }
+
+    ctx.accounts.vault.management_fee_bps = management_fee_bps;
+    ctx.accounts.vault.performance_fee_bps = performance_fee_bps;
+    ctx.accounts.vault.fee_recipient = fee_recipient;
+
+    emit!(VaultFeesUpdated {
+        vault: ctx.accounts.vault.key(),
+        management_fee_bps,
+        performance_fee_bps,
+        fee_recipient,
+    });
+    Ok(())
+}
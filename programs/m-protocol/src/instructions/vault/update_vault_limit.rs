@@ -1,4 +1,5 @@
 use crate::state::*;
+use crate::events::*;
 use crate::error::ErrorCode;
 
 use anchor_lang::prelude::*;
@@ -7,14 +8,21 @@ use anchor_lang::prelude::*;
 pub struct UpdateVaultLimit<'info> {
     #[account(
         mut, 
-        has_one=manager @ ErrorCode::NotAdmin,
+        has_one=admin @ ErrorCode::NotAdmin,
     )]
     pub vault: Account<'info, Vault>,
 
-    pub manager: Signer<'info>,
+    pub admin: Signer<'info>,
 }
 
 pub fn handler(ctx: Context<UpdateVaultLimit>, new_limit: u64) -> Result<()> {
+    let old_limit = ctx.accounts.vault.limit;
     ctx.accounts.vault.limit = new_limit;
+
+    emit!(VaultLimitUpdated {
+        vault: ctx.accounts.vault.key(),
+        old_limit,
+        new_limit,
+    });
     Ok(())
 }
@@ -0,0 +1,40 @@
+use crate::state::*;
+use crate::events::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateWithdrawalPenalty<'info> {
+    #[account(
+        mut, 
+        has_one=admin @ ErrorCode::NotAdmin,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateWithdrawalPenalty>,
+    early_withdrawal_penalty_bps: u16,
+    penalty_period_seconds: i64,
+    penalty_recipient: PenaltyRecipient,
+) -> Result<()> {
+    if early_withdrawal_penalty_bps > MAX_EARLY_WITHDRAWAL_PENALTY_BPS || penalty_period_seconds < 0 {
+        return err!(ErrorCode::InvalidPenalty)
+    }
+
+    ctx.accounts.vault.early_withdrawal_penalty_bps = early_withdrawal_penalty_bps;
+    ctx.accounts.vault.penalty_period_seconds = penalty_period_seconds;
+    ctx.accounts.vault.penalty_recipient = penalty_recipient;
+
+    emit!(WithdrawalPenaltyUpdated {
+        vault: ctx.accounts.vault.key(),
+        early_withdrawal_penalty_bps,
+        penalty_period_seconds,
+        penalty_recipient,
+    });
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use crate::state::*;
+use crate::events::*;
 use crate::error::ErrorCode;
 use crate::constants::*;
 
@@ -9,6 +10,13 @@ use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 pub struct WithdrawFromUserVaultAccount<'info> {
     pub vault: Account<'info, Vault>,
 
+    #[account(
+        seeds=[CONFIG_SEED.as_ref()],
+        bump=config.bump,
+        constraint=!config.is_paused(&vault, PAUSE_WITHDRAWALS) @ ErrorCode::Paused
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
     #[account(
         mut,
         seeds=[
@@ -89,6 +97,14 @@ pub fn handler (
         amount,
     )?;
 
-    ctx.accounts.user_vault_account.withdrawal = ctx.accounts.user_vault_account.withdrawal.checked_add(amount).unwrap();
+    ctx.accounts.user_vault_account.withdrawal = ctx.accounts.user_vault_account.withdrawal.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+
+    emit!(UserWithdrew {
+        vault: ctx.accounts.vault.key(),
+        user_vault_account: ctx.accounts.user_vault_account.key(),
+        authority: ctx.accounts.user_vault_account.authority,
+        amount,
+        withdrawal: ctx.accounts.user_vault_account.withdrawal,
+    });
     Ok(())
 }
\ No newline at end of file
@@ -4,9 +4,11 @@ pub mod instructions;
 pub mod state;
 pub mod error;
 pub mod constants;
+pub mod events;
 
 // pub use state::*;
 // use error::*;
+pub use state::{VaultRole, OrderSide, PerpOrderType, SpotOrderType, AccessMode, PenaltyRecipient};
 pub use instructions::*;
 
 declare_id!("8ifV5e1VBx8fYEA6tBBfBFYNme8u7AtwqvkrVxjqTPCR"); // devnet
@@ -18,6 +20,38 @@ pub mod m_protocol {
 
     use super::*;
 
+    pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
+        instructions::initialize_config::handler(ctx)
+    }
+
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+        instructions::propose_admin::handler(ctx, new_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        instructions::accept_admin::handler(ctx)
+    }
+
+    pub fn add_vault_creator(ctx: Context<AddVaultCreator>, creator: Pubkey) -> Result<()> {
+        instructions::add_vault_creator::handler(ctx, creator)
+    }
+
+    pub fn remove_vault_creator(ctx: Context<RemoveVaultCreator>, creator: Pubkey) -> Result<()> {
+        instructions::remove_vault_creator::handler(ctx, creator)
+    }
+
+    pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<()> {
+        instructions::set_guardian::handler(ctx, guardian)
+    }
+
+    pub fn pause_protocol(ctx: Context<PauseProtocol>, flags: u8) -> Result<()> {
+        instructions::pause_protocol::handler(ctx, flags)
+    }
+
+    pub fn unpause_protocol(ctx: Context<UnpauseProtocol>, flags: u8) -> Result<()> {
+        instructions::unpause_protocol::handler(ctx, flags)
+    }
+
     pub fn create_vault(ctx: Context<CreateVault>, name: String, limit: u64, vault_bump: u8, vault_pda_bump: u8) -> Result<()> {
         instructions::create_vault::handler(ctx, name, limit, vault_bump, vault_pda_bump)
     }
@@ -62,12 +96,24 @@ pub mod m_protocol {
         instructions::process_stake::handler(ctx)
     }
     
-    pub fn process_unstake(
-        ctx: Context<ProcessUnstake>,
+    pub fn process_unstake<'info> (
+        ctx: Context<'_, '_, '_, 'info, ProcessUnstake<'info>>,
     ) -> Result<()> {
         instructions::process_unstake::handler(ctx)
     }
     
+    pub fn process_stake_batch<'info> (
+        ctx: Context<'_, '_, '_, 'info, ProcessStakeBatch<'info>>,
+    ) -> Result<()> {
+        instructions::process_stake_batch::handler(ctx)
+    }
+    
+    pub fn process_unstake_batch<'info> (
+        ctx: Context<'_, '_, '_, 'info, ProcessUnstakeBatch<'info>>,
+    ) -> Result<()> {
+        instructions::process_unstake_batch::handler(ctx)
+    }
+    
     pub fn update_user_balance (
         ctx: Context<UpdateUserBalance>,
     ) -> Result<()> {
@@ -107,18 +153,154 @@ pub mod m_protocol {
         instructions::update_unstake_request::handler(ctx, amount, max, cancel)
     }
     
-    pub fn clear_stake_request (
-        ctx: Context<ClearStakeRequest>,
+    pub fn clear_stake_request<'info> (
+        ctx: Context<'_, '_, '_, 'info, ClearStakeRequest<'info>>,
     ) -> Result<()> {
         instructions::clear_stake_request::handler(ctx)
     }
     
-    pub fn clear_unstake_request (
-        ctx: Context<ClearUnstakeRequest>,
+    pub fn clear_unstake_request<'info> (
+        ctx: Context<'_, '_, '_, 'info, ClearUnstakeRequest<'info>>,
     ) -> Result<()> {
         instructions::clear_unstake_request::handler(ctx)
     }
     
+    pub fn set_vault_role (
+        ctx: Context<SetVaultRole>,
+        role: VaultRole,
+        new_key: Pubkey,
+    ) -> Result<()> {
+        instructions::set_vault_role::handler(ctx, role, new_key)
+    }
+    
+    pub fn update_vault_fees (
+        ctx: Context<UpdateVaultFees>,
+        management_fee_bps: u16,
+        performance_fee_bps: u16,
+        fee_recipient: Pubkey,
+    ) -> Result<()> {
+        instructions::update_vault_fees::handler(ctx, management_fee_bps, performance_fee_bps, fee_recipient)
+    }
+    
+    pub fn collect_fees<'info> (
+        ctx: Context<'_, '_, '_, 'info, CollectFees<'info>>,
+    ) -> Result<()> {
+        instructions::collect_fees::handler(ctx)
+    }
+    
+    pub fn initialize_risk_params (
+        ctx: Context<InitializeRiskParams>,
+        allowed_markets: u16,
+        max_leverage_bps: u64,
+        min_health_ratio_bps: u64,
+    ) -> Result<()> {
+        instructions::initialize_risk_params::handler(ctx, allowed_markets, max_leverage_bps, min_health_ratio_bps)
+    }
+    
+    pub fn set_market_risk (
+        ctx: Context<SetMarketRisk>,
+        market_index: u64,
+        allowed: bool,
+        max_notional: u64,
+    ) -> Result<()> {
+        instructions::set_market_risk::handler(ctx, market_index, allowed, max_notional)
+    }
+    
+    pub fn update_risk_limits (
+        ctx: Context<UpdateRiskLimits>,
+        max_leverage_bps: u64,
+        min_health_ratio_bps: u64,
+    ) -> Result<()> {
+        instructions::update_risk_limits::handler(ctx, max_leverage_bps, min_health_ratio_bps)
+    }
+    
+    pub fn update_drawdown_limits (
+        ctx: Context<UpdateDrawdownLimits>,
+        max_daily_drawdown_bps: u16,
+        max_drawdown_bps: u16,
+    ) -> Result<()> {
+        instructions::update_drawdown_limits::handler(ctx, max_daily_drawdown_bps, max_drawdown_bps)
+    }
+    
+    pub fn clear_halt (ctx: Context<ClearHalt>) -> Result<()> {
+        instructions::clear_halt::handler(ctx)
+    }
+    
+    pub fn pause_vault (ctx: Context<PauseVault>, flags: u8) -> Result<()> {
+        instructions::pause_vault::handler(ctx, flags)
+    }
+    
+    pub fn unpause_vault (ctx: Context<UnpauseVault>, flags: u8) -> Result<()> {
+        instructions::unpause_vault::handler(ctx, flags)
+    }
+    
+    pub fn set_gatekeeper_network (
+        ctx: Context<SetGatekeeperNetwork>,
+        gatekeeper_network: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_gatekeeper_network::handler(ctx, gatekeeper_network)
+    }
+    
+    pub fn set_access_mode (ctx: Context<SetAccessMode>, access_mode: AccessMode) -> Result<()> {
+        instructions::set_access_mode::handler(ctx, access_mode)
+    }
+    
+    pub fn add_access_entry (
+        ctx: Context<AddAccessEntry>,
+        wallet: Pubkey,
+        allowed: bool,
+        denied: bool,
+    ) -> Result<()> {
+        instructions::add_access_entry::handler(ctx, wallet, allowed, denied)
+    }
+    
+    pub fn update_access_entry (ctx: Context<UpdateAccessEntry>, allowed: bool, denied: bool) -> Result<()> {
+        instructions::update_access_entry::handler(ctx, allowed, denied)
+    }
+    
+    pub fn remove_access_entry (ctx: Context<RemoveAccessEntry>) -> Result<()> {
+        instructions::remove_access_entry::handler(ctx)
+    }
+    
+    pub fn update_lockup (
+        ctx: Context<UpdateLockup>,
+        lockup_seconds: i64,
+        unstake_cooldown_seconds: i64,
+    ) -> Result<()> {
+        instructions::update_lockup::handler(ctx, lockup_seconds, unstake_cooldown_seconds)
+    }
+    
+    pub fn update_withdrawal_penalty (
+        ctx: Context<UpdateWithdrawalPenalty>,
+        early_withdrawal_penalty_bps: u16,
+        penalty_period_seconds: i64,
+        penalty_recipient: PenaltyRecipient,
+    ) -> Result<()> {
+        instructions::update_withdrawal_penalty::handler(ctx, early_withdrawal_penalty_bps, penalty_period_seconds, penalty_recipient)
+    }
+    
+    pub fn create_insurance_fund (ctx: Context<CreateInsuranceFund>) -> Result<()> {
+        instructions::create_insurance_fund::handler(ctx)
+    }
+    
+    pub fn update_insurance_params (
+        ctx: Context<UpdateInsuranceParams>,
+        insurance_fee_bps: u16,
+        insurance_trigger_bps: u16,
+    ) -> Result<()> {
+        instructions::update_insurance_params::handler(ctx, insurance_fee_bps, insurance_trigger_bps)
+    }
+    
+    pub fn collect_insurance<'info> (
+        ctx: Context<'_, '_, '_, 'info, CollectInsurance<'info>>,
+    ) -> Result<()> {
+        instructions::collect_insurance::handler(ctx)
+    }
+    
+    pub fn inject_insurance (ctx: Context<InjectInsurance>, amount: u64) -> Result<()> {
+        instructions::inject_insurance::handler(ctx, amount)
+    }
+    
     pub fn create_mango_account (
         ctx: Context<CreateAccountOnMango>,
         account_num: u64,
@@ -140,8 +322,8 @@ pub mod m_protocol {
         instructions::deposit_to_mango::handler(ctx, amount)
     }
     
-    pub fn withdraw_from_mango (
-        ctx: Context<WithdrawFromMango>,
+    pub fn withdraw_from_mango<'info> (
+        ctx: Context<'_, '_, '_, 'info, WithdrawFromMango<'info>>,
         amount: u64,
     ) -> Result<()> {
         instructions::withdraw_from_mango::handler(ctx, amount)
@@ -152,4 +334,105 @@ pub mod m_protocol {
     ) -> Result<()> {
         instructions::delegate_mango_account::handler(ctx)
     }
+
+    pub fn revoke_mango_delegate (
+        ctx: Context<RevokeMangoDelegate>,
+    ) -> Result<()> {
+        instructions::revoke_mango_delegate::handler(ctx)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn place_perp_order<'info> (
+        ctx: Context<'_, '_, '_, 'info, PlacePerpOrderOnMango<'info>>,
+        side: OrderSide,
+        price: i64,
+        max_base_quantity: i64,
+        max_quote_quantity: i64,
+        client_order_id: u64,
+        order_type: PerpOrderType,
+        reduce_only: bool,
+        expiry_timestamp: Option<u64>,
+        limit: u8,
+    ) -> Result<()> {
+        instructions::place_perp_order::handler(
+            ctx,
+            side,
+            price,
+            max_base_quantity,
+            max_quote_quantity,
+            client_order_id,
+            order_type,
+            reduce_only,
+            expiry_timestamp,
+            limit,
+        )
+    }
+
+    pub fn cancel_perp_order (
+        ctx: Context<CancelPerpOrderOnMango>,
+        order_id: i128,
+        invalid_id_ok: bool,
+    ) -> Result<()> {
+        instructions::cancel_perp_order::handler(ctx, order_id, invalid_id_ok)
+    }
+
+    pub fn cancel_all_perp_orders (
+        ctx: Context<CancelAllPerpOrdersOnMango>,
+        limit: u8,
+    ) -> Result<()> {
+        instructions::cancel_all_perp_orders::handler(ctx, limit)
+    }
+
+    pub fn settle_pnl (
+        ctx: Context<SettlePnlOnMango>,
+        market_index: u64,
+    ) -> Result<()> {
+        instructions::settle_pnl::handler(ctx, market_index)
+    }
+
+    pub fn create_spot_open_orders (
+        ctx: Context<CreateSpotOpenOrdersOnMango>,
+        market_index: u64,
+    ) -> Result<()> {
+        instructions::create_spot_open_orders::handler(ctx, market_index)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn place_spot_order<'info> (
+        ctx: Context<'_, '_, '_, 'info, PlaceSpotOrderOnMango<'info>>,
+        side: OrderSide,
+        limit_price: u64,
+        max_coin_qty: u64,
+        max_native_pc_qty_including_fees: u64,
+        order_type: SpotOrderType,
+        client_order_id: u64,
+        limit: u16,
+        market_index: u64,
+    ) -> Result<()> {
+        instructions::place_spot_order::handler(
+            ctx,
+            side,
+            limit_price,
+            max_coin_qty,
+            max_native_pc_qty_including_fees,
+            order_type,
+            client_order_id,
+            limit,
+            market_index,
+        )
+    }
+
+    pub fn cancel_spot_order (
+        ctx: Context<CancelSpotOrderOnMango>,
+        side: OrderSide,
+        order_id: u128,
+    ) -> Result<()> {
+        instructions::cancel_spot_order::handler(ctx, side, order_id)
+    }
+
+    pub fn settle_funds (
+        ctx: Context<SettleFundsOnMango>,
+    ) -> Result<()> {
+        instructions::settle_funds::handler(ctx)
+    }
 }
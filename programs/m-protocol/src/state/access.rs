@@ -0,0 +1,46 @@
+use crate::error::ErrorCode;
+use crate::state::Vault;
+
+use anchor_lang::prelude::*;
+
+// * one entry per vault and wallet, holds both the allowlist and the denylist flags
+#[account]
+pub struct AccessEntry {
+    pub vault: Pubkey, // 32
+    pub wallet: Pubkey, // 32
+    pub allowed: bool, // 1 // * counted only when the vault is in allowlist mode
+    pub denied: bool, // 1  // * blocks onboarding and new stakes, unstakes still go through
+    pub bump: u8, // 1
+}
+
+impl AccessEntry {
+    // * `entry` is the wallet's access entry pda, which may not have been created
+    pub fn check(vault: &Vault, entry: &AccountInfo, program_id: &Pubkey) -> Result<()> {
+        let access_entry = if entry.owner == program_id && !entry.data_is_empty() {
+            Some(AccessEntry::try_deserialize(&mut &entry.try_borrow_data()?[..])?)
+        } else {
+            None
+        };
+
+        if access_entry.as_ref().map_or(false, |access_entry| access_entry.denied) {
+            return err!(ErrorCode::WalletDenied);
+        }
+        if vault.access_mode == AccessMode::Allowlist
+            && !access_entry.as_ref().map_or(false, |access_entry| access_entry.allowed) {
+            return err!(ErrorCode::WalletNotAllowed);
+        }
+        Ok(())
+    }
+
+    pub const LEN: usize = (
+        (32 * 2) + // vault and wallet
+        (1 * 2) + // flags
+        1 // bump
+    );
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum AccessMode {
+    Open,
+    Allowlist,
+}
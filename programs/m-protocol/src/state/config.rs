@@ -0,0 +1,34 @@
+use crate::constants::*;
+use crate::state::Vault;
+
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct ProtocolConfig {
+    pub admin: Pubkey, // 32
+    pub pending_admin: Option<Pubkey>, // 1 + 32
+    pub creators: Vec<Pubkey>, // 4 + 32 * MAX_VAULT_CREATORS
+    pub bump: u8, // 1
+    pub guardian: Pubkey, // 32  // * can pause, only the admin unpauses
+    pub paused: u8, // 1         // * PAUSE_* bits applied to every vault
+}
+
+impl ProtocolConfig {
+    pub fn is_creator(&self, key: &Pubkey) -> bool {
+        self.creators.contains(key)
+    }
+
+    // * an operation is paused when either the protocol or the vault has its bit set
+    pub fn is_paused(&self, vault: &Vault, flag: u8) -> bool {
+        (self.paused | vault.paused) & flag != 0
+    }
+
+    pub const LEN: usize = (
+        32 + // admin
+        (1 + 32) + // pending_admin
+        (4 + 32 * MAX_VAULT_CREATORS) + // creators
+        1 + // bump
+        32 + // guardian
+        1 // paused
+    );
+}
@@ -1,3 +1,13 @@
 pub mod vault;
+pub mod config;
+pub mod queue;
+pub mod order;
+pub mod risk;
+pub mod access;
 
-pub use vault::*;
\ No newline at end of file
+pub use vault::*;
+pub use config::*;
+pub use queue::*;
+pub use order::*;
+pub use risk::*;
+pub use access::*;
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use mango::matching::{OrderType, Side};
+use serum_dex::matching::{OrderType as SpotOrderKind, Side as SpotSide};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum OrderSide {
+    Bid,
+    Ask,
+}
+
+impl From<OrderSide> for Side {
+    fn from(side: OrderSide) -> Self {
+        match side {
+            OrderSide::Bid => Side::Bid,
+            OrderSide::Ask => Side::Ask,
+        }
+    }
+}
+
+impl From<OrderSide> for SpotSide {
+    fn from(side: OrderSide) -> Self {
+        match side {
+            OrderSide::Bid => SpotSide::Bid,
+            OrderSide::Ask => SpotSide::Ask,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum PerpOrderType {
+    Limit,
+    ImmediateOrCancel,
+    PostOnly,
+    Market,
+    PostOnlySlide,
+}
+
+impl From<PerpOrderType> for OrderType {
+    fn from(order_type: PerpOrderType) -> Self {
+        match order_type {
+            PerpOrderType::Limit => OrderType::Limit,
+            PerpOrderType::ImmediateOrCancel => OrderType::ImmediateOrCancel,
+            PerpOrderType::PostOnly => OrderType::PostOnly,
+            PerpOrderType::Market => OrderType::Market,
+            PerpOrderType::PostOnlySlide => OrderType::PostOnlySlide,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum SpotOrderType {
+    Limit,
+    ImmediateOrCancel,
+    PostOnly,
+}
+
+impl From<SpotOrderType> for SpotOrderKind {
+    fn from(order_type: SpotOrderType) -> Self {
+        match order_type {
+            SpotOrderType::Limit => SpotOrderKind::Limit,
+            SpotOrderType::ImmediateOrCancel => SpotOrderKind::ImmediateOrCancel,
+            SpotOrderType::PostOnly => SpotOrderKind::PostOnly,
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use crate::constants::*;
+use crate::error::ErrorCode;
+
+use anchor_lang::prelude::*;
+
+#[zero_copy]
+pub struct RequestEntry {
+    pub user_vault_account: Pubkey, // 32
+    pub amount: u64, // 8
+    pub slot: u64, // 8  // * slot the request was made or last updated
+    pub timestamp: i64, // 8 // * unix time the request was made or last updated
+    pub epoch: u64, // 8 // * vault epoch the request settles after
+    pub cooldown_ends_at: i64, // 8 // * unix time an unstake can settle from, 0 for stakes
+    pub max: u8, // 1   // * 1 when the whole balance is requested
+}
+
+// * fixed capacity ring buffer, entries live in [head, head + count)
+#[zero_copy]
+pub struct RequestQueue {
+    pub head: u32, // 4
+    pub tail: u32, // 4
+    pub count: u32, // 4
+    pub entries: [RequestEntry; MAX_REQUESTS], // 73 * 1000
+}
+
+impl RequestQueue {
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.count as usize >= MAX_REQUESTS
+    }
+
+    pub fn len(&self) -> u32 {
+        self.count
+    }
+
+    // * entry at `index` positions from the head
+    pub fn get(&self, index: u32) -> Option<RequestEntry> {
+        if index >= self.count {
+            return None;
+        }
+        Some(self.entries[self.position(index)])
+    }
+
+    pub fn push(&mut self, entry: RequestEntry) -> Result<()> {
+        if self.is_full() {
+            return err!(ErrorCode::QueueFull);
+        }
+        self.entries[self.tail as usize] = entry;
+        self.tail = ((self.tail as usize + 1) % MAX_REQUESTS) as u32;
+        self.count += 1;
+        Ok(())
+    }
+
+    pub fn pop(&mut self) -> Option<RequestEntry> {
+        if self.is_empty() {
+            return None;
+        }
+        let entry = self.entries[self.head as usize];
+        self.entries[self.head as usize] = RequestEntry::default();
+        self.head = ((self.head as usize + 1) % MAX_REQUESTS) as u32;
+        self.count -= 1;
+        Some(entry)
+    }
+
+    // * queued entries from the head
+    pub fn iter(&self) -> impl Iterator<Item = RequestEntry> + '_ {
+        (0..self.count).map(move |index| self.entries[self.position(index)])
+    }
+
+    pub fn find(&self, user_vault_account: &Pubkey) -> Option<u32> {
+        (0..self.count).find(|index| self.entries[self.position(*index)].user_vault_account == *user_vault_account)
+    }
+
+    // * replaces the queued entry of `user_vault_account` without changing its position
+    pub fn update(&mut self, entry: RequestEntry) -> Result<()> {
+        let index = self
+            .find(&entry.user_vault_account)
+            .ok_or(ErrorCode::RequestNotQueued)?;
+        let position = self.position(index);
+        self.entries[position] = entry;
+        Ok(())
+    }
+
+    // * removes the entry of `user_vault_account` and closes the gap behind it
+    pub fn remove(&mut self, user_vault_account: &Pubkey) -> Option<RequestEntry> {
+        let index = self.find(user_vault_account)?;
+        let entry = self.entries[self.position(index)];
+        for i in index..self.count - 1 {
+            let (to, from) = (self.position(i), self.position(i + 1));
+            self.entries[to] = self.entries[from];
+        }
+        self.tail = ((self.tail as usize + MAX_REQUESTS - 1) % MAX_REQUESTS) as u32;
+        self.entries[self.tail as usize] = RequestEntry::default();
+        self.count -= 1;
+        Some(entry)
+    }
+
+    fn position(&self, index: u32) -> usize {
+        (self.head as usize + index as usize) % MAX_REQUESTS
+    }
+}
+
+impl Default for RequestEntry {
+    fn default() -> Self {
+        RequestEntry {
+            user_vault_account: Pubkey::default(),
+            amount: 0,
+            slot: 0,
+            timestamp: 0,
+            epoch: 0,
+            cooldown_ends_at: 0,
+            max: 0,
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use crate::constants::*;
+use crate::error::ErrorCode;
+
+use anchor_lang::prelude::*;
+use fixed::types::I80F48;
+use mango::state::{load_open_orders, MangoAccount, MangoCache, MangoGroup, MAX_PAIRS, QUOTE_INDEX};
+
+#[account]
+pub struct RiskParams {
+    pub vault: Pubkey, // 32
+    pub allowed_markets: u16, // 2              // * bit per mango market index
+    pub max_notional: [u64; MAX_PAIRS], // 8 * 15 // * native quote, per mango market index
+    pub max_leverage_bps: u64, // 8             // * gross notional over equity
+    pub min_health_ratio_bps: u64, // 8         // * init health ratio, 10_000 when assets are twice the liabilities
+    pub bump: u8, // 1
+}
+
+impl RiskParams {
+    pub fn is_market_allowed(&self, market_index: usize) -> bool {
+        market_index < MAX_PAIRS && self.allowed_markets & (1 << market_index) != 0
+    }
+
+    pub fn set_market(&mut self, market_index: usize, allowed: bool, max_notional: u64) {
+        if allowed {
+            self.allowed_markets |= 1 << market_index;
+        } else {
+            self.allowed_markets &= !(1 << market_index);
+        }
+        self.max_notional[market_index] = max_notional;
+    }
+
+    // * checked before an order reaches mango, assuming it fills in full
+    pub fn check_order(&self, exposure: &MangoExposure, market_index: usize, order_notional: I80F48) -> Result<()> {
+        if !self.is_market_allowed(market_index) {
+            return err!(ErrorCode::MarketNotAllowed);
+        }
+
+        let market_notional = checked_add(exposure.market_notional[market_index], order_notional)?;
+        if market_notional > I80F48::from_num(self.max_notional[market_index]) {
+            return err!(ErrorCode::MaxNotionalExceeded);
+        }
+
+        let gross_notional = checked_add(exposure.gross_notional, order_notional)?;
+        if gross_notional > I80F48::ZERO {
+            let max_notional = checked_mul(exposure.equity, I80F48::from_num(self.max_leverage_bps))?
+                .checked_div(I80F48::from_num(BPS_DENOMINATOR))
+                .ok_or_else(|| error!(ErrorCode::DivideByZero))?;
+            if exposure.equity <= I80F48::ZERO || gross_notional > max_notional {
+                return err!(ErrorCode::MaxLeverageExceeded);
+            }
+        }
+        Ok(())
+    }
+
+    // * checked against the mango account once the order has been placed
+    pub fn check_health(&self, exposure: &MangoExposure) -> Result<()> {
+        if exposure.init_liabs == I80F48::ZERO {
+            return Ok(());
+        }
+        let ratio_bps = checked_mul(
+            exposure.init_assets - exposure.init_liabs,
+            I80F48::from_num(BPS_DENOMINATOR),
+        )?
+        .checked_div(exposure.init_liabs)
+        .ok_or_else(|| error!(ErrorCode::DivideByZero))?;
+
+        if ratio_bps < I80F48::from_num(self.min_health_ratio_bps) {
+            return err!(ErrorCode::HealthBelowMinimum);
+        }
+        Ok(())
+    }
+
+    pub const LEN: usize = (
+        32 + // vault
+        2 + // allowed markets
+        (8 * MAX_PAIRS) + // max notional
+        (8 * 2) + // leverage and health
+        1 // bump
+    );
+}
+
+// * vault's mango positions valued at cache prices, resting orders count as if the worse side filled
+pub struct MangoExposure {
+    pub equity: I80F48,
+    pub gross_notional: I80F48,
+    pub market_notional: [I80F48; MAX_PAIRS],
+    pub init_assets: I80F48,
+    pub init_liabs: I80F48,
+    prices: [I80F48; MAX_PAIRS],
+    perp_markets: [Pubkey; MAX_PAIRS],
+    perp_base_lot_sizes: [i64; MAX_PAIRS],
+    spot_base_lot_sizes: [i64; MAX_PAIRS],
+}
+
+impl MangoExposure {
+    // * `open_orders_ais` hold the open orders of every spot market in the margin basket
+    pub fn load(
+        mango_group_ai: &AccountInfo,
+        mango_account_ai: &AccountInfo,
+        mango_cache_ai: &AccountInfo,
+        open_orders_ais: &[AccountInfo],
+        mango_program_id: &Pubkey,
+    ) -> Result<Self> {
+        let mango_group = MangoGroup::load_checked(mango_group_ai, mango_program_id)
+            .map_err(|_| error!(ErrorCode::MangoGroupLoadFailed))?;
+        let mango_account = MangoAccount::load_checked(mango_account_ai, mango_program_id, mango_group_ai.key)
+            .map_err(|_| error!(ErrorCode::MangoAccountLoadFailed))?;
+        let mango_cache = MangoCache::load_checked(mango_cache_ai, mango_program_id, &mango_group)
+            .map_err(|_| error!(ErrorCode::MangoCacheLoadFailed))?;
+
+        let mut exposure = MangoExposure {
+            equity: I80F48::ZERO,
+            gross_notional: I80F48::ZERO,
+            market_notional: [I80F48::ZERO; MAX_PAIRS],
+            init_assets: I80F48::ZERO,
+            init_liabs: I80F48::ZERO,
+            prices: [I80F48::ZERO; MAX_PAIRS],
+            perp_markets: [Pubkey::default(); MAX_PAIRS],
+            perp_base_lot_sizes: [0; MAX_PAIRS],
+            spot_base_lot_sizes: [0; MAX_PAIRS],
+        };
+
+        // * quote deposits count one to one
+        let (deposit, borrow) = native_balances(&mango_account, &mango_cache, QUOTE_INDEX)?;
+        exposure.add_token(deposit, borrow, I80F48::ONE, I80F48::ONE)?;
+
+        for market_index in 0..mango_group.num_oracles {
+            let price = mango_cache.price_cache[market_index].price;
+            exposure.prices[market_index] = price;
+
+            if mango_group.tokens[market_index].mint != Pubkey::default() {
+                let spot_market = &mango_group.spot_markets[market_index];
+                exposure.spot_base_lot_sizes[market_index] = spot_market.base_lot_size;
+
+                let (deposit, borrow) = native_balances(&mango_account, &mango_cache, market_index)?;
+                let deposit_value = checked_mul(deposit, price)?;
+                let borrow_value = checked_mul(borrow, price)?;
+                exposure.add_token(deposit_value, borrow_value, spot_market.init_asset_weight, spot_market.init_liab_weight)?;
+
+                // * resting bids lock quote that buys base, resting asks lock base that is sold
+                let mut if_bids_fill = deposit_value - borrow_value;
+                let mut if_asks_fill = if_bids_fill;
+                if mango_account.in_margin_basket[market_index] {
+                    let open_orders_key = mango_account.spot_open_orders[market_index];
+                    let open_orders_ai = open_orders_ais
+                        .iter()
+                        .find(|ai| *ai.key == open_orders_key)
+                        .ok_or_else(|| error!(ErrorCode::InvalidRemainingAccounts))?;
+                    let open_orders = load_open_orders(open_orders_ai)
+                        .map_err(|_| error!(ErrorCode::OpenOrdersLoadFailed))?;
+
+                    let coin_total = checked_mul(I80F48::from_num(open_orders.native_coin_total), price)?;
+                    let coin_free = checked_mul(I80F48::from_num(open_orders.native_coin_free), price)?;
+                    let pc_total = I80F48::from_num(open_orders.native_pc_total);
+                    let pc_locked = I80F48::from_num(open_orders.native_pc_total.saturating_sub(open_orders.native_pc_free));
+
+                    exposure.equity = checked_add(exposure.equity, checked_add(coin_total, pc_total)?)?;
+                    if_bids_fill = checked_add(checked_add(if_bids_fill, coin_total)?, pc_locked)?;
+                    if_asks_fill = checked_add(if_asks_fill, coin_free)?;
+                }
+                exposure.add_notional(market_index, if_bids_fill.abs().max(if_asks_fill.abs()))?;
+            }
+
+            let perp_market = &mango_group.perp_markets[market_index];
+            if perp_market.perp_market != Pubkey::default() {
+                exposure.perp_markets[market_index] = perp_market.perp_market;
+                exposure.perp_base_lot_sizes[market_index] = perp_market.base_lot_size;
+
+                let perp_account = &mango_account.perp_accounts[market_index];
+                let base_native = I80F48::from_num(perp_account.base_position)
+                    .checked_mul(I80F48::from_num(perp_market.base_lot_size))
+                    .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+                let base_value = checked_mul(base_native, price)?;
+
+                exposure.equity = checked_add(exposure.equity, checked_add(base_value, perp_account.quote_position)?)?;
+                if base_value > I80F48::ZERO {
+                    exposure.init_assets = checked_add(exposure.init_assets, checked_mul(base_value, perp_market.init_asset_weight)?)?;
+                } else {
+                    exposure.init_liabs = checked_add(exposure.init_liabs, checked_mul(-base_value, perp_market.init_liab_weight)?)?;
+                }
+                if perp_account.quote_position > I80F48::ZERO {
+                    exposure.init_assets = checked_add(exposure.init_assets, perp_account.quote_position)?;
+                } else {
+                    exposure.init_liabs = checked_add(exposure.init_liabs, -perp_account.quote_position)?;
+                }
+
+                // * resting perp orders move the position by up to their quantity on either side
+                let base_lots = perp_account.base_position
+                    .checked_add(perp_account.taker_base)
+                    .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+                let if_bids_fill = base_lots
+                    .checked_add(perp_account.bids_quantity)
+                    .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+                let if_asks_fill = base_lots
+                    .checked_sub(perp_account.asks_quantity)
+                    .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+                let worst_native = I80F48::from_num(if_bids_fill.unsigned_abs().max(if_asks_fill.unsigned_abs()))
+                    .checked_mul(I80F48::from_num(perp_market.base_lot_size))
+                    .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+                exposure.add_notional(market_index, checked_mul(worst_native, price)?.abs())?;
+            }
+        }
+
+        Ok(exposure)
+    }
+
+    pub fn perp_market_index(&self, perp_market: &Pubkey) -> Result<usize> {
+        self.perp_markets
+            .iter()
+            .position(|key| key == perp_market)
+            .ok_or_else(|| error!(ErrorCode::InvalidMarketIndex))
+    }
+
+    // * value of `base_lots` perp lots at the cache price
+    pub fn perp_order_notional(&self, market_index: usize, base_lots: i64) -> Result<I80F48> {
+        let base_native = I80F48::from_num(base_lots)
+            .checked_mul(I80F48::from_num(self.perp_base_lot_sizes[market_index]))
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        Ok(checked_mul(base_native, self.prices[market_index])?.abs())
+    }
+
+    // * value of `coin_lots` serum lots at the cache price
+    pub fn spot_order_notional(&self, market_index: usize, coin_lots: u64) -> Result<I80F48> {
+        let base_native = I80F48::from_num(coin_lots)
+            .checked_mul(I80F48::from_num(self.spot_base_lot_sizes[market_index]))
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        checked_mul(base_native, self.prices[market_index])
+    }
+
+    fn add_token(&mut self, deposit_value: I80F48, borrow_value: I80F48, asset_weight: I80F48, liab_weight: I80F48) -> Result<()> {
+        self.equity = checked_add(self.equity, deposit_value - borrow_value)?;
+        self.init_assets = checked_add(self.init_assets, checked_mul(deposit_value, asset_weight)?)?;
+        self.init_liabs = checked_add(self.init_liabs, checked_mul(borrow_value, liab_weight)?)?;
+        Ok(())
+    }
+
+    fn add_notional(&mut self, market_index: usize, notional: I80F48) -> Result<()> {
+        self.market_notional[market_index] = checked_add(self.market_notional[market_index], notional)?;
+        self.gross_notional = checked_add(self.gross_notional, notional)?;
+        Ok(())
+    }
+}
+
+fn native_balances(mango_account: &MangoAccount, mango_cache: &MangoCache, token_index: usize) -> Result<(I80F48, I80F48)> {
+    let root_bank_cache = &mango_cache.root_bank_cache[token_index];
+    let deposit = mango_account
+        .get_native_deposit(root_bank_cache, token_index)
+        .map_err(|_| error!(ErrorCode::MathOverflow))?;
+    let borrow = mango_account
+        .get_native_borrow(root_bank_cache, token_index)
+        .map_err(|_| error!(ErrorCode::MathOverflow))?;
+    Ok((deposit, borrow))
+}
+
+fn checked_add(a: I80F48, b: I80F48) -> Result<I80F48> {
+    a.checked_add(b).ok_or_else(|| error!(ErrorCode::MathOverflow))
+}
+
+fn checked_mul(a: I80F48, b: I80F48) -> Result<I80F48> {
+    a.checked_mul(b).ok_or_else(|| error!(ErrorCode::MathOverflow))
+}
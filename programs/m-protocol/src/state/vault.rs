@@ -1,13 +1,16 @@
 use crate::constants::*;
-use std::str::FromStr;
+use crate::error::ErrorCode;
 
 use anchor_lang::prelude::*;
-use rust_decimal::prelude::ToPrimitive;
+use mango::state::MAX_PAIRS;
+use solana_gateway::Gateway;
 
 #[account]
 pub struct Vault {
     pub publickey: Pubkey, // 32
-    pub manager: Pubkey, // 32
+    pub admin: Pubkey, // 32   // * updates vault config and roles
+    pub trader: Pubkey, // 32  // * moves funds and trades on mango
+    pub keeper: Pubkey, // 32  // * processes queues and balances
     pub name: String, // 4 + 16
     
     pub limit: u64, // 8
@@ -16,10 +19,11 @@ pub struct Vault {
     pub total_equity: u64, // 8
     pub previous_total_equity: u64, // 8
     pub total_equity_before_settlements: u64, // 8
-    pub day_pnl: f64, // 8
+    pub day_pnl: i128, // 16 // * scaled by PNL_SCALE
 
     pub token_account: Pubkey, // 32 // * vault usdc main token account
     pub mint: Pubkey, // 32          // * vault usdc token mint
+    pub share_mint: Pubkey, // 32    // * vault share token mint
 
     pub stake_request_account: Pubkey, // 32
     pub unstake_request_account: Pubkey, // 32
@@ -29,30 +33,284 @@ pub struct Vault {
 
     pub mango_account_bump: u8, // 1
     pub mango_account_num: u64, // 8
+    pub mango_program_id: Pubkey, // 32 // * mango program and group the account was created on
+    pub mango_group: Pubkey, // 32
+
+    pub fee_recipient: Pubkey, // 32
+    pub management_fee_bps: u16, // 2  // * annual, prorated per balance update
+    pub performance_fee_bps: u16, // 2 // * charged on gains above the high water mark
+    pub high_water_mark: u64, // 8     // * share price scaled by SHARE_PRICE_SCALE
+    pub accrued_fees: u64, // 8        // * fees owed to the recipient, still held on mango
+    pub last_fee_timestamp: i64, // 8
+
+    pub epoch: Epoch, // 24
+
+    pub spot_open_orders: [Pubkey; MAX_PAIRS], // 32 * 15 // * indexed by mango market index, default when unused
+
+    pub status: VaultStatus, // 1
+    pub halt_reason: HaltReason, // 1
+    pub halted_at: i64, // 8
+    pub max_daily_drawdown_bps: u16, // 2 // * 0 disables the check
+    pub max_drawdown_bps: u16, // 2       // * from peak share price, 0 disables the check
+    pub peak_share_price: u64, // 8       // * scaled by SHARE_PRICE_SCALE
+
+    pub paused: u8, // 1 // * PAUSE_* bits
+
+    pub gatekeeper_network: Option<Pubkey>, // 1 + 32 // * users need a gateway token from this network when set
+    pub access_mode: AccessMode, // 1
+
+    pub lockup_seconds: i64, // 8           // * minimum time after a stake before it can be unstaked
+    pub unstake_cooldown_seconds: i64, // 8 // * minimum time an unstake request waits before it settles
+
+    pub early_withdrawal_penalty_bps: u16, // 2   // * charged on unstakes of a fresh stake, decays to zero
+    pub penalty_period_seconds: i64, // 8         // * stake age at which the penalty reaches zero
+    pub penalty_recipient: PenaltyRecipient, // 1
+    pub accrued_insurance: u64, // 8              // * penalties and fees owed to the insurance fund, still held on mango
+
+    pub insurance_fund: Pubkey, // 32             // * default until the fund is created
+    pub insurance_fee_bps: u16, // 2              // * slice of performance fees sent to the insurance fund
+    pub insurance_trigger_bps: u16, // 2          // * daily loss that allows an injection, 0 disables injections
+    pub insurance_injection_allowed: bool, // 1
+    pub insurance_fund_balance: u64, // 8         // * token balance after the last collection or injection
 }
 
 impl Vault {
-    pub fn manager() -> Pubkey {
-        Pubkey::from_str(MANAGER_PUBKEY).unwrap()
-    }
-
     pub fn calculate_daily_pnl(&self) -> i128 {
         (self.total_equity as i128) - (self.previous_total_equity as i128)
     }
 
-    pub fn calculate_pnl_ratio(&self) -> f64 {
+    // * daily pnl over the previous equity, scaled by PNL_SCALE
+    pub fn calculate_pnl_ratio(&self) -> Result<i128> {
         if self.previous_total_equity == 0 {
-            return 0_f64;
+            return Ok(0);
+        }
+        to_pnl_ratio(self.calculate_daily_pnl(), self.previous_total_equity)
+    }
+
+    // * shares minted for a stake of `amount` at the settlement price of the last closed epoch
+    pub fn calculate_shares_to_mint(&self, amount: u64) -> Result<u64> {
+        if self.epoch.settlement_price == 0 {
+            return err!(ErrorCode::DivideByZero);
+        }
+        let shares = (amount as u128)
+            .checked_mul(SHARE_PRICE_SCALE as u128)
+            .and_then(|value| value.checked_div(self.epoch.settlement_price as u128));
+        to_u64(shares)
+    }
+
+    // * shares burned for an unstake of `amount`, rounded up in favour of the vault
+    pub fn calculate_shares_to_burn(&self, amount: u64) -> Result<u64> {
+        if self.epoch.settlement_price == 0 {
+            return err!(ErrorCode::DivideByZero);
+        }
+        let price = self.epoch.settlement_price as u128;
+        let shares = (amount as u128)
+            .checked_mul(SHARE_PRICE_SCALE as u128)
+            .and_then(|value| value.checked_add(price - 1))
+            .and_then(|value| value.checked_div(price));
+        to_u64(shares)
+    }
+
+    // * amount paid out for `shares` at the settlement price, rounded down in favour of the vault
+    pub fn calculate_settlement_amount(&self, shares: u64) -> Result<u64> {
+        let amount = (shares as u128)
+            .checked_mul(self.epoch.settlement_price as u128)
+            .and_then(|value| value.checked_div(SHARE_PRICE_SCALE as u128));
+        to_u64(amount)
+    }
+
+    // * equity represented by `shares`, rounded down in favour of the vault
+    pub fn calculate_equity(&self, shares: u64, share_supply: u64) -> Result<u64> {
+        if share_supply == 0 {
+            return Ok(0);
+        }
+        let equity = (shares as u128)
+            .checked_mul(self.total_equity as u128)
+            .and_then(|value| value.checked_div(share_supply as u128));
+        to_u64(equity)
+    }
+
+    // * equity per share scaled by SHARE_PRICE_SCALE
+    pub fn calculate_share_price(equity: u64, share_supply: u64) -> Result<u64> {
+        if share_supply == 0 {
+            return Ok(SHARE_PRICE_SCALE);
+        }
+        let price = (equity as u128)
+            .checked_mul(SHARE_PRICE_SCALE as u128)
+            .and_then(|value| value.checked_div(share_supply as u128));
+        to_u64(price)
+    }
+
+    // * management fee on `equity` for the time elapsed since the last accrual
+    pub fn calculate_management_fee(&self, equity: u64, now: i64) -> Result<u64> {
+        let elapsed = now
+            .checked_sub(self.last_fee_timestamp)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?
+            .max(0) as u128;
+        let fee = (equity as u128)
+            .checked_mul(self.management_fee_bps as u128)
+            .and_then(|value| value.checked_mul(elapsed))
+            .and_then(|value| value.checked_div(BPS_DENOMINATOR as u128 * SECONDS_PER_YEAR as u128));
+        to_u64(fee)
+    }
+
+    // * performance fee on the share price gain above the high water mark
+    pub fn calculate_performance_fee(&self, equity: u64, share_supply: u64) -> Result<u64> {
+        let price = Vault::calculate_share_price(equity, share_supply)?;
+        if share_supply == 0 || price <= self.high_water_mark {
+            return Ok(0);
+        }
+        let fee = ((price - self.high_water_mark) as u128)
+            .checked_mul(share_supply as u128)
+            .and_then(|value| value.checked_mul(self.performance_fee_bps as u128))
+            .and_then(|value| value.checked_div(SHARE_PRICE_SCALE as u128 * BPS_DENOMINATOR as u128));
+        to_u64(fee)
+    }
+
+    // * always valid for vaults without a gatekeeper network
+    pub fn has_valid_gateway_token(&self, gateway_token: &AccountInfo, owner: &Pubkey) -> bool {
+        match self.gatekeeper_network {
+            Some(network) => Gateway::verify_gateway_token_account_info(gateway_token, owner, &network, None).is_ok(),
+            None => true,
+        }
+    }
+
+    // * seconds left before a stake settled at `staked_at` can be unstaked
+    pub fn lockup_remaining(&self, staked_at: i64, now: i64) -> i64 {
+        staked_at.saturating_add(self.lockup_seconds).saturating_sub(now).max(0)
+    }
+
+    // * unix time an unstake requested at `requested_at` can settle from, fixed on the request
+    pub fn cooldown_ends_at(&self, requested_at: i64) -> i64 {
+        requested_at.saturating_add(self.unstake_cooldown_seconds)
+    }
+
+    // * seconds left before an unstake whose cooldown ends at `cooldown_ends_at` can settle
+    pub fn cooldown_remaining(cooldown_ends_at: i64, now: i64) -> i64 {
+        cooldown_ends_at.saturating_sub(now).max(0)
+    }
+
+    // * penalty on unstaking `amount` out of `equity`, settled stake leaves first so only the part drawn from
+    // * stake still inside the penalty period is charged, that part no longer counts as penalized
+    pub fn take_early_withdrawal_penalty(&self, user_vault_account: &mut UserVaultAccount, amount: u64, equity: u64, now: i64) -> Result<u64> {
+        let penalized_stake = user_vault_account.penalized_stake(self.penalty_period_seconds, now);
+        let penalized = amount
+            .saturating_sub(equity.saturating_sub(penalized_stake))
+            .min(penalized_stake);
+        user_vault_account.penalized_stake = penalized_stake - penalized;
+        self.calculate_early_withdrawal_penalty(penalized, user_vault_account.penalized_since, now)
+    }
+
+    // * penalty on unstaking `amount` from a stake settled at `staked_at`, decays linearly over the penalty period
+    pub fn calculate_early_withdrawal_penalty(&self, amount: u64, staked_at: i64, now: i64) -> Result<u64> {
+        if self.early_withdrawal_penalty_bps == 0 || self.penalty_period_seconds <= 0 {
+            return Ok(0);
+        }
+        let age = now.saturating_sub(staked_at).max(0);
+        if age >= self.penalty_period_seconds {
+            return Ok(0);
+        }
+        let penalty = (amount as u128)
+            .checked_mul(self.early_withdrawal_penalty_bps as u128)
+            .and_then(|value| value.checked_mul((self.penalty_period_seconds - age) as u128))
+            .and_then(|value| value.checked_div(BPS_DENOMINATOR as u128 * self.penalty_period_seconds as u128));
+        to_u64(penalty)
+    }
+
+    // * part of the performance fee sent to the insurance fund
+    pub fn calculate_insurance_share(&self, performance_fee: u64) -> Result<u64> {
+        let share = (performance_fee as u128)
+            .checked_mul(self.insurance_fee_bps as u128)
+            .and_then(|value| value.checked_div(BPS_DENOMINATOR as u128));
+        to_u64(share)
+    }
+
+    // * insurance held in the fund and still owed to it on mango, not part of total equity
+    pub fn insurance_balance(&self) -> Result<u64> {
+        self.insurance_fund_balance
+            .checked_add(self.accrued_insurance)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))
+    }
+
+    // * equity leaving the vault when `amount` is unstaked with `penalty` withheld
+    pub fn unstake_equity_outflow(&mut self, amount: u64, penalty: u64) -> Result<u64> {
+        match self.penalty_recipient {
+            // * the penalty stays on mango and raises the share price of the remaining stakers
+            PenaltyRecipient::Stakers => amount.checked_sub(penalty).ok_or_else(|| error!(ErrorCode::MathOverflow)),
+            PenaltyRecipient::InsuranceFund => {
+                self.accrued_insurance = self.accrued_insurance
+                    .checked_add(penalty)
+                    .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+                Ok(amount)
+            }
+        }
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.status == VaultStatus::Halted
+    }
+
+    // * whether the last balance update lost at least `bps` of the previous equity
+    pub fn daily_loss_exceeds(&self, bps: u16) -> Result<bool> {
+        if self.day_pnl >= 0 {
+            return Ok(false);
         }
-        self.calculate_daily_pnl().to_f64().unwrap() / self.previous_total_equity.to_f64().unwrap()
+        let loss_bps = self.day_pnl
+            .checked_neg()
+            .and_then(|value| value.checked_mul(BPS_DENOMINATOR as i128))
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        Ok(loss_bps >= (bps as i128) * PNL_SCALE)
+    }
+
+    // * first drawdown limit breached by the last balance update, daily loss is checked first
+    pub fn check_drawdown(&self, share_price: u64) -> Result<Option<HaltReason>> {
+        // * outstanding shares worth nothing, no stake can be priced until equity returns
+        if share_price == 0 {
+            return Ok(Some(HaltReason::ZeroSharePrice));
+        }
+
+        if self.max_daily_drawdown_bps > 0 && self.daily_loss_exceeds(self.max_daily_drawdown_bps)? {
+            return Ok(Some(HaltReason::DailyDrawdown));
+        }
+
+        if self.max_drawdown_bps > 0 && share_price < self.peak_share_price {
+            let drawdown = ((self.peak_share_price - share_price) as u128)
+                .checked_mul(BPS_DENOMINATOR as u128)
+                .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+            if drawdown >= (self.max_drawdown_bps as u128) * (self.peak_share_price as u128) {
+                return Ok(Some(HaltReason::PeakDrawdown));
+            }
+        }
+
+        Ok(None)
     }
 
     pub const LEN: usize = (
-        (8 * 5) + // u64
-        8 + // day_pnl
+        (8 * 6) + // u64
+        16 + // day_pnl
         (4 + 16) + // 16 characters max string
-        (1 * 2) + // u8
-        (32 * 6) // pubkey
+        (1 * 3) + // u8
+        (32 * 12) + // pubkey
+        (2 * 2) + // fee rates
+        (8 * 3) + // fee accounting
+        (8 * 3) + // epoch
+        (32 * MAX_PAIRS) + // spot open orders
+        (1 * 2) + // status and halt reason
+        8 + // halted_at
+        (2 * 2) + // drawdown limits
+        8 + // peak share price
+        1 + // paused
+        (1 + 32) + // gatekeeper network
+        1 + // access mode
+        (8 * 2) + // lockup and cooldown
+        2 + // early withdrawal penalty
+        8 + // penalty period
+        1 + // penalty recipient
+        8 + // accrued insurance
+        32 + // insurance fund
+        (2 * 2) + // insurance rates
+        1 + // insurance injection allowed
+        8 // insurance fund balance
     );
 }
 
@@ -66,9 +324,14 @@ pub struct UserVaultAccount {
     pub equity: u64,
     pub user_total_stake: u64,
     pub user_total_unstake: u64,
-    pub user_pnl: f64,
+    pub user_pnl: i128, // * scaled by PNL_SCALE
+    pub last_stake_timestamp: i64, // * unix time the last stake settled
+    pub user_total_penalty: u64, // * early withdrawal penalties paid since the position opened
+    pub penalized_stake: u64, // * stake still inside the penalty period
+    pub penalized_since: i64, // * stake time of `penalized_stake`, weighted by amount
     pub authority: Pubkey,
     pub token_account: Pubkey,
+    pub share_token_account: Pubkey,
     pub user_stake: UserStake,
     pub user_unstake: UserUnstake,
     pub last_trade_stat: LastTradeStat,
@@ -76,52 +339,87 @@ pub struct UserVaultAccount {
 }
 
 impl UserVaultAccount {
-    pub fn calculate_pnl(&self) -> f64 {
+    pub fn calculate_pnl(&self) -> Result<i128> {
         if self.user_total_stake == 0 {
-            return 0_f64;
+            return Ok(0);
         }
-        (self.user_total_unstake.to_f64().unwrap() + self.equity.to_f64().unwrap()
-            - self.user_total_stake.to_f64().unwrap())
-            / self.user_total_stake.to_f64().unwrap()
+        let pnl = (self.user_total_unstake as i128)
+            .checked_add(self.equity as i128)
+            .and_then(|value| value.checked_sub(self.user_total_stake as i128))
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        to_pnl_ratio(pnl, self.user_total_stake)
     }
 
-    pub fn calclulate_realised_pnl(&self) -> f64 {
+    pub fn calclulate_realised_pnl(&self) -> Result<i128> {
         if self.last_trade_stat.user_total_stake == 0 {
-            return 0_f64;
+            return Ok(0);
+        }
+        let pnl = (self.last_trade_stat.user_total_unstake as i128)
+            .checked_sub(self.last_trade_stat.user_total_stake as i128)
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))?;
+        to_pnl_ratio(pnl, self.last_trade_stat.user_total_stake)
+    }
+
+    // * stake still inside the penalty period, none once its weighted stake time has aged out
+    pub fn penalized_stake(&self, penalty_period_seconds: i64, now: i64) -> u64 {
+        if now.saturating_sub(self.penalized_since) >= penalty_period_seconds {
+            return 0;
+        }
+        self.penalized_stake
+    }
+
+    // * stakes inside the penalty period share one stake time weighted by amount, the linear decay charges them as one
+    pub fn add_penalized_stake(&mut self, amount: u64, penalty_period_seconds: i64, now: i64) -> Result<()> {
+        let previous = self.penalized_stake(penalty_period_seconds, now);
+        let total = previous.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+        if total == 0 {
+            return Ok(());
         }
-        (self.last_trade_stat.user_total_unstake.to_f64().unwrap()
-            - self.last_trade_stat.user_total_stake.to_f64().unwrap())
-            / self.last_trade_stat.user_total_stake.to_f64().unwrap()
+        let since = (previous as i128)
+            .checked_mul(self.penalized_since as i128)
+            .and_then(|value| value.checked_add((amount as i128).checked_mul(now as i128)?))
+            .and_then(|value| value.checked_div(total as i128))
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.penalized_stake = total;
+        self.penalized_since = since as i64;
+        Ok(())
+    }
+
+    // * penalties are recorded at every settlement, not only once the position closes
+    pub fn record_unstake(&mut self, payout: u64, penalty: u64) -> Result<()> {
+        self.user_total_unstake = self.user_total_unstake.checked_add(payout).ok_or(ErrorCode::MathOverflow)?;
+        self.user_total_penalty = self.user_total_penalty.checked_add(penalty).ok_or(ErrorCode::MathOverflow)?;
+        self.last_trade_stat.penalty_paid = self.user_total_penalty;
+        Ok(())
     }
 
-    pub fn refresh_stats(&mut self) {
+    pub fn refresh_stats(&mut self) -> Result<()> {
         if self.equity == 0 {
             self.last_trade_stat.user_total_stake = self.user_total_stake;
             self.last_trade_stat.user_total_unstake = self.user_total_unstake;
 
             self.user_total_stake = 0_u64;
             self.user_total_unstake = 0_u64;
-            self.user_pnl = 0_f64;
+            self.user_total_penalty = 0_u64;
+            self.penalized_stake = 0_u64;
+            self.user_pnl = 0;
 
-            self.last_trade_stat.user_realised_pnl = self.calclulate_realised_pnl()
+            self.last_trade_stat.user_realised_pnl = self.calclulate_realised_pnl()?;
         }
+        Ok(())
     }
 }
 
 #[account(zero_copy)]
 pub struct StakeReq {
-    pub vault: Pubkey,
-    pub max_requests: u32,
-    pub count: u32,                     // 4
-    pub orders: [Pubkey; MAX_REQUESTS], // 32 * 1000 + 1
+    pub vault: Pubkey, // 32
+    pub queue: RequestQueue,
 }
 
 #[account(zero_copy)]
 pub struct UnstakeReq {
-    pub vault: Pubkey,
-    pub max_requests: u32,
-    pub count: u32,                     // 4
-    pub orders: [Pubkey; MAX_REQUESTS], // 32 *1000 + 1
+    pub vault: Pubkey, // 32
+    pub queue: RequestQueue,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
@@ -131,6 +429,9 @@ pub struct UserStake {
     pub max: bool,
     pub cancel: bool,
     pub status: RequestStatus,
+    pub epoch: u64,            // * epoch the request was made in
+    pub settled_epoch: u64,    // * epoch whose price the last request settled at
+    pub settled_price: u64,    // * share price scaled by SHARE_PRICE_SCALE
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
@@ -140,13 +441,65 @@ pub struct UserUnstake {
     pub max: bool,
     pub cancel: bool,
     pub status: RequestStatus,
+    pub epoch: u64,            // * epoch the request was made in
+    pub settled_epoch: u64,    // * epoch whose price the last request settled at
+    pub settled_price: u64,    // * share price scaled by SHARE_PRICE_SCALE
+    pub requested_at: i64,     // * unix time the request was made or last updated
+    pub cooldown_ends_at: i64, // * unix time the request can settle from, under the cooldown it was made with
+}
+
+// * requests made during `current` settle once it closes at the next balance update
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub struct Epoch {
+    pub current: u64,
+    pub started_at: i64,
+    pub settlement_price: u64, // * share price recorded when `current - 1` closed
+}
+
+impl Epoch {
+    pub fn is_settled(&self, epoch: u64) -> bool {
+        epoch < self.current
+    }
+
+    // * the epoch whose close recorded `settlement_price`, the one settled requests are priced at
+    pub fn last_closed(&self) -> u64 {
+        self.current.saturating_sub(1)
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
 pub struct LastTradeStat {
     pub user_total_stake: u64,
     pub user_total_unstake: u64,
-    pub user_realised_pnl: f64,
+    pub user_realised_pnl: i128, // * scaled by PNL_SCALE
+    pub penalty_paid: u64, // * early withdrawal penalties paid over the open or last closed position, as of the last unstake
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum VaultRole {
+    Admin,
+    Trader,
+    Keeper,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum VaultStatus {
+    Active,
+    Halted, // * stakes and trading blocked, unstakes still processed
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum HaltReason {
+    None,
+    DailyDrawdown,
+    PeakDrawdown,
+    ZeroSharePrice,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum PenaltyRecipient {
+    Stakers,
+    InsuranceFund,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
@@ -157,4 +510,21 @@ pub enum RequestStatus {
     Unsuccessful = 3,
     Cancelled = 4,
     InsufficientBalance = 5,
+    LockupActive = 6, // * the user staked again after requesting, the unstake is turned away
+}
+
+fn to_u64(value: Option<u128>) -> Result<u64> {
+    value
+        .and_then(|value| u64::try_from(value).ok())
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))
+}
+
+// * `pnl / base` scaled by PNL_SCALE
+fn to_pnl_ratio(pnl: i128, base: u64) -> Result<i128> {
+    if base == 0 {
+        return err!(ErrorCode::DivideByZero);
+    }
+    pnl.checked_mul(PNL_SCALE)
+        .and_then(|value| value.checked_div(base as i128))
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))
 }
@@ -0,0 +1,200 @@
+//! Stand-in for the mango v3 program, enough of it for the vault flows to run offline.
+//!
+//! Accounts keep the real mango layouts so `update_vault_balance` reads them exactly
+//! as it reads mainnet accounts. Only the quote token is supported and deposits are
+//! booked at the cached deposit index, no interest, health or margin basket.
+
+use fixed::types::I80F48;
+use mango::instruction::MangoInstruction;
+use mango::state::{DataType, Loadable, MangoAccount, MangoCache, MangoGroup, MetaData};
+use solana_program::account_info::AccountInfo;
+use solana_program::entrypoint::ProgramResult;
+use solana_program::program::{invoke, invoke_signed};
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::rent::Rent;
+use solana_program::system_instruction;
+use solana_program::sysvar::Sysvar;
+use std::mem::size_of;
+
+// * mango bumps its own layout version, the vault never reads it
+const MANGO_ACCOUNT_VERSION: u8 = 1;
+
+pub fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    match MangoInstruction::unpack(data).ok_or(ProgramError::InvalidInstructionData)? {
+        MangoInstruction::CreateMangoAccount { account_num } => create_mango_account(program_id, accounts, account_num),
+        MangoInstruction::CloseMangoAccount => close_mango_account(program_id, accounts),
+        MangoInstruction::Deposit { quantity } => deposit(program_id, accounts, quantity),
+        MangoInstruction::Withdraw { quantity, allow_borrow } => withdraw(program_id, accounts, quantity, allow_borrow),
+        MangoInstruction::SetDelegate => set_delegate(program_id, accounts),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+// * [group, mango account, owner, system program, payer]
+fn create_mango_account(program_id: &Pubkey, accounts: &[AccountInfo], account_num: u64) -> ProgramResult {
+    let [group_ai, mango_account_ai, owner_ai, system_program_ai, payer_ai] = account_slice::<5>(accounts)?;
+    if !owner_ai.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    MangoGroup::load_checked(group_ai, program_id).map_err(|_| ProgramError::InvalidAccountData)?;
+
+    let seeds: &[&[u8]] = &[group_ai.key.as_ref(), owner_ai.key.as_ref(), &account_num.to_le_bytes()];
+    let (address, bump) = Pubkey::find_program_address(seeds, program_id);
+    if address != *mango_account_ai.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // * builtins cannot grow account data across a cpi, the harness allocates the pda up front
+    let space = size_of::<MangoAccount>();
+    if mango_account_ai.data_is_empty() {
+        invoke_signed(
+            &system_instruction::create_account(
+                payer_ai.key,
+                mango_account_ai.key,
+                Rent::get()?.minimum_balance(space),
+                space as u64,
+                program_id,
+            ),
+            &[payer_ai.clone(), mango_account_ai.clone(), system_program_ai.clone()],
+            &[&[group_ai.key.as_ref(), owner_ai.key.as_ref(), &account_num.to_le_bytes(), &[bump]]],
+        )?;
+    } else if mango_account_ai.owner != program_id || mango_account_ai.data_len() != space {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let mut mango_account = MangoAccount::load_mut(mango_account_ai)?;
+    if mango_account.meta_data.is_initialized {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    mango_account.meta_data = MetaData::new(DataType::MangoAccount, MANGO_ACCOUNT_VERSION, true);
+    mango_account.mango_group = *group_ai.key;
+    mango_account.owner = *owner_ai.key;
+    Ok(())
+}
+
+// * [group, mango account, owner], rent goes back to the owner
+fn close_mango_account(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let [group_ai, mango_account_ai, owner_ai] = account_slice::<3>(accounts)?;
+    check_owner(program_id, group_ai, mango_account_ai, owner_ai)?;
+
+    let lamports = mango_account_ai.lamports();
+    **mango_account_ai.try_borrow_mut_lamports()? = 0;
+    **owner_ai.try_borrow_mut_lamports()? += lamports;
+    mango_account_ai.try_borrow_mut_data()?.fill(0);
+    Ok(())
+}
+
+// * [group, mango account, owner, cache, root bank, node bank, vault, token program, owner token account]
+fn deposit(program_id: &Pubkey, accounts: &[AccountInfo], quantity: u64) -> ProgramResult {
+    let [group_ai, mango_account_ai, owner_ai, cache_ai, root_bank_ai, _node_bank_ai, vault_ai, token_program_ai, owner_token_account_ai] =
+        account_slice::<9>(accounts)?;
+    check_owner(program_id, group_ai, mango_account_ai, owner_ai)?;
+    let token_index = token_index(program_id, group_ai, root_bank_ai)?;
+    let deposit_index = deposit_index(program_id, group_ai, cache_ai, token_index)?;
+
+    invoke(
+        &spl_token::instruction::transfer(
+            token_program_ai.key,
+            owner_token_account_ai.key,
+            vault_ai.key,
+            owner_ai.key,
+            &[],
+            quantity,
+        )?,
+        &[owner_token_account_ai.clone(), vault_ai.clone(), owner_ai.clone(), token_program_ai.clone()],
+    )?;
+
+    let mut mango_account = MangoAccount::load_mut(mango_account_ai)?;
+    let deposit = I80F48::from_num(quantity)
+        .checked_div(deposit_index)
+        .ok_or(ProgramError::InvalidArgument)?;
+    mango_account.deposits[token_index] = mango_account.deposits[token_index]
+        .checked_add(deposit)
+        .ok_or(ProgramError::InvalidArgument)?;
+    Ok(())
+}
+
+// * [group, mango account, owner, cache, root bank, node bank, vault, token account, signer, token program, open orders..]
+fn withdraw(program_id: &Pubkey, accounts: &[AccountInfo], quantity: u64, allow_borrow: bool) -> ProgramResult {
+    let [group_ai, mango_account_ai, owner_ai, cache_ai, root_bank_ai, _node_bank_ai, vault_ai, token_account_ai, signer_ai, token_program_ai] =
+        account_slice::<10>(accounts)?;
+    check_owner(program_id, group_ai, mango_account_ai, owner_ai)?;
+    if allow_borrow {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let token_index = token_index(program_id, group_ai, root_bank_ai)?;
+    let deposit_index = deposit_index(program_id, group_ai, cache_ai, token_index)?;
+
+    {
+        let mut mango_account = MangoAccount::load_mut(mango_account_ai)?;
+        let withdrawn = I80F48::from_num(quantity)
+            .checked_div(deposit_index)
+            .ok_or(ProgramError::InvalidArgument)?;
+        if withdrawn > mango_account.deposits[token_index] {
+            return Err(ProgramError::InsufficientFunds);
+        }
+        mango_account.deposits[token_index] -= withdrawn;
+    }
+
+    let signer_nonce = {
+        let mango_group = MangoGroup::load_checked(group_ai, program_id).map_err(|_| ProgramError::InvalidAccountData)?;
+        if mango_group.signer_key != *signer_ai.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+        mango_group.signer_nonce
+    };
+    invoke_signed(
+        &spl_token::instruction::transfer(
+            token_program_ai.key,
+            vault_ai.key,
+            token_account_ai.key,
+            signer_ai.key,
+            &[],
+            quantity,
+        )?,
+        &[vault_ai.clone(), token_account_ai.clone(), signer_ai.clone(), token_program_ai.clone()],
+        &[&[group_ai.key.as_ref(), &signer_nonce.to_le_bytes()]],
+    )
+}
+
+// * [group, mango account, owner, delegate]
+fn set_delegate(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let [group_ai, mango_account_ai, owner_ai, delegate_ai] = account_slice::<4>(accounts)?;
+    check_owner(program_id, group_ai, mango_account_ai, owner_ai)?;
+
+    MangoAccount::load_mut(mango_account_ai)?.delegate = *delegate_ai.key;
+    Ok(())
+}
+
+fn account_slice<'a, 'info, const N: usize>(accounts: &'a [AccountInfo<'info>]) -> Result<&'a [AccountInfo<'info>; N], ProgramError> {
+    accounts
+        .get(..N)
+        .and_then(|accounts| accounts.try_into().ok())
+        .ok_or(ProgramError::NotEnoughAccountKeys)
+}
+
+fn check_owner(program_id: &Pubkey, group_ai: &AccountInfo, mango_account_ai: &AccountInfo, owner_ai: &AccountInfo) -> ProgramResult {
+    let mango_account = MangoAccount::load_checked(mango_account_ai, program_id, group_ai.key)
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    if mango_account.owner != *owner_ai.key {
+        return Err(ProgramError::IllegalOwner);
+    }
+    if !owner_ai.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    Ok(())
+}
+
+fn token_index(program_id: &Pubkey, group_ai: &AccountInfo, root_bank_ai: &AccountInfo) -> Result<usize, ProgramError> {
+    MangoGroup::load_checked(group_ai, program_id)
+        .map_err(|_| ProgramError::InvalidAccountData)?
+        .find_root_bank_index(root_bank_ai.key)
+        .ok_or(ProgramError::InvalidArgument)
+}
+
+fn deposit_index(program_id: &Pubkey, group_ai: &AccountInfo, cache_ai: &AccountInfo, token_index: usize) -> Result<I80F48, ProgramError> {
+    let mango_group = MangoGroup::load_checked(group_ai, program_id).map_err(|_| ProgramError::InvalidAccountData)?;
+    let mango_cache = MangoCache::load_checked(cache_ai, program_id, &mango_group).map_err(|_| ProgramError::InvalidAccountData)?;
+    Ok(mango_cache.root_bank_cache[token_index].deposit_index)
+}
@@ -0,0 +1,452 @@
+//! Program-test harness running m-protocol against the mock mango program, shared
+//! with the keeper tests through a `#[path]` module.
+//!
+//! Run with `cargo test-bpf` so m-protocol loads from its built `.so`; anchor's `init`
+//! grows accounts through the system program, which native builtins cannot do.
+
+#![allow(dead_code)]
+
+pub mod mock_mango;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AccountDeserialize, AccountSerialize};
+use fixed::types::I80F48;
+use m_protocol::error::ErrorCode;
+use m_protocol::state::{ProtocolConfig, RequestEntry, UserVaultAccount, Vault};
+use mango::state::{DataType, MangoAccount, MangoCache, MangoGroup, MetaData, QUOTE_INDEX};
+use merstab_client::{decode_stake_req, decode_unstake_req, instruction, pda, queued_entries, MangoAccounts, VaultKeys};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::account::{Account, AccountSharedData};
+use solana_sdk::instruction::{Instruction, InstructionError};
+use solana_sdk::program_pack::Pack;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::sysvar::clock::Clock;
+use solana_sdk::transaction::{Transaction, TransactionError};
+use solana_sdk::{system_instruction, system_program};
+use std::mem::size_of;
+
+pub const VAULT_NAME: &str = "flows";
+pub const VAULT_LIMIT: u64 = 1_000_000_000_000;
+pub const USER_LIMIT: u64 = 10_000_000_000;
+
+// * mango version of the fixture accounts, the vault never reads it
+const MANGO_VERSION: u8 = 1;
+
+pub struct Harness {
+    pub context: ProgramTestContext,
+    pub admin: Keypair,
+    pub keys: VaultKeys,
+    slot: u64,
+}
+
+impl Harness {
+    // * a vault created through the program with its account on the mock mango group
+    pub async fn new() -> Self {
+        let admin = Keypair::new();
+        let mint = Keypair::new();
+        let mango = mango_accounts();
+
+        let mut program_test = ProgramTest::new("m_protocol", m_protocol::ID, processor!(m_protocol::entry));
+        program_test.add_program("mock_mango", mango.program_id, processor!(mock_mango::process_instruction));
+        program_test.add_account(admin.pubkey(), Account::new(100_000_000_000, 0, &system_program::ID));
+
+        let (config_key, config_bump) = pda::config(&m_protocol::ID);
+        program_test.add_account(
+            config_key,
+            program_account(
+                &ProtocolConfig {
+                    admin: admin.pubkey(),
+                    pending_admin: None,
+                    creators: vec![admin.pubkey()],
+                    bump: config_bump,
+                    guardian: admin.pubkey(),
+                    paused: 0,
+                },
+                8 + ProtocolConfig::LEN,
+            ),
+        );
+        add_mango_fixtures(&mut program_test, &mango, &mint.pubkey());
+        program_test.add_account(
+            mint.pubkey(),
+            packed_account(
+                spl_token::state::Mint {
+                    mint_authority: Some(admin.pubkey()).into(),
+                    supply: 0,
+                    decimals: 6,
+                    is_initialized: true,
+                    freeze_authority: None.into(),
+                },
+                &spl_token::ID,
+            ),
+        );
+
+        let mut context = program_test.start_with_context().await;
+        let mut slot = context.banks_client.get_root_slot().await.unwrap() + 1;
+
+        let stake_req = Keypair::new();
+        let unstake_req = Keypair::new();
+        let rent = context.banks_client.get_rent().await.unwrap();
+        let request_lamports = rent.minimum_balance(instruction::REQUEST_ACCOUNT_SPACE);
+        send(
+            &mut context,
+            &mut slot,
+            &[
+                instruction::create_request_account(&m_protocol::ID, &admin.pubkey(), &stake_req.pubkey(), request_lamports),
+                instruction::create_request_account(&m_protocol::ID, &admin.pubkey(), &unstake_req.pubkey(), request_lamports),
+                instruction::create_vault(
+                    &m_protocol::ID,
+                    &admin.pubkey(),
+                    &mint.pubkey(),
+                    &stake_req.pubkey(),
+                    &unstake_req.pubkey(),
+                    VAULT_NAME.to_string(),
+                    VAULT_LIMIT,
+                ),
+            ],
+            &[&admin, &stake_req, &unstake_req],
+        )
+        .await
+        .unwrap();
+
+        let vault_key = pda::vault(&m_protocol::ID, VAULT_NAME).0;
+        let vault: Vault = load(&mut context, &vault_key).await;
+        let mut harness = Harness {
+            context,
+            keys: VaultKeys::new(m_protocol::ID, vault_key, &vault, mango),
+            admin,
+            slot,
+        };
+        let ix = instruction::create_mango_account(&harness.keys, 0);
+        harness.process(&[ix], &[]).await.unwrap();
+        harness.refresh_keys().await;
+        harness
+    }
+
+    pub async fn refresh_keys(&mut self) {
+        let vault_key = pda::vault(&m_protocol::ID, VAULT_NAME).0;
+        let vault: Vault = self.load(&vault_key).await;
+        let mango_account = self.mango_account().await;
+        self.keys = VaultKeys::new(m_protocol::ID, vault_key, &vault, self.keys.mango).with_margin_basket(&mango_account);
+    }
+
+    pub async fn add_user(&mut self, amount: u64) -> User {
+        self.add_user_with_limit(amount, USER_LIMIT).await
+    }
+
+    // * a funded wallet holding `amount` of the vault token with its user vault account created
+    pub async fn add_user_with_limit(&mut self, amount: u64, limit: u64) -> User {
+        let wallet = Keypair::new();
+        let token_account = Keypair::new();
+        let rent = self.context.banks_client.get_rent().await.unwrap();
+        let payer = self.context.payer.pubkey();
+        let admin_key = self.admin.pubkey();
+        self.process(
+            &[
+                system_instruction::transfer(&payer, &wallet.pubkey(), 1_000_000_000),
+                system_instruction::create_account(
+                    &payer,
+                    &token_account.pubkey(),
+                    rent.minimum_balance(spl_token::state::Account::LEN),
+                    spl_token::state::Account::LEN as u64,
+                    &spl_token::ID,
+                ),
+                spl_token::instruction::initialize_account(
+                    &spl_token::ID,
+                    &token_account.pubkey(),
+                    &self.keys.token_mint,
+                    &wallet.pubkey(),
+                )
+                .unwrap(),
+                spl_token::instruction::mint_to(&spl_token::ID, &self.keys.token_mint, &token_account.pubkey(), &admin_key, &[], amount)
+                    .unwrap(),
+            ],
+            &[&token_account],
+        )
+        .await
+        .unwrap();
+
+        let user = User {
+            wallet,
+            token_account: token_account.pubkey(),
+        };
+        self.process(
+            &[instruction::create_user_vault_account(&self.keys, &user.pubkey(), None, limit)],
+            &[&user.wallet],
+        )
+        .await
+        .unwrap();
+        user
+    }
+
+    // * deposits into the user vault account and queues a stake of the same amount
+    pub async fn deposit_and_stake(&mut self, user: &User, amount: u64) -> Result<(), BanksClientError> {
+        let keys = self.keys.clone();
+        self.process(
+            &[
+                instruction::deposit_to_user_vault_account(&keys, &user.pubkey(), &user.token_account, None, amount),
+                instruction::request_to_stake(&keys, &user.pubkey(), None, amount, false),
+            ],
+            &[&user.wallet],
+        )
+        .await
+    }
+
+    // * closes the open epoch, the admin is also the vault keeper
+    pub async fn update_vault_balance(&mut self) -> Result<(), BanksClientError> {
+        let ix = instruction::update_vault_balance(&self.keys);
+        self.process(&[ix], &[]).await
+    }
+
+    // * the admin signs along whenever the instructions need it
+    pub async fn process(&mut self, instructions: &[Instruction], signers: &[&Keypair]) -> Result<(), BanksClientError> {
+        self.process_signed(instructions, signers).await.map(|_| ())
+    }
+
+    pub async fn process_signed(&mut self, instructions: &[Instruction], signers: &[&Keypair]) -> Result<Signature, BanksClientError> {
+        let mut all_signers = vec![&self.admin];
+        all_signers.extend_from_slice(signers);
+        send(&mut self.context, &mut self.slot, instructions, &all_signers).await
+    }
+
+    pub async fn load<T: AccountDeserialize>(&mut self, key: &Pubkey) -> T {
+        load(&mut self.context, key).await
+    }
+
+    // * banks never move the clock back, later slots keep the advanced timestamp
+    pub async fn advance_clock(&mut self, seconds: i64) {
+        let mut clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp += seconds;
+        self.context.set_sysvar(&clock);
+    }
+
+    pub async fn vault(&mut self) -> Vault {
+        let key = self.keys.vault;
+        self.load(&key).await
+    }
+
+    pub async fn user_vault_account(&mut self, user: &User) -> UserVaultAccount {
+        let key = self.keys.user_vault_account(&user.pubkey());
+        self.load(&key).await
+    }
+
+    pub async fn token_balance(&mut self, key: &Pubkey) -> u64 {
+        let account = self.context.banks_client.get_account(*key).await.unwrap().unwrap();
+        spl_token::state::Account::unpack(&account.data).unwrap().amount
+    }
+
+    pub async fn share_balance(&mut self, user: &User) -> u64 {
+        let key = self.keys.user_share_token_account(&user.pubkey());
+        self.token_balance(&key).await
+    }
+
+    // * balance of the user's token account held by the vault
+    pub async fn user_token_balance(&mut self, user: &User) -> u64 {
+        let key = self.keys.user_token_account(&user.pubkey());
+        self.token_balance(&key).await
+    }
+
+    pub async fn share_supply(&mut self) -> u64 {
+        let account = self.context.banks_client.get_account(self.keys.share_mint).await.unwrap().unwrap();
+        spl_token::state::Mint::unpack(&account.data).unwrap().supply
+    }
+
+    pub async fn stake_queue(&mut self) -> Vec<RequestEntry> {
+        let key = self.keys.stake_request_account;
+        let account = self.context.banks_client.get_account(key).await.unwrap().unwrap();
+        queued_entries(&decode_stake_req(&key, &account.data).unwrap().queue)
+    }
+
+    pub async fn unstake_queue(&mut self) -> Vec<RequestEntry> {
+        let key = self.keys.unstake_request_account;
+        let account = self.context.banks_client.get_account(key).await.unwrap().unwrap();
+        queued_entries(&decode_unstake_req(&key, &account.data).unwrap().queue)
+    }
+
+    pub async fn stake_queue_len(&mut self) -> usize {
+        self.stake_queue().await.len()
+    }
+
+    pub async fn unstake_queue_len(&mut self) -> usize {
+        self.unstake_queue().await.len()
+    }
+
+    pub async fn mango_account(&mut self) -> MangoAccount {
+        let account = self.context.banks_client.get_account(self.keys.mango_account).await.unwrap().unwrap();
+        bytemuck::pod_read_unaligned(&account.data)
+    }
+
+    // * native quote deposit of the vault's mango account
+    pub async fn mango_deposit(&mut self) -> u64 {
+        self.mango_account().await.deposits[QUOTE_INDEX].to_num()
+    }
+
+    // * books trading pnl on the vault's mango account, gains are minted into the mango vault
+    pub async fn realize_pnl(&mut self, pnl: i64) {
+        let mut mango_account = self.mango_account().await;
+        mango_account.deposits[QUOTE_INDEX] += I80F48::from_num(pnl);
+        let mut account = self.context.banks_client.get_account(self.keys.mango_account).await.unwrap().unwrap();
+        account.data.copy_from_slice(bytemuck::bytes_of(&mango_account));
+        self.context.set_account(&self.keys.mango_account, &AccountSharedData::from(account));
+
+        if pnl > 0 {
+            let ix = spl_token::instruction::mint_to(
+                &spl_token::ID,
+                &self.keys.token_mint,
+                &self.keys.mango.vault,
+                &self.admin.pubkey(),
+                &[],
+                pnl as u64,
+            )
+            .unwrap();
+            self.process(&[ix], &[]).await.unwrap();
+        }
+    }
+}
+
+pub struct User {
+    pub wallet: Keypair,
+    pub token_account: Pubkey, // * wallet's own token account, outside the vault
+}
+
+impl User {
+    pub fn pubkey(&self) -> Pubkey {
+        self.wallet.pubkey()
+    }
+}
+
+pub fn assert_error(result: Result<(), BanksClientError>, error: ErrorCode) {
+    let expected = format!("{:?}", error);
+    match result.unwrap_err().unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => assert_eq!(code, u32::from(error), "expected {}", expected),
+        other => panic!("expected {}, got {:?}", expected, other),
+    }
+}
+
+// * a fresh slot per transaction so repeated instructions get a new blockhash,
+// * signers the transaction does not need are dropped
+async fn send(
+    context: &mut ProgramTestContext,
+    slot: &mut u64,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<Signature, BanksClientError> {
+    *slot += 1;
+    context.warp_to_slot(*slot).unwrap();
+    let blockhash = context.banks_client.get_latest_blockhash().await?;
+
+    let mut transaction = Transaction::new_with_payer(instructions, Some(&context.payer.pubkey()));
+    let required = &transaction.message.account_keys[..transaction.message.header.num_required_signatures as usize];
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend(signers.iter().copied().filter(|signer| required.contains(&signer.pubkey())));
+    transaction.sign(&all_signers, blockhash);
+
+    let signature = transaction.signatures[0];
+    context.banks_client.process_transaction(transaction).await.map(|()| signature)
+}
+
+async fn load<T: AccountDeserialize>(context: &mut ProgramTestContext, key: &Pubkey) -> T {
+    let account = context.banks_client.get_account(*key).await.unwrap().unwrap();
+    T::try_deserialize(&mut &account.data[..]).unwrap()
+}
+
+fn mango_accounts() -> MangoAccounts {
+    let program_id = Pubkey::new_unique();
+    let group = Pubkey::new_unique();
+    MangoAccounts {
+        program_id,
+        group,
+        cache: Pubkey::new_unique(),
+        root_bank: Pubkey::new_unique(),
+        node_bank: Pubkey::new_unique(),
+        vault: Pubkey::new_unique(),
+        signer: signer_key(&program_id, &group).0,
+    }
+}
+
+// * mango signs for its vaults with [group, nonce] and no bump
+fn signer_key(program_id: &Pubkey, group: &Pubkey) -> (Pubkey, u64) {
+    (0..u64::MAX)
+        .find_map(|nonce| {
+            Pubkey::create_program_address(&[group.as_ref(), &nonce.to_le_bytes()], program_id)
+                .ok()
+                .map(|key| (key, nonce))
+        })
+        .unwrap()
+}
+
+// * group and cache with a single quote token at a deposit index of one
+fn add_mango_fixtures(program_test: &mut ProgramTest, mango: &MangoAccounts, mint: &Pubkey) {
+    let mut group: MangoGroup = bytemuck::Zeroable::zeroed();
+    group.meta_data = MetaData::new(DataType::MangoGroup, MANGO_VERSION, true);
+    group.tokens[QUOTE_INDEX].mint = *mint;
+    group.tokens[QUOTE_INDEX].root_bank = mango.root_bank;
+    group.tokens[QUOTE_INDEX].decimals = 6;
+    let (signer_key, signer_nonce) = signer_key(&mango.program_id, &mango.group);
+    group.signer_key = signer_key;
+    group.signer_nonce = signer_nonce;
+    group.mango_cache = mango.cache;
+    program_test.add_account(mango.group, mango_account_data(bytemuck::bytes_of(&group), &mango.program_id));
+
+    let mut cache: MangoCache = bytemuck::Zeroable::zeroed();
+    cache.meta_data = MetaData::new(DataType::MangoCache, MANGO_VERSION, true);
+    cache.root_bank_cache[QUOTE_INDEX].deposit_index = I80F48::ONE;
+    cache.root_bank_cache[QUOTE_INDEX].borrow_index = I80F48::ONE;
+    program_test.add_account(mango.cache, mango_account_data(bytemuck::bytes_of(&cache), &mango.program_id));
+
+    // * zeroed and owned by the mock, initialized by its create instruction
+    let vault_authority = pda::vault_authority(&m_protocol::ID, &pda::vault(&m_protocol::ID, VAULT_NAME).0).0;
+    let mango_account = pda::mango_account(&mango.program_id, &mango.group, &vault_authority, 0).0;
+    program_test.add_account(mango_account, mango_account_data(&vec![0; size_of::<MangoAccount>()], &mango.program_id));
+
+    // * banks are passed through but never read by the mock
+    for bank in [mango.root_bank, mango.node_bank] {
+        program_test.add_account(bank, mango_account_data(&[0; 8], &mango.program_id));
+    }
+    program_test.add_account(
+        mango.vault,
+        packed_account(
+            spl_token::state::Account {
+                mint: *mint,
+                owner: signer_key,
+                state: spl_token::state::AccountState::Initialized,
+                ..spl_token::state::Account::default()
+            },
+            &spl_token::ID,
+        ),
+    );
+}
+
+fn mango_account_data(data: &[u8], owner: &Pubkey) -> Account {
+    Account {
+        lamports: 1_000_000_000,
+        data: data.to_vec(),
+        owner: *owner,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn packed_account<T: Pack>(state: T, owner: &Pubkey) -> Account {
+    let mut data = vec![0; T::LEN];
+    state.pack_into_slice(&mut data);
+    Account {
+        lamports: 1_000_000_000,
+        data,
+        owner: *owner,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn program_account<T: AccountSerialize>(state: &T, space: usize) -> Account {
+    let mut data = Vec::new();
+    state.try_serialize(&mut data).unwrap();
+    data.resize(space, 0);
+    Account {
+        lamports: 1_000_000_000,
+        data,
+        owner: m_protocol::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
@@ -0,0 +1,522 @@
+mod common;
+
+use common::{assert_error, Harness};
+use m_protocol::constants::{MAX_LOCKUP_SECONDS, MAX_UNSTAKE_COOLDOWN_SECONDS};
+use m_protocol::error::ErrorCode;
+use m_protocol::state::{HaltReason, PenaltyRecipient, RequestStatus};
+use merstab_client::instruction;
+use solana_sdk::pubkey::Pubkey;
+
+#[tokio::test]
+async fn stake_and_unstake_round_trip() {
+    let mut harness = Harness::new().await;
+    let user = harness.add_user(1_000_000_000).await;
+
+    harness.deposit_and_stake(&user, 400_000_000).await.unwrap();
+    assert_eq!(harness.stake_queue_len().await, 1);
+    assert_eq!(harness.token_balance(&user.token_account).await, 600_000_000);
+
+    harness.update_vault_balance().await.unwrap();
+    let ix = instruction::process_stake(&harness.keys, &user.pubkey());
+    harness.process(&[ix], &[]).await.unwrap();
+
+    assert_eq!(harness.stake_queue_len().await, 0);
+    assert_eq!(harness.mango_deposit().await, 400_000_000);
+    assert_eq!(harness.share_balance(&user).await, 400_000_000);
+    assert_eq!(harness.vault().await.total_equity, 400_000_000);
+    assert_eq!(harness.user_vault_account(&user).await.equity, 400_000_000);
+
+    // * 10% on mango, picked up by the next balance update
+    harness.realize_pnl(40_000_000).await;
+    harness.update_vault_balance().await.unwrap();
+    let ix = instruction::update_user_balance(&harness.keys, &user.pubkey());
+    harness.process(&[ix], &[]).await.unwrap();
+
+    assert_eq!(harness.vault().await.total_equity, 440_000_000);
+    assert_eq!(harness.user_vault_account(&user).await.equity, 440_000_000);
+
+    let ix = instruction::request_to_unstake(&harness.keys, &user.pubkey(), 0, true);
+    harness.process(&[ix], &[&user.wallet]).await.unwrap();
+    harness.update_vault_balance().await.unwrap();
+    let ix = instruction::process_unstake(&harness.keys, &user.pubkey());
+    harness.process(&[ix], &[]).await.unwrap();
+
+    let user_token_account = harness.keys.user_token_account(&user.pubkey());
+    assert_eq!(harness.unstake_queue_len().await, 0);
+    assert_eq!(harness.share_balance(&user).await, 0);
+    assert_eq!(harness.mango_deposit().await, 0);
+    assert_eq!(harness.token_balance(&user_token_account).await, 440_000_000);
+    assert_eq!(harness.vault().await.total_equity, 0);
+
+    let ix = instruction::withdraw_from_user_vault_account(&harness.keys, &user.pubkey(), &user.token_account, 440_000_000);
+    harness.process(&[ix], &[&user.wallet]).await.unwrap();
+    assert_eq!(harness.token_balance(&user.token_account).await, 1_040_000_000);
+}
+
+#[tokio::test]
+async fn batches_settle_every_request_of_the_closed_epoch() {
+    let mut harness = Harness::new().await;
+    let first = harness.add_user(100_000_000).await;
+    let second = harness.add_user(300_000_000).await;
+    harness.deposit_and_stake(&first, 100_000_000).await.unwrap();
+    harness.deposit_and_stake(&second, 300_000_000).await.unwrap();
+
+    harness.update_vault_balance().await.unwrap();
+    let ix = instruction::process_stake_batch(&harness.keys, &[first.pubkey(), second.pubkey()]);
+    harness.process(&[ix], &[]).await.unwrap();
+
+    assert_eq!(harness.stake_queue_len().await, 0);
+    assert_eq!(harness.mango_deposit().await, 400_000_000);
+    assert_eq!(harness.share_balance(&first).await, 100_000_000);
+    assert_eq!(harness.share_balance(&second).await, 300_000_000);
+    assert_eq!(harness.vault().await.total_equity, 400_000_000);
+
+    for user in [&first, &second] {
+        let ix = instruction::request_to_unstake(&harness.keys, &user.pubkey(), 0, true);
+        harness.process(&[ix], &[&user.wallet]).await.unwrap();
+    }
+    harness.update_vault_balance().await.unwrap();
+    let ix = instruction::process_unstake_batch(&harness.keys, &[first.pubkey(), second.pubkey()]);
+    harness.process(&[ix], &[]).await.unwrap();
+
+    assert_eq!(harness.unstake_queue_len().await, 0);
+    assert_eq!(harness.mango_deposit().await, 0);
+    for (user, amount) in [(&first, 100_000_000), (&second, 300_000_000)] {
+        let user_token_account = harness.keys.user_token_account(&user.pubkey());
+        assert_eq!(harness.token_balance(&user_token_account).await, amount);
+        assert_eq!(harness.share_balance(user).await, 0);
+    }
+}
+
+#[tokio::test]
+async fn unstakes_pay_out_after_losses() {
+    let mut harness = Harness::new().await;
+    let user = harness.add_user(400_000_000).await;
+    harness.deposit_and_stake(&user, 400_000_000).await.unwrap();
+    harness.update_vault_balance().await.unwrap();
+    let ix = instruction::process_stake(&harness.keys, &user.pubkey());
+    harness.process(&[ix], &[]).await.unwrap();
+
+    harness.realize_pnl(-100_000_000).await;
+    let ix = instruction::request_to_unstake(&harness.keys, &user.pubkey(), 0, true);
+    harness.process(&[ix], &[&user.wallet]).await.unwrap();
+    harness.update_vault_balance().await.unwrap();
+    let ix = instruction::process_unstake(&harness.keys, &user.pubkey());
+    harness.process(&[ix], &[]).await.unwrap();
+
+    let user_token_account = harness.keys.user_token_account(&user.pubkey());
+    assert_eq!(harness.token_balance(&user_token_account).await, 300_000_000);
+    assert_eq!(harness.mango_deposit().await, 0);
+    assert_eq!(harness.vault().await.total_equity, 0);
+}
+
+#[tokio::test]
+async fn requests_settle_only_once_their_epoch_closed() {
+    let mut harness = Harness::new().await;
+    let user = harness.add_user(100_000_000).await;
+    harness.deposit_and_stake(&user, 100_000_000).await.unwrap();
+
+    let ix = instruction::process_stake(&harness.keys, &user.pubkey());
+    assert_error(harness.process(&[ix], &[]).await, ErrorCode::EpochNotSettled);
+
+    // * the closed epoch has to settle before the next one closes
+    harness.update_vault_balance().await.unwrap();
+    assert_error(harness.update_vault_balance().await, ErrorCode::UnsettledRequests);
+
+    let ix = instruction::process_stake(&harness.keys, &user.pubkey());
+    harness.process(&[ix], &[]).await.unwrap();
+    harness.update_vault_balance().await.unwrap();
+    assert_eq!(harness.vault().await.epoch.current, 2);
+}
+
+#[tokio::test]
+async fn unstakes_held_by_the_cooldown_record_the_epoch_they_settle_at() {
+    let mut harness = Harness::new().await;
+    let user = harness.add_user(100_000_000).await;
+    harness.deposit_and_stake(&user, 100_000_000).await.unwrap();
+    harness.update_vault_balance().await.unwrap();
+    let ix = instruction::process_stake(&harness.keys, &user.pubkey());
+    harness.process(&[ix], &[]).await.unwrap();
+
+    let ix = instruction::update_lockup(&harness.keys, 0, 3_600);
+    harness.process(&[ix], &[]).await.unwrap();
+    let ix = instruction::request_to_unstake(&harness.keys, &user.pubkey(), 0, true);
+    harness.process(&[ix], &[&user.wallet]).await.unwrap();
+
+    // * two epochs close while the cooldown holds the request, it settles at the later price
+    harness.update_vault_balance().await.unwrap();
+    let ix = instruction::process_unstake(&harness.keys, &user.pubkey());
+    assert_error(harness.process(&[ix], &[]).await, ErrorCode::UnstakeCooldownActive);
+
+    harness.realize_pnl(10_000_000).await;
+    harness.update_vault_balance().await.unwrap();
+    harness.advance_clock(3_600).await;
+    let ix = instruction::process_unstake(&harness.keys, &user.pubkey());
+    harness.process(&[ix], &[]).await.unwrap();
+
+    let vault = harness.vault().await;
+    let unstake = harness.user_vault_account(&user).await.user_unstake;
+    assert_eq!(unstake.epoch, 1);
+    assert_eq!(unstake.settled_epoch, 2);
+    assert_eq!(unstake.settled_price, vault.epoch.settlement_price);
+}
+
+#[tokio::test]
+async fn queued_unstakes_keep_the_cooldown_they_were_requested_under() {
+    let mut harness = Harness::new().await;
+    let user = harness.add_user(100_000_000).await;
+    harness.deposit_and_stake(&user, 100_000_000).await.unwrap();
+    harness.update_vault_balance().await.unwrap();
+    let ix = instruction::process_stake(&harness.keys, &user.pubkey());
+    harness.process(&[ix], &[]).await.unwrap();
+
+    let ix = instruction::update_lockup(&harness.keys, 0, 3_600);
+    harness.process(&[ix], &[]).await.unwrap();
+    let ix = instruction::request_to_unstake(&harness.keys, &user.pubkey(), 0, true);
+    harness.process(&[ix], &[&user.wallet]).await.unwrap();
+
+    let ix = instruction::update_lockup(&harness.keys, 0, MAX_UNSTAKE_COOLDOWN_SECONDS);
+    harness.process(&[ix], &[]).await.unwrap();
+    harness.update_vault_balance().await.unwrap();
+    harness.advance_clock(3_600).await;
+    let ix = instruction::process_unstake(&harness.keys, &user.pubkey());
+    harness.process(&[ix], &[]).await.unwrap();
+    assert_eq!(harness.share_balance(&user).await, 0);
+
+    let ix = instruction::update_lockup(&harness.keys, 0, MAX_UNSTAKE_COOLDOWN_SECONDS + 1);
+    assert_error(harness.process(&[ix], &[]).await, ErrorCode::InvalidLockupPeriod);
+    let ix = instruction::update_lockup(&harness.keys, MAX_LOCKUP_SECONDS + 1, 0);
+    assert_error(harness.process(&[ix], &[]).await, ErrorCode::InvalidLockupPeriod);
+}
+
+#[tokio::test]
+async fn updated_unstakes_cool_down_again_without_holding_back_the_queue() {
+    let mut harness = Harness::new().await;
+    let first = harness.add_user(100_000_000).await;
+    let second = harness.add_user(100_000_000).await;
+    harness.deposit_and_stake(&first, 100_000_000).await.unwrap();
+    harness.deposit_and_stake(&second, 100_000_000).await.unwrap();
+    harness.update_vault_balance().await.unwrap();
+    let ix = instruction::process_stake_batch(&harness.keys, &[first.pubkey(), second.pubkey()]);
+    harness.process(&[ix], &[]).await.unwrap();
+
+    let ix = instruction::update_lockup(&harness.keys, 0, 3_600);
+    harness.process(&[ix], &[]).await.unwrap();
+    for user in [&first, &second] {
+        let ix = instruction::request_to_unstake(&harness.keys, &user.pubkey(), 0, true);
+        harness.process(&[ix], &[&user.wallet]).await.unwrap();
+    }
+
+    // * the head re-times its request, it moves behind the one that has cooled down
+    harness.advance_clock(3_600).await;
+    let ix = instruction::update_unstake_request(&harness.keys, &first.pubkey(), 50_000_000, false, false);
+    harness.process(&[ix], &[&first.wallet]).await.unwrap();
+    let second_account = harness.keys.user_vault_account(&second.pubkey());
+    assert_eq!(harness.unstake_queue().await[0].user_vault_account, second_account);
+
+    harness.update_vault_balance().await.unwrap();
+    let ix = instruction::process_unstake_batch(&harness.keys, &[first.pubkey(), second.pubkey()]);
+    harness.process(&[ix], &[]).await.unwrap();
+
+    let queue = harness.unstake_queue().await;
+    assert_eq!(queue.len(), 1);
+    assert_eq!(queue[0].user_vault_account, harness.keys.user_vault_account(&first.pubkey()));
+    assert_eq!(harness.share_balance(&second).await, 0);
+    assert_eq!(harness.share_balance(&first).await, 100_000_000);
+    harness.update_vault_balance().await.unwrap();
+}
+
+#[tokio::test]
+async fn unstakes_are_turned_away_when_a_later_stake_restarts_the_lockup() {
+    let mut harness = Harness::new().await;
+    let user = harness.add_user(200_000_000).await;
+    harness.deposit_and_stake(&user, 100_000_000).await.unwrap();
+    harness.update_vault_balance().await.unwrap();
+    let ix = instruction::process_stake(&harness.keys, &user.pubkey());
+    harness.process(&[ix], &[]).await.unwrap();
+
+    let ix = instruction::update_lockup(&harness.keys, 3_600, 0);
+    harness.process(&[ix], &[]).await.unwrap();
+    harness.advance_clock(3_600).await;
+    let ix = instruction::request_to_unstake(&harness.keys, &user.pubkey(), 0, true);
+    harness.process(&[ix], &[&user.wallet]).await.unwrap();
+    harness.deposit_and_stake(&user, 50_000_000).await.unwrap();
+
+    harness.update_vault_balance().await.unwrap();
+    let ix = instruction::process_stake(&harness.keys, &user.pubkey());
+    harness.process(&[ix], &[]).await.unwrap();
+    let ix = instruction::process_unstake(&harness.keys, &user.pubkey());
+    harness.process(&[ix], &[]).await.unwrap();
+
+    let unstake = harness.user_vault_account(&user).await.user_unstake;
+    assert!(unstake.status == RequestStatus::LockupActive);
+    assert!(!unstake.unstake_request_active);
+    assert_eq!(harness.unstake_queue_len().await, 0);
+    assert_eq!(harness.share_balance(&user).await, 150_000_000);
+}
+
+#[tokio::test]
+async fn clearing_a_queue_leaves_live_requests_in_it() {
+    let mut harness = Harness::new().await;
+    let user = harness.add_user(100_000_000).await;
+    let other = harness.add_user(100_000_000).await;
+    harness.deposit_and_stake(&user, 100_000_000).await.unwrap();
+
+    let user_vault_account = harness.keys.user_vault_account(&user.pubkey());
+    let ix = instruction::clear_stake_request(&harness.keys, &[user_vault_account]);
+    harness.process(&[ix], &[]).await.unwrap();
+    assert_eq!(harness.stake_queue_len().await, 1);
+    assert!(harness.user_vault_account(&user).await.user_stake.stake_request_active);
+
+    let ix = instruction::clear_stake_request(&harness.keys, &[harness.keys.user_vault_account(&other.pubkey())]);
+    assert_error(harness.process(&[ix], &[]).await, ErrorCode::RequestNotQueued);
+}
+
+#[tokio::test]
+async fn unfunded_stakes_leave_the_queue() {
+    let mut harness = Harness::new().await;
+    let user = harness.add_user(100_000_000).await;
+    harness.deposit_and_stake(&user, 100_000_000).await.unwrap();
+    let ix = instruction::withdraw_from_user_vault_account(&harness.keys, &user.pubkey(), &user.token_account, 50_000_000);
+    harness.process(&[ix], &[&user.wallet]).await.unwrap();
+
+    harness.update_vault_balance().await.unwrap();
+    let ix = instruction::process_stake(&harness.keys, &user.pubkey());
+    harness.process(&[ix], &[]).await.unwrap();
+
+    assert_eq!(harness.stake_queue_len().await, 0);
+    assert_eq!(harness.share_balance(&user).await, 0);
+    assert!(harness.user_vault_account(&user).await.user_stake.status == RequestStatus::InsufficientBalance);
+    harness.update_vault_balance().await.unwrap();
+}
+
+#[tokio::test]
+async fn unstakes_beyond_the_shares_held_leave_the_queue() {
+    let mut harness = Harness::new().await;
+    let user = harness.add_user(100_000_000).await;
+    harness.deposit_and_stake(&user, 100_000_000).await.unwrap();
+    harness.update_vault_balance().await.unwrap();
+    let ix = instruction::process_stake(&harness.keys, &user.pubkey());
+    harness.process(&[ix], &[]).await.unwrap();
+
+    let ix = instruction::request_to_unstake(&harness.keys, &user.pubkey(), 200_000_000, false);
+    harness.process(&[ix], &[&user.wallet]).await.unwrap();
+    harness.update_vault_balance().await.unwrap();
+    let ix = instruction::process_unstake(&harness.keys, &user.pubkey());
+    harness.process(&[ix], &[]).await.unwrap();
+
+    let unstake = harness.user_vault_account(&user).await.user_unstake;
+    assert!(unstake.status == RequestStatus::InsufficientBalance);
+    assert!(!unstake.unstake_request_active);
+    assert_eq!(harness.unstake_queue_len().await, 0);
+    assert_eq!(harness.share_balance(&user).await, 100_000_000);
+    harness.update_vault_balance().await.unwrap();
+}
+
+#[tokio::test]
+async fn denied_wallets_cannot_raise_or_settle_queued_stakes() {
+    let mut harness = Harness::new().await;
+    let first = harness.add_user(200_000_000).await;
+    let second = harness.add_user(200_000_000).await;
+    let third = harness.add_user(200_000_000).await;
+    for user in [&first, &second, &third] {
+        harness.deposit_and_stake(user, 100_000_000).await.unwrap();
+    }
+    for user in [&first, &second] {
+        let ix = instruction::add_access_entry(&harness.keys, user.pubkey(), false, true);
+        harness.process(&[ix], &[]).await.unwrap();
+    }
+
+    let ix = instruction::update_stake_request(&harness.keys, &first.pubkey(), 200_000_000, false, false);
+    assert_error(harness.process(&[ix], &[&first.wallet]).await, ErrorCode::WalletDenied);
+
+    harness.update_vault_balance().await.unwrap();
+    let ix = instruction::process_stake(&harness.keys, &first.pubkey());
+    harness.process(&[ix], &[]).await.unwrap();
+    let ix = instruction::process_stake_batch(&harness.keys, &[second.pubkey(), third.pubkey()]);
+    harness.process(&[ix], &[]).await.unwrap();
+
+    assert_eq!(harness.stake_queue_len().await, 0);
+    for user in [&first, &second] {
+        assert_eq!(harness.share_balance(user).await, 0);
+        assert!(harness.user_vault_account(user).await.user_stake.status == RequestStatus::Cancelled);
+    }
+    assert_eq!(harness.share_balance(&third).await, 100_000_000);
+}
+
+#[tokio::test]
+async fn a_wiped_out_vault_halts_instead_of_pricing_shares_at_zero() {
+    let mut harness = Harness::new().await;
+    let user = harness.add_user(100_000_000).await;
+    harness.deposit_and_stake(&user, 100_000_000).await.unwrap();
+    harness.update_vault_balance().await.unwrap();
+    let ix = instruction::process_stake(&harness.keys, &user.pubkey());
+    harness.process(&[ix], &[]).await.unwrap();
+
+    harness.realize_pnl(-100_000_000).await;
+    harness.update_vault_balance().await.unwrap();
+
+    let vault = harness.vault().await;
+    assert!(vault.is_halted() && vault.halt_reason == HaltReason::ZeroSharePrice);
+    let ix = instruction::clear_halt(&harness.keys);
+    assert_error(harness.process(&[ix], &[]).await, ErrorCode::ZeroSharePrice);
+}
+
+#[tokio::test]
+async fn delegates_the_mango_account() {
+    let mut harness = Harness::new().await;
+    let delegate = Pubkey::new_unique();
+
+    let ix = instruction::delegate_mango_account(&harness.keys, &delegate);
+    harness.process(&[ix], &[]).await.unwrap();
+
+    assert_eq!(harness.mango_account().await.delegate, delegate);
+}
+
+#[tokio::test]
+async fn revokes_the_mango_delegate() {
+    let mut harness = Harness::new().await;
+    let ix = instruction::delegate_mango_account(&harness.keys, &Pubkey::new_unique());
+    harness.process(&[ix], &[]).await.unwrap();
+
+    let ix = instruction::revoke_mango_delegate(&harness.keys, &harness.keys.admin);
+    harness.process(&[ix], &[]).await.unwrap();
+    assert_eq!(harness.mango_account().await.delegate, Pubkey::default());
+
+    // * users of the vault cannot revoke
+    let user = harness.add_user(1_000_000).await;
+    let ix = instruction::revoke_mango_delegate(&harness.keys, &user.pubkey());
+    assert_error(harness.process(&[ix], &[&user.wallet]).await, ErrorCode::NotDelegateAuthority);
+}
+
+#[tokio::test]
+async fn risk_limited_or_halted_vaults_cannot_delegate() {
+    let mut harness = Harness::new().await;
+    let ix = instruction::initialize_risk_params(&harness.keys, 1, 30_000, 1_000);
+    harness.process(&[ix], &[]).await.unwrap();
+
+    let ix = instruction::delegate_mango_account(&harness.keys, &Pubkey::new_unique());
+    assert_error(harness.process(&[ix], &[]).await, ErrorCode::DelegationNotAllowed);
+
+    let mut harness = Harness::new().await;
+    let user = harness.add_user(100_000_000).await;
+    harness.deposit_and_stake(&user, 100_000_000).await.unwrap();
+    harness.update_vault_balance().await.unwrap();
+    let ix = instruction::process_stake(&harness.keys, &user.pubkey());
+    harness.process(&[ix], &[]).await.unwrap();
+    harness.realize_pnl(-100_000_000).await;
+    harness.update_vault_balance().await.unwrap();
+
+    let ix = instruction::delegate_mango_account(&harness.keys, &Pubkey::new_unique());
+    assert_error(harness.process(&[ix], &[]).await, ErrorCode::VaultHalted);
+}
+
+#[tokio::test]
+async fn risk_params_revoke_a_delegate_set_before_them() {
+    let mut harness = Harness::new().await;
+    let ix = instruction::delegate_mango_account(&harness.keys, &Pubkey::new_unique());
+    harness.process(&[ix], &[]).await.unwrap();
+
+    let ix = instruction::initialize_risk_params(&harness.keys, 1, 30_000, 1_000);
+    harness.process(&[ix], &[]).await.unwrap();
+    assert_eq!(harness.mango_account().await.delegate, Pubkey::default());
+}
+
+#[tokio::test]
+async fn halting_revokes_the_mango_delegate() {
+    let mut harness = Harness::new().await;
+    let user = harness.add_user(100_000_000).await;
+    harness.deposit_and_stake(&user, 100_000_000).await.unwrap();
+    harness.update_vault_balance().await.unwrap();
+    let ix = instruction::process_stake(&harness.keys, &user.pubkey());
+    harness.process(&[ix], &[]).await.unwrap();
+
+    let ix = instruction::delegate_mango_account(&harness.keys, &Pubkey::new_unique());
+    harness.process(&[ix], &[]).await.unwrap();
+
+    harness.realize_pnl(-100_000_000).await;
+    harness.update_vault_balance().await.unwrap();
+    assert!(harness.vault().await.halt_reason == HaltReason::ZeroSharePrice);
+    assert_eq!(harness.mango_account().await.delegate, Pubkey::default());
+}
+
+#[tokio::test]
+async fn balance_updates_only_read_the_vaults_own_mango_account() {
+    let mut harness = Harness::new().await;
+    let vault = harness.vault().await;
+    assert_eq!(vault.mango_program_id, harness.keys.mango.program_id);
+    assert_eq!(vault.mango_group, harness.keys.mango.group);
+
+    let mut ix = instruction::update_vault_balance(&harness.keys);
+    for account in ix.accounts.iter_mut().filter(|account| account.pubkey == harness.keys.mango.program_id) {
+        account.pubkey = Pubkey::new_unique();
+    }
+    assert_error(harness.process(&[ix], &[]).await, ErrorCode::MangoProgramNotMatch);
+
+    let mut ix = instruction::update_vault_balance(&harness.keys);
+    for account in ix.accounts.iter_mut().filter(|account| account.pubkey == harness.keys.mango_account) {
+        account.pubkey = Pubkey::new_unique();
+    }
+    assert!(harness.process(&[ix], &[]).await.is_err());
+
+    harness.update_vault_balance().await.unwrap();
+}
+
+#[tokio::test]
+async fn early_withdrawal_penalties_only_charge_stake_inside_the_penalty_period() {
+    let mut harness = Harness::new().await;
+    let ix = instruction::update_withdrawal_penalty(&harness.keys, 1_000, 86_400, PenaltyRecipient::Stakers);
+    harness.process(&[ix], &[]).await.unwrap();
+
+    let user = harness.add_user(110_000_000).await;
+    harness.deposit_and_stake(&user, 100_000_000).await.unwrap();
+    harness.update_vault_balance().await.unwrap();
+    let ix = instruction::process_stake(&harness.keys, &user.pubkey());
+    harness.process(&[ix], &[]).await.unwrap();
+
+    // * the first stake ages out of the penalty period, only the second is still inside it
+    harness.advance_clock(86_400).await;
+    harness.deposit_and_stake(&user, 10_000_000).await.unwrap();
+    harness.update_vault_balance().await.unwrap();
+    let ix = instruction::process_stake(&harness.keys, &user.pubkey());
+    harness.process(&[ix], &[]).await.unwrap();
+    assert_eq!(harness.user_vault_account(&user).await.penalized_stake, 10_000_000);
+
+    // * settled stake leaves first, 5_000_000 of the unstake comes out of the fresh stake
+    let ix = instruction::request_to_unstake(&harness.keys, &user.pubkey(), 105_000_000, false);
+    harness.process(&[ix], &[&user.wallet]).await.unwrap();
+    harness.update_vault_balance().await.unwrap();
+    let ix = instruction::process_unstake(&harness.keys, &user.pubkey());
+    harness.process(&[ix], &[]).await.unwrap();
+
+    let user_token_account = harness.keys.user_token_account(&user.pubkey());
+    let penalty = 105_000_000 - harness.token_balance(&user_token_account).await;
+    assert!(penalty > 0 && penalty <= 500_000);
+
+    // * recorded on the partial unstake, before the position closes
+    let user_vault_account = harness.user_vault_account(&user).await;
+    assert!(user_vault_account.equity > 0);
+    assert_eq!(user_vault_account.penalized_stake, 5_000_000);
+    assert_eq!(user_vault_account.user_total_penalty, penalty);
+    assert_eq!(user_vault_account.last_trade_stat.penalty_paid, penalty);
+}
+
+#[tokio::test]
+async fn insurance_injections_lapse_once_an_epoch_closes_without_the_loss() {
+    let mut harness = Harness::new().await;
+    let ix = instruction::update_insurance_params(&harness.keys, 0, 1_000);
+    harness.process(&[ix], &[]).await.unwrap();
+
+    let user = harness.add_user(100_000_000).await;
+    harness.deposit_and_stake(&user, 100_000_000).await.unwrap();
+    harness.update_vault_balance().await.unwrap();
+    let ix = instruction::process_stake(&harness.keys, &user.pubkey());
+    harness.process(&[ix], &[]).await.unwrap();
+
+    harness.realize_pnl(-20_000_000).await;
+    harness.update_vault_balance().await.unwrap();
+    assert!(harness.vault().await.insurance_injection_allowed);
+
+    harness.update_vault_balance().await.unwrap();
+    assert!(!harness.vault().await.insurance_injection_allowed);
+}
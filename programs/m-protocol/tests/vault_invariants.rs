@@ -0,0 +1,299 @@
+mod common;
+
+use anchor_lang::AnchorDeserialize;
+use common::{Harness, User};
+use m_protocol::constants::SHARE_PRICE_SCALE;
+use m_protocol::state::Vault;
+use merstab_client::instruction;
+use proptest::collection::vec;
+use proptest::prelude::*;
+use solana_sdk::pubkey::Pubkey;
+
+const USERS: usize = 3;
+const WALLET: u64 = 1_000_000_000;
+const USER_CAP: u64 = 600_000_000; // * below the wallet so deposits run into it
+const VAULT_CAP: u64 = 1_500_000_000; // * below the sum of user caps
+
+// * every field zeroed, as anchor sees the account before create_vault writes it
+fn vault(total_equity: u64, settlement_price: u64) -> Vault {
+    let mut vault = Vault::deserialize(&mut &vec![0; Vault::LEN][..]).unwrap();
+    vault.total_equity = total_equity;
+    vault.epoch.settlement_price = settlement_price;
+    vault
+}
+
+proptest! {
+    #[test]
+    fn minted_shares_never_redeem_for_more_than_staked(
+        amount in 0..u64::MAX >> 16,
+        price in SHARE_PRICE_SCALE / 100..100 * SHARE_PRICE_SCALE,
+    ) {
+        let vault = vault(0, price);
+        let shares = vault.calculate_shares_to_mint(amount).unwrap();
+        prop_assert!(vault.calculate_settlement_amount(shares).unwrap() <= amount);
+    }
+
+    #[test]
+    fn burned_shares_always_cover_the_unstake(
+        amount in 0..u64::MAX >> 16,
+        price in SHARE_PRICE_SCALE / 100..100 * SHARE_PRICE_SCALE,
+    ) {
+        let vault = vault(0, price);
+        let shares = vault.calculate_shares_to_burn(amount).unwrap();
+        prop_assert!(vault.calculate_settlement_amount(shares).unwrap() >= amount);
+    }
+
+    #[test]
+    fn user_equity_sums_to_vault_equity(
+        total_equity in 0..u64::MAX >> 16,
+        shares in vec(0..u64::MAX >> 24, 1..8),
+    ) {
+        let vault = vault(total_equity, SHARE_PRICE_SCALE);
+        let supply: u64 = shares.iter().sum();
+        let equity: u64 = shares.iter().map(|shares| vault.calculate_equity(*shares, supply).unwrap()).sum();
+
+        if supply == 0 {
+            prop_assert_eq!(equity, 0);
+        } else {
+            prop_assert!(equity <= total_equity);
+            prop_assert!(total_equity - equity < shares.len() as u64);
+        }
+    }
+
+    #[test]
+    fn share_price_never_overvalues_the_supply(
+        equity in 0..u64::MAX >> 24,
+        supply in 1_000..u64::MAX >> 16,
+    ) {
+        let price = Vault::calculate_share_price(equity, supply).unwrap();
+        prop_assert!(vault(equity, price).calculate_settlement_amount(supply).unwrap() <= equity);
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Op {
+    Deposit { user: usize, amount: u64 },
+    Withdraw { user: usize, amount: u64 },
+    RequestStake { user: usize, amount: u64 },
+    RequestUnstake { user: usize },
+    CancelStake { user: usize },
+    CancelUnstake { user: usize },
+    Pnl { bps: i64 },
+    CloseEpoch,
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        4 => (0..USERS, 1..WALLET / 2).prop_map(|(user, amount)| Op::Deposit { user, amount }),
+        2 => (0..USERS, 1..WALLET / 2).prop_map(|(user, amount)| Op::Withdraw { user, amount }),
+        4 => (0..USERS, 1..WALLET / 2).prop_map(|(user, amount)| Op::RequestStake { user, amount }),
+        2 => (0..USERS).prop_map(|user| Op::RequestUnstake { user }),
+        1 => (0..USERS).prop_map(|user| Op::CancelStake { user }),
+        1 => (0..USERS).prop_map(|user| Op::CancelUnstake { user }),
+        2 => (-3_000..3_000i64).prop_map(|bps| Op::Pnl { bps }),
+        3 => Just(Op::CloseEpoch),
+    ]
+}
+
+// * what each user put in and took out, tracked by the test rather than read from the program
+#[derive(Default)]
+struct Ledger {
+    deposited: u64,
+    withdrawn: u64,
+    pnl_share: i128, // * mango pnl allotted by shares held when a balance update recognized it
+}
+
+struct Run {
+    harness: Harness,
+    users: Vec<User>,
+    ledgers: Vec<Ledger>,
+    closed_epochs: u64,
+}
+
+impl Run {
+    async fn new() -> Self {
+        let mut harness = Harness::new().await;
+        let ix = instruction::update_vault_limit(&harness.keys, VAULT_CAP);
+        harness.process(&[ix], &[]).await.unwrap();
+
+        let mut users = Vec::new();
+        for _ in 0..USERS {
+            users.push(harness.add_user_with_limit(WALLET, USER_CAP).await);
+        }
+        Run {
+            harness,
+            users,
+            ledgers: (0..USERS).map(|_| Ledger::default()).collect(),
+            closed_epochs: 0,
+        }
+    }
+
+    async fn apply(&mut self, op: &Op) -> Result<(), TestCaseError> {
+        let keys = self.harness.keys.clone();
+        match *op {
+            Op::Deposit { user, amount } => {
+                let wallet = self.users[user].pubkey();
+                let room = USER_CAP - self.ledgers[user].deposited;
+                let vault_room = VAULT_CAP - self.ledgers.iter().map(|ledger| ledger.deposited).sum::<u64>();
+                let ix = instruction::deposit_to_user_vault_account(&keys, &wallet, &self.users[user].token_account, None, amount);
+                let result = self.harness.process(&[ix], &[&self.users[user].wallet]).await;
+
+                if amount > room || amount > vault_room {
+                    prop_assert!(result.is_err(), "deposit of {} bypassed the limits", amount);
+                }
+                if result.is_ok() {
+                    self.ledgers[user].deposited += amount;
+                }
+            }
+            Op::Withdraw { user, amount } => {
+                let wallet = self.users[user].pubkey();
+                let balance = self.harness.user_token_balance(&self.users[user]).await;
+                let ix = instruction::withdraw_from_user_vault_account(&keys, &wallet, &self.users[user].token_account, amount);
+                let result = self.harness.process(&[ix], &[&self.users[user].wallet]).await;
+
+                prop_assert_eq!(result.is_ok(), amount <= balance, "withdrawal of {} from {}", amount, balance);
+                if result.is_ok() {
+                    self.ledgers[user].withdrawn += amount;
+                }
+            }
+            // * requests the program rejects are fine, the invariants must hold either way
+            Op::RequestStake { user, amount } => {
+                let ix = instruction::request_to_stake(&keys, &self.users[user].pubkey(), None, amount, false);
+                let _ = self.harness.process(&[ix], &[&self.users[user].wallet]).await;
+            }
+            Op::RequestUnstake { user } => {
+                let ix = instruction::request_to_unstake(&keys, &self.users[user].pubkey(), 0, true);
+                let _ = self.harness.process(&[ix], &[&self.users[user].wallet]).await;
+            }
+            Op::CancelStake { user } => {
+                let ix = instruction::update_stake_request(&keys, &self.users[user].pubkey(), 0, false, true);
+                let _ = self.harness.process(&[ix], &[&self.users[user].wallet]).await;
+            }
+            Op::CancelUnstake { user } => {
+                let ix = instruction::update_unstake_request(&keys, &self.users[user].pubkey(), 0, true, true);
+                let _ = self.harness.process(&[ix], &[&self.users[user].wallet]).await;
+            }
+            Op::Pnl { bps } => {
+                let deposit = self.harness.mango_deposit().await as i64;
+                self.harness.realize_pnl(deposit * bps / 10_000).await;
+            }
+            Op::CloseEpoch => self.close_epoch().await?,
+        }
+        self.check_invariants().await
+    }
+
+    // * one keeper cycle, the balance update then every request of the closed epoch
+    async fn close_epoch(&mut self) -> Result<(), TestCaseError> {
+        let keys = self.harness.keys.clone();
+
+        let previous_equity = self.harness.vault().await.total_equity;
+        let supply = self.harness.share_supply().await;
+        let mut shares = Vec::new();
+        for user in &self.users {
+            shares.push(self.harness.share_balance(user).await);
+        }
+
+        self.harness.update_vault_balance().await.unwrap();
+        self.closed_epochs += 1;
+
+        let recognized = self.harness.vault().await.total_equity as i128 - previous_equity as i128;
+        if supply > 0 {
+            for (ledger, shares) in self.ledgers.iter_mut().zip(shares) {
+                ledger.pnl_share += (recognized * shares as i128).div_euclid(supply as i128);
+            }
+        }
+
+        for entry in self.harness.stake_queue().await {
+            let user = self.users[self.user_index(entry.user_vault_account)].pubkey();
+            let ix = instruction::process_stake(&keys, &user);
+            self.harness.process(&[ix], &[]).await.unwrap();
+        }
+        for entry in self.harness.unstake_queue().await {
+            let user = self.users[self.user_index(entry.user_vault_account)].pubkey();
+            let ix = instruction::process_unstake(&keys, &user);
+            self.harness.process(&[ix], &[]).await.unwrap();
+        }
+        for user in &self.users {
+            let ix = instruction::update_user_balance(&keys, &user.pubkey());
+            self.harness.process(&[ix], &[]).await.unwrap();
+        }
+
+        // * refreshed user equity adds up to the vault's, each share of it rounded down
+        let total_equity = self.harness.vault().await.total_equity;
+        let mut user_equity = 0;
+        for user in &self.users {
+            user_equity += self.harness.user_vault_account(user).await.equity;
+        }
+        if self.harness.share_supply().await > 0 {
+            prop_assert!(user_equity <= total_equity);
+            prop_assert!(total_equity - user_equity < USERS as u64);
+        }
+        Ok(())
+    }
+
+    async fn check_invariants(&mut self) -> Result<(), TestCaseError> {
+        let vault = self.harness.vault().await;
+        let supply = self.harness.share_supply().await;
+
+        prop_assert_eq!(vault.deposit + vault.limit, VAULT_CAP);
+        prop_assert!(self.ledgers.iter().map(|ledger| ledger.deposited).sum::<u64>() <= VAULT_CAP);
+
+        let mut user_equity = 0;
+        for (user, ledger) in self.users.iter().zip(&self.ledgers) {
+            let account = self.harness.user_vault_account(user).await;
+            prop_assert_eq!(account.deposit, ledger.deposited);
+            prop_assert_eq!(account.deposit + account.deposit_limit, USER_CAP);
+            prop_assert_eq!(account.withdrawal, ledger.withdrawn);
+
+            // * everything the user took out or could still take out, at the last recognized balance
+            let shares = self.harness.share_balance(user).await;
+            let equity = vault.calculate_equity(shares, supply).unwrap();
+            user_equity += equity;
+            let reachable = ledger.withdrawn + self.harness.user_token_balance(user).await + equity;
+            prop_assert!(
+                reachable as i128 <= ledger.deposited as i128 + ledger.pnl_share + self.rounding_slack(),
+                "user can reach {} after depositing {} with a pnl share of {}",
+                reachable,
+                ledger.deposited,
+                ledger.pnl_share,
+            );
+        }
+
+        // * each user's equity is rounded down in favour of the vault
+        if supply > 0 {
+            prop_assert!(user_equity <= vault.total_equity);
+            prop_assert!(vault.total_equity - user_equity < USERS as u64);
+        }
+        Ok(())
+    }
+
+    // * each balance update can leave up to a unit per user unallotted, a later staker may inherit it
+    fn rounding_slack(&self) -> i128 {
+        (self.closed_epochs as i128 + 1) * USERS as i128
+    }
+
+    // * queue entries are packed, the key is taken by value
+    fn user_index(&self, user_vault_account: Pubkey) -> usize {
+        self.users
+            .iter()
+            .position(|user| self.harness.keys.user_vault_account(&user.pubkey()) == user_vault_account)
+            .unwrap()
+    }
+}
+
+proptest! {
+    // * every case starts a bank, keep the count low
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn handlers_keep_vault_accounting_consistent(ops in vec(op(), 1..24)) {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            let mut run = Run::new().await;
+            for op in &ops {
+                run.apply(op).await?;
+            }
+            Ok::<(), TestCaseError>(())
+        })?;
+    }
+}
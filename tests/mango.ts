@@ -15,6 +15,7 @@ import {
   connection,
   fetchAllUserVaultAccountForVault,
   fetchUnstakeReqAccount,
+  getProgramData,
 } from './utils';
 
 describe('Vault Tests', () => {
@@ -31,8 +32,38 @@ describe('Vault Tests', () => {
 
   let tx;
 
+  // * vault creation needs the protocol config and the manager approved as a creator
+  before(async () => {
+    const { config } = await getPda(vaultName);
+
+    // * the config is a singleton, it survives earlier runs against the same cluster
+    if ((await connection.getAccountInfo(config)) === null) {
+      await program.rpc.initializeConfig({
+        accounts: {
+          config,
+          admin: manager.publicKey,
+          program: program.programId,
+          programData: await getProgramData(),
+          systemProgram: SystemProgram.programId,
+        },
+        signers: [manager],
+      });
+    }
+
+    const configInfo = await program.account.protocolConfig.fetch(config);
+    if (!configInfo.creators.some((creator) => creator.equals(manager.publicKey))) {
+      await program.rpc.addVaultCreator(manager.publicKey, {
+        accounts: {
+          config,
+          admin: manager.publicKey,
+        },
+        signers: [manager],
+      });
+    }
+  });
+
   it('creates a vault', async () => {
-    const { vaultKey, vaultAuthorityBump, usdcTokenKey, vaultAuthority, vaultBump } = await getPda(vaultName);
+    const { config, vaultKey, vaultAuthorityBump, usdcTokenKey, shareMint, vaultAuthority, vaultBump } = await getPda(vaultName);
     const { quoteMint } = await getMangoData(vaultAuthority);
 
     const limit = new anchor.BN(5_000e6);
@@ -41,13 +72,15 @@ describe('Vault Tests', () => {
       program.rpc.createVault(vaultName, limit, vaultBump, vaultAuthorityBump, {
         accounts: {
           vault: vaultKey,
-          manager: manager.publicKey,
+          creator: manager.publicKey,
+          config,
           // payer: manager.publicKey,
           rent: SYSVAR_RENT_PUBKEY,
           stakeReq: stakereq.publicKey,
           unstakeReq: unstakereq.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
           tokenAccount: usdcTokenKey,
+          shareMint: shareMint,
           tokenMint: quoteMint,
           vaultPdaAuthority: vaultAuthority,
           systemProgram: SystemProgram.programId,
@@ -67,12 +100,13 @@ describe('Vault Tests', () => {
   });
 
   it('should not create vault with wrong credentials', async () => {
-    const { vaultKey, vaultAuthorityBump, usdcTokenKey, vaultAuthority, vaultBump } = await getPda(vaultName);
+    const { config, vaultKey, vaultAuthorityBump, usdcTokenKey, shareMint, vaultAuthority, vaultBump } = await getPda(vaultName);
     const {
       vaultKey: vaultKey2,
       vaultBump: vaultBump2,
       vaultAuthorityBump: vaultAuthorityBump2,
       usdcTokenKey: usdcTokenKey2,
+      shareMint: shareMint2,
       vaultAuthority: vaultAuthority2,
     } = await getPda(anotherVaultName);
     const { quoteMint } = await getMangoData(vaultAuthority);
@@ -84,12 +118,14 @@ describe('Vault Tests', () => {
       program.rpc.createVault(vaultName, limit, vaultBump, vaultAuthorityBump, {
         accounts: {
           vault: vaultKey,
-          manager: manager.publicKey,
+          creator: manager.publicKey,
+          config,
           rent: SYSVAR_RENT_PUBKEY,
           stakeReq: stakereq.publicKey,
           unstakeReq: unstakereq.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
           tokenAccount: usdcTokenKey,
+          shareMint: shareMint,
           tokenMint: quoteMint,
           vaultPdaAuthority: vaultAuthority,
           systemProgram: SystemProgram.programId,
@@ -113,12 +149,14 @@ describe('Vault Tests', () => {
         {
           accounts: {
             vault: vaultKey2,
-            manager: manager.publicKey,
+            creator: manager.publicKey,
+            config,
             rent: SYSVAR_RENT_PUBKEY,
             stakeReq: anotherStakereq.publicKey,
             unstakeReq: anotherUnstakereq.publicKey,
             tokenProgram: TOKEN_PROGRAM_ID,
             tokenAccount: usdcTokenKey2,
+            shareMint: shareMint2,
             tokenMint: quoteMint,
             vaultPdaAuthority: vaultAuthority2,
             systemProgram: SystemProgram.programId,
@@ -138,12 +176,14 @@ describe('Vault Tests', () => {
       program.rpc.createVault(anotherVaultName, limit, vaultBump2, vaultAuthorityBump2, {
         accounts: {
           vault: vaultKey, //! wrong vault keys
-          manager: manager.publicKey,
+          creator: manager.publicKey,
+          config,
           rent: SYSVAR_RENT_PUBKEY,
           stakeReq: anotherStakereq.publicKey,
           unstakeReq: anotherUnstakereq.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
           tokenAccount: usdcTokenKey2,
+          shareMint: shareMint2,
           tokenMint: quoteMint,
           vaultPdaAuthority: vaultAuthority2,
           systemProgram: SystemProgram.programId,
@@ -161,12 +201,14 @@ describe('Vault Tests', () => {
       program.rpc.createVault(anotherVaultName, limit, vaultBump2, vaultAuthorityBump2, {
         accounts: {
           vault: vaultKey2,
-          manager: manager.publicKey,
+          creator: manager.publicKey,
+          config,
           rent: SYSVAR_RENT_PUBKEY,
           stakeReq: anotherStakereq.publicKey,
           unstakeReq: anotherUnstakereq.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
           tokenAccount: usdcTokenKey, //! wrong token account
+          shareMint: shareMint2,
           tokenMint: quoteMint,
           vaultPdaAuthority: vaultAuthority2,
           systemProgram: SystemProgram.programId,
@@ -184,12 +226,14 @@ describe('Vault Tests', () => {
       program.rpc.createVault(anotherVaultName, limit, vaultBump2, vaultAuthorityBump2, {
         accounts: {
           vault: vaultKey2,
-          manager: manager.publicKey,
+          creator: manager.publicKey,
+          config,
           rent: SYSVAR_RENT_PUBKEY,
           stakeReq: anotherStakereq.publicKey,
           unstakeReq: anotherUnstakereq.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
           tokenAccount: usdcTokenKey2,
+          shareMint: shareMint2,
           tokenMint: quoteMint,
           vaultPdaAuthority: vaultAuthority2,
           systemProgram: SystemProgram.programId,
@@ -208,12 +252,13 @@ describe('Vault Tests', () => {
   });
 
   it('creates mango account', async () => {
-    const { vaultAuthority, vaultKey } = await getPda(vaultName);
+    const { config, vaultAuthority, vaultKey } = await getPda(vaultName);
     const { mangoAccountKey, mangoAccountNum, mangoBump, mGroup, mangoAddress } = await getMangoData(vaultAuthority);
 
     tx = await program.rpc.createMangoAccount(mangoAccountNum, mangoBump, {
         accounts: {
-          manager: manager.publicKey,
+          admin: manager.publicKey,
+          config,
           mangoGroupAi: mGroup.publicKey,
           mangoProgramId: mangoAddress,
           systemProgram: SystemProgram.programId,
@@ -250,6 +295,7 @@ describe('Vault Tests', () => {
   // })
 
   // it('creates a user vault account', async () => {
+  //   const { config, depositorAccessEntry } = await getPda(vaultName);
   //   const { vaultKey, vaultAuthority, depositorVaultKey, depositorUsdcTokenKey, depositorBump } = await getPda(vaultName);
   //   const { quoteMint } = await getMangoData(vaultAuthority);
 
@@ -276,6 +322,9 @@ describe('Vault Tests', () => {
   //       tokenMint: quoteMint,
   //       tokenProgram: TOKEN_PROGRAM_ID,
   //       userAccountAuthority: depositor.publicKey,
+  //       config,
+  //       gatewayToken: depositor.publicKey,
+  //       accessEntry: depositorAccessEntry,
   //       userVaultAccount: depositorVaultKey,
   //       vault: vaultKey,
   //       vaultPdaAuthority: vaultAuthority,
@@ -287,6 +336,7 @@ describe('Vault Tests', () => {
   // });
 
   // it('should not create user vault account with wrong credentials', async () => {
+  //   const { config, depositor2AccessEntry, depositorAccessEntry } = await getPda(vaultName);
   //   const {
   //     vaultKey,
   //     vaultAuthority,
@@ -308,6 +358,9 @@ describe('Vault Tests', () => {
   //         tokenMint: quoteMint,
   //         tokenProgram: TOKEN_PROGRAM_ID,
   //         userAccountAuthority: depositor2.publicKey,
+  //         config,
+  //         gatewayToken: depositor2.publicKey,
+  //         accessEntry: depositor2AccessEntry,
   //         userVaultAccount: depositor2VaultKey,
   //         vault: vaultKey,
   //         vaultPdaAuthority: vaultAuthority,
@@ -328,6 +381,9 @@ describe('Vault Tests', () => {
   //         tokenMint: quoteMint,
   //         tokenProgram: TOKEN_PROGRAM_ID,
   //         userAccountAuthority: depositor.publicKey, // ! wrong authority
+  //         config,
+  //         gatewayToken: depositor.publicKey,
+  //         accessEntry: depositorAccessEntry,
   //         userVaultAccount: depositor2VaultKey,
   //         vault: vaultKey,
   //         vaultPdaAuthority: vaultAuthority,
@@ -348,6 +404,9 @@ describe('Vault Tests', () => {
   //         tokenMint: quoteMint,
   //         tokenProgram: TOKEN_PROGRAM_ID,
   //         userAccountAuthority: depositor2.publicKey,
+  //         config,
+  //         gatewayToken: depositor2.publicKey,
+  //         accessEntry: depositor2AccessEntry,
   //         userVaultAccount: depositor2VaultKey,
   //         vault: vaultKey,
   //         vaultPdaAuthority: vaultAuthority,
@@ -370,6 +429,9 @@ describe('Vault Tests', () => {
   //         tokenMint: quoteMint,
   //         tokenProgram: TOKEN_PROGRAM_ID,
   //         userAccountAuthority: depositor2.publicKey,
+  //         config,
+  //         gatewayToken: depositor2.publicKey,
+  //         accessEntry: depositor2AccessEntry,
   //         userVaultAccount: depositor2VaultKey,
   //         vault: wrongVaultKey, // ! wrong vault
   //         vaultPdaAuthority: wrongVA, // ! wrong vault authority
@@ -390,6 +452,9 @@ describe('Vault Tests', () => {
   //         tokenMint: quoteMint,
   //         tokenProgram: TOKEN_PROGRAM_ID,
   //         userAccountAuthority: depositor2.publicKey,
+  //         config,
+  //         gatewayToken: depositor2.publicKey,
+  //         accessEntry: depositor2AccessEntry,
   //         userVaultAccount: depositor2VaultKey,
   //         vault: vaultKey,
   //         vaultPdaAuthority: vaultAuthority,
@@ -402,11 +467,14 @@ describe('Vault Tests', () => {
   // });
 
   // it('deposit to user vault account', async () => {
+  //   const { config } = await getPda(vaultName);
   //   const { vaultKey, vaultAuthority, depositorVaultKey, depositorUsdcTokenKey } = await getPda(vaultName);
 
   //   tx = await program.rpc.depositToUserVaultAccount(new anchor.BN(10e6), {
   //     accounts: {
   //       authority: depositor.publicKey,
+  //       config,
+  //       gatewayToken: depositor.publicKey,
   //       tokenProgram: TOKEN_PROGRAM_ID,
   //       userAta: depositorAta,
   //       userVaultAccount: depositorVaultKey,
@@ -433,6 +501,7 @@ describe('Vault Tests', () => {
   // });
 
   // it('should not deposit to wrong user vault account', async () => {
+  //   const { config } = await getPda(vaultName);
   //   // todo: deposit over user account limit should fail
 
   //   const {
@@ -449,6 +518,8 @@ describe('Vault Tests', () => {
   //     program.rpc.depositToUserVaultAccount(new anchor.BN(10e6), {
   //       accounts: {
   //         authority: depositor2.publicKey,
+  //         config,
+  //         gatewayToken: depositor2.publicKey,
   //         tokenProgram: TOKEN_PROGRAM_ID,
   //         userAta: depositor2Ata,
   //         userVaultAccount: depositor2VaultKey,
@@ -464,6 +535,8 @@ describe('Vault Tests', () => {
   //     program.rpc.depositToUserVaultAccount(new anchor.BN(10e6), {
   //       accounts: {
   //         authority: depositor2.publicKey,
+  //         config,
+  //         gatewayToken: depositor2.publicKey,
   //         tokenProgram: TOKEN_PROGRAM_ID,
   //         userAta: depositor2Ata,
   //         userVaultAccount: depositor2VaultKey,
@@ -479,6 +552,8 @@ describe('Vault Tests', () => {
   //     program.rpc.depositToUserVaultAccount(new anchor.BN(10e6), {
   //       accounts: {
   //         authority: depositor2.publicKey,
+  //         config,
+  //         gatewayToken: depositor2.publicKey,
   //         tokenProgram: TOKEN_PROGRAM_ID,
   //         userAta: depositor2Ata,
   //         userVaultAccount: depositor2VaultKey,
@@ -494,6 +569,8 @@ describe('Vault Tests', () => {
   //     program.rpc.depositToUserVaultAccount(new anchor.BN(10e6), {
   //       accounts: {
   //         authority: depositor2.publicKey,
+  //         config,
+  //         gatewayToken: depositor2.publicKey,
   //         tokenProgram: TOKEN_PROGRAM_ID,
   //         userAta: depositor2Ata,
   //         userVaultAccount: depositor2VaultKey,
@@ -520,12 +597,16 @@ describe('Vault Tests', () => {
   // });
 
   // it('requests to stake', async () => {
+  //   const { config, depositorAccessEntry } = await getPda(vaultName);
   //   const { depositorVaultKey } = await getPda(vaultName);
 
   //   tx = await expect(
   //     program.rpc.requestToStake(new anchor.BN(5e6), false, {
   //       accounts: {
   //         authority: depositor.publicKey,
+  //         config,
+  //         gatewayToken: depositor.publicKey,
+  //         accessEntry: depositorAccessEntry,
   //         userVaultAccount: depositorVaultKey,
   //         vaultStakeReqAccount: stakereq.publicKey,
   //       },
@@ -892,6 +973,7 @@ describe('Vault Tests', () => {
   // });
 
   // it('stake max, cancel stake', async () => {
+  //   const { config, depositor2AccessEntry, depositorAccessEntry } = await getPda(vaultName);
   //   const {
   //     depositorVaultKey,
   //     vaultKey,
@@ -918,6 +1000,9 @@ describe('Vault Tests', () => {
   //     program.rpc.requestToStake(amountToStake, false, {
   //       accounts: {
   //         authority: depositor.publicKey,
+  //         config,
+  //         gatewayToken: depositor.publicKey,
+  //         accessEntry: depositorAccessEntry,
   //         userVaultAccount: depositorVaultKey,
   //         vaultStakeReqAccount: stakereq.publicKey,
   //       },
@@ -930,6 +1015,9 @@ describe('Vault Tests', () => {
   //     program.rpc.requestToStake(amountToStake, true, {
   //       accounts: {
   //         authority: depositor2.publicKey,
+  //         config,
+  //         gatewayToken: depositor2.publicKey,
+  //         accessEntry: depositor2AccessEntry,
   //         userVaultAccount: depositor2VaultKey,
   //         vaultStakeReqAccount: stakereq.publicKey,
   //       },
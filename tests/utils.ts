@@ -54,7 +54,18 @@ export const fetchAllUserVaultAccountForVault = async (vaultKey: PublicKey) => {
 
 // export const vaultName = Keypair.generate().publicKey.toString().slice(0, 7);
 
+// * program data account, its upgrade authority bootstraps the protocol config
+export const getProgramData = async () => {
+  let [programData] = await PublicKey.findProgramAddress(
+    [program.programId.toBuffer()],
+    new PublicKey('BPFLoaderUpgradeab1e11111111111111111111111')
+  );
+  return programData;
+};
+
 export const getPda = async (vaultName: string) => {
+  let [config] = await PublicKey.findProgramAddress([Buffer.from('config')], program.programId);
+
   let [vaultKey, vaultBump] = await PublicKey.findProgramAddress(
     [Buffer.from(vaultName), Buffer.from('vault')],
     program.programId
@@ -65,6 +76,11 @@ export const getPda = async (vaultName: string) => {
     program.programId
   );
 
+  let [shareMint] = await PublicKey.findProgramAddress(
+    [vaultKey.toBuffer(), Buffer.from('shares')],
+    program.programId
+  );
+
   let [vaultAuthority, vaultAuthorityBump] = await PublicKey.findProgramAddress(
     [vaultKey.toBuffer(), Buffer.from('pdaauthority')],
     program.programId
@@ -85,11 +101,21 @@ export const getPda = async (vaultName: string) => {
     program.programId
   );
 
+  let [depositorShareTokenKey] = await PublicKey.findProgramAddress(
+    [vaultKey.toBuffer(), depositor.publicKey.toBuffer(), Buffer.from('shares')],
+    program.programId
+  );
+
   let [depositorVaultKey, depositorBump] = await PublicKey.findProgramAddress(
     [vaultKey.toBuffer(), depositor.publicKey.toBuffer()],
     program.programId
   );
 
+  let [depositorAccessEntry] = await PublicKey.findProgramAddress(
+    [vaultKey.toBuffer(), depositor.publicKey.toBuffer(), Buffer.from('access')],
+    program.programId
+  );
+
   // * Depositor 2 credentials
 
   let [depositor2UsdcTokenKey] = await PublicKey.findProgramAddress(
@@ -97,14 +123,26 @@ export const getPda = async (vaultName: string) => {
     program.programId
   );
 
+  let [depositor2ShareTokenKey] = await PublicKey.findProgramAddress(
+    [vaultKey.toBuffer(), depositor2.publicKey.toBuffer(), Buffer.from('shares')],
+    program.programId
+  );
+
   let [depositor2VaultKey, depositor2Bump] = await PublicKey.findProgramAddress(
     [vaultKey.toBuffer(), depositor2.publicKey.toBuffer()],
     program.programId
   );
 
+  let [depositor2AccessEntry] = await PublicKey.findProgramAddress(
+    [vaultKey.toBuffer(), depositor2.publicKey.toBuffer(), Buffer.from('access')],
+    program.programId
+  );
+
   return {
+    config,
     vaultKey,
     usdcTokenKey,
+    shareMint,
     vaultAuthority,
     vaultAuthorityBump,
     vaultBump,
@@ -115,8 +153,12 @@ export const getPda = async (vaultName: string) => {
     vaultAuthorityAta,
     depositorUsdcTokenKey,
     depositor2UsdcTokenKey,
+    depositorShareTokenKey,
+    depositor2ShareTokenKey,
     depositorVaultKey,
     depositor2VaultKey,
+    depositorAccessEntry,
+    depositor2AccessEntry,
   };
 };
 